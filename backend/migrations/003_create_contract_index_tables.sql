@@ -0,0 +1,31 @@
+-- Contract state mirrored from on-chain events by the event indexer
+
+CREATE TABLE contract_players (
+    wallet_address VARCHAR(56) PRIMARY KEY,
+    wins INTEGER NOT NULL DEFAULT 0,
+    joined_ledger BIGINT,          -- Ledger of the player_joined event
+    last_event_ledger BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE contract_events (
+    event_id VARCHAR(64) PRIMARY KEY, -- RPC event id, unique per contract event
+    contract_id VARCHAR(56) NOT NULL,
+    ledger BIGINT NOT NULL,
+    event_type VARCHAR(50) NOT NULL, -- 'player_joined', 'win_added'
+    player_wallet_address VARCHAR(56) NOT NULL,
+    wins INTEGER,                    -- Win total carried by win_added
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- Where each contract's indexer left off
+CREATE TABLE contract_indexer_cursors (
+    contract_id VARCHAR(56) PRIMARY KEY,
+    cursor VARCHAR(64) NOT NULL,
+    latest_ledger BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX idx_contract_players_wins ON contract_players(wins DESC);
+CREATE INDEX idx_contract_events_player ON contract_events(player_wallet_address);
+CREATE INDEX idx_contract_events_ledger ON contract_events(ledger);
@@ -0,0 +1,33 @@
+-- Outbox of signed contract transactions, worked by the contract queue
+
+CREATE TABLE contract_operations (
+    id VARCHAR(36) PRIMARY KEY,          -- UUID as string, the operation id handed to clients
+    contract_id VARCHAR(56) NOT NULL,
+    rpc_url TEXT NOT NULL,
+    function_name VARCHAR(64) NOT NULL,
+    source_account VARCHAR(56) NOT NULL,
+    signed_xdr TEXT,
+    priority SMALLINT NOT NULL DEFAULT 1, -- OperationPriority: 0 low .. 3 critical
+
+    -- Lifecycle
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- 'pending', 'submitted', 'confirmed', 'failed'
+    attempt_count INTEGER NOT NULL DEFAULT 0,      -- sendTransaction attempts so far
+    max_retries INTEGER NOT NULL DEFAULT 3,
+    next_retry_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(), -- When a worker may next pick the row up
+    last_error TEXT,
+
+    -- Outcome
+    transaction_hash VARCHAR(64),
+    ledger BIGINT,
+    contract_error INTEGER,
+
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Workers scan unfinished rows by priority, then due time
+CREATE INDEX idx_contract_operations_due
+    ON contract_operations(priority DESC, next_retry_at, created_at)
+    WHERE status IN ('pending', 'submitted');
+CREATE INDEX idx_contract_operations_source ON contract_operations(source_account);
@@ -0,0 +1,37 @@
+-- Anti-cheat: one result per game session, and a review queue for doubtful submissions
+
+-- Keep the first result stored for any session submitted more than once
+DELETE FROM game_instances a
+USING game_instances b
+WHERE a.game_session_id = b.game_session_id AND a.id > b.id;
+
+DROP INDEX idx_game_instances_session_id;
+ALTER TABLE game_instances
+    ADD CONSTRAINT game_instances_game_session_id_key UNIQUE (game_session_id);
+
+-- Rate limiting looks at a wallet's latest submissions
+CREATE INDEX idx_game_instances_wallet_created_at
+    ON game_instances(player_wallet_address, created_at DESC);
+
+CREATE TABLE suspicious_results (
+    id SERIAL PRIMARY KEY,
+    game_instance_id INTEGER REFERENCES game_instances(id) ON DELETE SET NULL, -- NULL when the result was rejected
+
+    -- The submission as received
+    game_session_id VARCHAR(255) NOT NULL,
+    player_wallet_address VARCHAR(255) NOT NULL,
+    player_result VARCHAR(10) NOT NULL,
+    player_score INTEGER NOT NULL,
+    opponent_score INTEGER NOT NULL,
+    duration_seconds REAL NOT NULL,
+    game_mode VARCHAR(50),
+
+    reasons TEXT[] NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- 'rejected', 'pending', 'cleared', 'confirmed'
+    review_note TEXT,
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_suspicious_results_status ON suspicious_results(status, created_at);
+CREATE INDEX idx_suspicious_results_wallet ON suspicious_results(player_wallet_address);
@@ -0,0 +1,16 @@
+-- Server-issued game sessions: a result is only accepted for an active session of the submitting wallet
+
+CREATE TABLE game_sessions (
+    id UUID PRIMARY KEY,
+    player_wallet_address VARCHAR(56) NOT NULL,
+    game_mode VARCHAR(50) NOT NULL,
+    config_hash VARCHAR(64) NOT NULL, -- MatchRules the game reported running
+    status VARCHAR(20) NOT NULL DEFAULT 'active', -- 'active', 'finished', 'expired'
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMP WITH TIME ZONE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+-- The expiry sweep only looks at sessions still in play
+CREATE INDEX idx_game_sessions_active_expiry ON game_sessions(expires_at) WHERE status = 'active';
+CREATE INDEX idx_game_sessions_wallet ON game_sessions(player_wallet_address, started_at DESC);
@@ -0,0 +1,9 @@
+-- Game history pages are read in (created_at, id) order, newest or oldest first
+
+DROP INDEX idx_game_instances_created_at;
+CREATE INDEX idx_game_instances_created_at_id ON game_instances(created_at, id);
+
+-- Also serves the rate limit's latest-submission lookups
+DROP INDEX idx_game_instances_wallet_created_at;
+CREATE INDEX idx_game_instances_wallet_created_at_id
+    ON game_instances(player_wallet_address, created_at DESC, id DESC);
@@ -0,0 +1,6 @@
+-- Contract calls the backend signs itself with the oracle (admin) key
+--
+-- Oracle operations have no signed_xdr; the worker builds and signs the call
+-- when it sends it, so every attempt uses the account's current sequence number.
+
+ALTER TABLE contract_operations ADD COLUMN oracle_call TEXT; -- JSON OracleCall
@@ -0,0 +1,9 @@
+-- Player ratings mirrored from the contract's rating_changed events
+--
+-- player_joined sets the starting rating and each rating_changed replaces it.
+-- Players migrated from the version 1 contract have no events, so stay NULL.
+
+ALTER TABLE contract_players ADD COLUMN rating INTEGER;
+ALTER TABLE contract_events ADD COLUMN rating INTEGER; -- Rating carried by player_joined or rating_changed
+
+CREATE INDEX idx_contract_players_rating ON contract_players(rating DESC NULLS LAST);
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct User {
@@ -47,4 +48,108 @@ pub struct NewGameInstance {
     pub opponent_score: i32,
     pub duration_seconds: f32,
     pub game_mode: String,
-}
\ No newline at end of file
+}
+/// Player state mirrored from contract events
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContractPlayerRecord {
+    pub wallet_address: String,
+    pub wins: i32,
+    pub rating: Option<i32>,
+    pub joined_ledger: Option<i64>,
+    pub last_event_ledger: i64,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A decoded contract event ready to be indexed
+#[derive(Debug, Clone)]
+pub struct NewContractEvent {
+    pub event_id: String,
+    pub ledger: i64,
+    pub event_type: String, // 'player_joined', 'win_added' or 'rating_changed'
+    pub player_wallet_address: String,
+    pub wins: Option<i32>,
+    pub rating: Option<i32>,
+}
+
+/// Row of the contract_operations outbox
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContractOperationRecord {
+    pub id: String,
+    pub contract_id: String,
+    pub rpc_url: String,
+    pub function_name: String,
+    pub source_account: String,
+    pub signed_xdr: Option<String>,
+    pub oracle_call: Option<String>,
+    pub priority: i16,
+    pub status: String,
+    pub attempt_count: i32,
+    pub max_retries: i32,
+    pub next_retry_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub transaction_hash: Option<String>,
+    pub ledger: Option<i64>,
+    pub contract_error: Option<i32>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewContractOperation {
+    pub id: String,
+    pub contract_id: String,
+    pub rpc_url: String,
+    pub function_name: String,
+    pub source_account: String,
+    pub signed_xdr: Option<String>,
+    pub oracle_call: Option<String>,
+    pub priority: i16,
+    pub max_retries: i32,
+}
+
+/// A game result held for review, or rejected, by the anti-cheat checks
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SuspiciousResultRecord {
+    pub id: i32,
+    pub game_instance_id: Option<i32>,
+    pub game_session_id: String,
+    pub player_wallet_address: String,
+    pub player_result: String,
+    pub player_score: i32,
+    pub opponent_score: i32,
+    pub duration_seconds: f32,
+    pub game_mode: Option<String>,
+    pub reasons: Vec<String>,
+    pub status: String, // 'rejected', 'pending', 'cleared' or 'confirmed'
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewSuspiciousResult {
+    pub game_instance_id: Option<i32>,
+    pub game_session_id: String,
+    pub player_wallet_address: String,
+    pub player_result: String,
+    pub player_score: i32,
+    pub opponent_score: i32,
+    pub duration_seconds: f32,
+    pub game_mode: Option<String>,
+    pub reasons: Vec<String>,
+    pub status: String,
+}
+
+/// A match the server issued, from start until it's finished or expires
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct GameSessionRecord {
+    pub id: Uuid,
+    pub player_wallet_address: String,
+    pub game_mode: String,
+    pub config_hash: String,
+    pub status: String, // 'active', 'finished' or 'expired'
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+}
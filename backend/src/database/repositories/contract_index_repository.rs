@@ -0,0 +1,140 @@
+use crate::database::connection::DbPool;
+use crate::database::models::{ContractPlayerRecord, NewContractEvent};
+use sqlx::{Error as SqlxError};
+
+pub struct ContractIndexRepository;
+
+impl ContractIndexRepository {
+    /// Cursor the indexer last stored for a contract
+    pub async fn get_cursor(
+        pool: &DbPool,
+        contract_id: &str,
+    ) -> Result<Option<String>, SqlxError> {
+        let row = sqlx::query!(
+            "SELECT cursor FROM contract_indexer_cursors WHERE contract_id = $1",
+            contract_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|row| row.cursor))
+    }
+
+    /// Store a page of events and the cursor after it in one transaction
+    ///
+    /// Events already stored are skipped, so replaying a page after a crash
+    /// doesn't count anything twice. Returns the number of new events.
+    pub async fn record_page(
+        pool: &DbPool,
+        contract_id: &str,
+        events: &[NewContractEvent],
+        cursor: &str,
+        latest_ledger: i64,
+    ) -> Result<u64, SqlxError> {
+        let mut tx = pool.begin().await?;
+        let mut inserted = 0;
+
+        for event in events {
+            let result = sqlx::query!(
+                r#"
+                INSERT INTO contract_events (
+                    event_id, contract_id, ledger, event_type, player_wallet_address, wins, rating, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+                ON CONFLICT (event_id) DO NOTHING
+                "#,
+                event.event_id,
+                contract_id,
+                event.ledger,
+                event.event_type,
+                event.player_wallet_address,
+                event.wins,
+                event.rating
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                continue;
+            }
+            inserted += 1;
+
+            // win_added carries the player's new total, so keep the highest seen;
+            // ratings go both ways, and events arrive in order, so the latest wins
+            sqlx::query!(
+                r#"
+                INSERT INTO contract_players (wallet_address, wins, rating, joined_ledger, last_event_ledger, updated_at)
+                VALUES ($1, COALESCE($2, 0), $5, CASE WHEN $3 = 'player_joined' THEN $4::BIGINT END, $4, NOW())
+                ON CONFLICT (wallet_address) DO UPDATE SET
+                    wins = GREATEST(contract_players.wins, EXCLUDED.wins),
+                    rating = COALESCE(EXCLUDED.rating, contract_players.rating),
+                    joined_ledger = COALESCE(EXCLUDED.joined_ledger, contract_players.joined_ledger),
+                    last_event_ledger = GREATEST(contract_players.last_event_ledger, EXCLUDED.last_event_ledger),
+                    updated_at = NOW()
+                "#,
+                event.player_wallet_address,
+                event.wins,
+                event.event_type,
+                event.ledger,
+                event.rating
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO contract_indexer_cursors (contract_id, cursor, latest_ledger, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (contract_id) DO UPDATE SET
+                cursor = EXCLUDED.cursor,
+                latest_ledger = EXCLUDED.latest_ledger,
+                updated_at = NOW()
+            "#,
+            contract_id,
+            cursor,
+            latest_ledger
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    pub async fn get_player(
+        pool: &DbPool,
+        wallet_address: &str,
+    ) -> Result<Option<ContractPlayerRecord>, SqlxError> {
+        sqlx::query_as!(
+            ContractPlayerRecord,
+            r#"
+            SELECT wallet_address, wins, rating, joined_ledger, last_event_ledger, updated_at
+            FROM contract_players
+            WHERE wallet_address = $1
+            "#,
+            wallet_address
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Indexed players ranked by rating, then wins
+    pub async fn get_leaderboard(
+        pool: &DbPool,
+        limit: i64,
+    ) -> Result<Vec<ContractPlayerRecord>, SqlxError> {
+        sqlx::query_as!(
+            ContractPlayerRecord,
+            r#"
+            SELECT wallet_address, wins, rating, joined_ledger, last_event_ledger, updated_at
+            FROM contract_players
+            ORDER BY rating DESC NULLS LAST, wins DESC, joined_ledger ASC NULLS LAST
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use crate::database::connection::DbPool;
+use crate::database::models::{ContractOperationRecord, NewContractOperation};
+use sqlx::{Error as SqlxError};
+
+pub struct ContractOperationRepository;
+
+impl ContractOperationRepository {
+    pub async fn enqueue(
+        pool: &DbPool,
+        operation: &NewContractOperation,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO contract_operations (
+                id, contract_id, rpc_url, function_name, source_account, signed_xdr,
+                oracle_call, priority, max_retries, status, next_retry_at, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'pending', NOW(), NOW(), NOW())
+            "#,
+            operation.id,
+            operation.contract_id,
+            operation.rpc_url,
+            operation.function_name,
+            operation.source_account,
+            operation.signed_xdr,
+            operation.oracle_call,
+            operation.priority,
+            operation.max_retries
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Claim up to `limit` due operations, highest priority first
+    ///
+    /// Rows locked by another worker are skipped rather than waited on. Claimed
+    /// rows are pushed back to `lease_until`, so a worker that dies mid-send
+    /// only delays them; pending rows also count the send attempt here.
+    pub async fn claim_due(
+        pool: &DbPool,
+        limit: i64,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Vec<ContractOperationRecord>, SqlxError> {
+        let mut claimed = sqlx::query_as!(
+            ContractOperationRecord,
+            r#"
+            UPDATE contract_operations
+            SET attempt_count = attempt_count + CASE WHEN status = 'pending' THEN 1 ELSE 0 END,
+                next_retry_at = $2,
+                updated_at = NOW()
+            WHERE id IN (
+                SELECT id FROM contract_operations
+                WHERE status IN ('pending', 'submitted') AND next_retry_at <= NOW()
+                ORDER BY priority DESC, next_retry_at, created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, contract_id, rpc_url, function_name, source_account, signed_xdr,
+                      oracle_call, priority, status, attempt_count, max_retries, next_retry_at, last_error,
+                      transaction_hash, ledger, contract_error, submitted_at, created_at, updated_at
+            "#,
+            limit,
+            lease_until
+        )
+        .fetch_all(pool)
+        .await?;
+
+        // RETURNING doesn't keep the subquery's order
+        claimed.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.created_at.cmp(&b.created_at)));
+        Ok(claimed)
+    }
+
+    pub async fn mark_submitted(
+        pool: &DbPool,
+        id: &str,
+        transaction_hash: &str,
+        next_poll_at: DateTime<Utc>,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE contract_operations
+            SET status = 'submitted', transaction_hash = $2, submitted_at = NOW(),
+                next_retry_at = $3, last_error = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            transaction_hash,
+            next_poll_at
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Leave the status alone and pick the row up again at `next_retry_at`
+    pub async fn reschedule(
+        pool: &DbPool,
+        id: &str,
+        next_retry_at: DateTime<Utc>,
+        last_error: Option<&str>,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE contract_operations
+            SET next_retry_at = $2, last_error = COALESCE($3, last_error), updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            next_retry_at,
+            last_error
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_confirmed(
+        pool: &DbPool,
+        id: &str,
+        transaction_hash: &str,
+        ledger: i64,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE contract_operations
+            SET status = 'confirmed', transaction_hash = $2, ledger = $3, last_error = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            transaction_hash,
+            ledger
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_failed(
+        pool: &DbPool,
+        id: &str,
+        transaction_hash: Option<&str>,
+        error: &str,
+        contract_error: Option<i32>,
+    ) -> Result<(), SqlxError> {
+        sqlx::query!(
+            r#"
+            UPDATE contract_operations
+            SET status = 'failed', transaction_hash = COALESCE($2, transaction_hash),
+                last_error = $3, contract_error = $4, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            transaction_hash,
+            error,
+            contract_error
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(
+        pool: &DbPool,
+        id: &str,
+    ) -> Result<Option<ContractOperationRecord>, SqlxError> {
+        sqlx::query_as!(
+            ContractOperationRecord,
+            r#"
+            SELECT id, contract_id, rpc_url, function_name, source_account, signed_xdr,
+                   oracle_call, priority, status, attempt_count, max_retries, next_retry_at, last_error,
+                   transaction_hash, ledger, contract_error, submitted_at, created_at, updated_at
+            FROM contract_operations
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
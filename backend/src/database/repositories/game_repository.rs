@@ -1,14 +1,17 @@
 use crate::database::connection::DbPool;
 use crate::database::models::NewGameInstance;
-use sqlx::{Error as SqlxError};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, PgExecutor};
 use bigdecimal::ToPrimitive;
-use shared::dto::game::{GameInstance, PlayerStats, LeaderboardEntry};
+use shared::dto::game::{
+    GameCursor, GameHistoryFilter, GameInstance, GameSort, LeaderboardEntry, PlayerStats,
+};
 
 pub struct GameRepository;
 
 impl GameRepository {
     pub async fn create_game_instance(
-        pool: &DbPool,
+        executor: impl PgExecutor<'_>,
         new_game: NewGameInstance,
     ) -> Result<GameInstance, SqlxError> {
         let row = sqlx::query!(
@@ -31,7 +34,7 @@ impl GameRepository {
             new_game.duration_seconds,
             new_game.game_mode
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(GameInstance {
@@ -48,50 +51,102 @@ impl GameRepository {
         })
     }
 
-    pub async fn get_player_games(
+    /// A page of games, optionally for one wallet, matching `filter` in its sort order
+    /// Starts after `after` when given, otherwise skips `offset` games
+    pub async fn list_games(
         pool: &DbPool,
-        wallet_address: &str,
+        wallet_address: Option<&str>,
+        filter: &GameHistoryFilter,
+        after: Option<GameCursor>,
         limit: i64,
+        offset: i64,
     ) -> Result<Vec<GameInstance>, SqlxError> {
-        let rows = sqlx::query!(
-            r#"
-            SELECT id, user_id, game_session_id, player_username, player_wallet_address,
-                   player_result, player_score, opponent_score, duration_seconds, game_mode, created_at
-            FROM game_instances
-            WHERE player_wallet_address = $1
-            ORDER BY created_at DESC
-            LIMIT $2
-            "#,
-            wallet_address,
-            limit
-        )
-        .fetch_all(pool)
-        .await?;
+        let (after_created_at, after_id) = after.map(|c| (c.created_at, c.id)).unzip();
 
-        Ok(rows
-            .into_iter()
-            .map(|row| GameInstance {
-                id: row.id,
-                game_session_id: row.game_session_id,
-                player_username: row.player_username,
-                player_wallet_address: row.player_wallet_address,
-                player_result: row.player_result,
-                player_score: row.player_score,
-                opponent_score: row.opponent_score,
-                duration_seconds: row.duration_seconds,
-                game_mode: row.game_mode.unwrap_or_else(|| "single_player".to_string()),
-                created_at: row.created_at,
-            })
-            .collect())
+        match filter.sort {
+            GameSort::Newest => {
+                sqlx::query_as!(
+                    GameInstance,
+                    r#"
+                    SELECT id, game_session_id, player_username, player_wallet_address,
+                           player_result, player_score, opponent_score, duration_seconds,
+                           COALESCE(game_mode, 'single_player') as "game_mode!", created_at
+                    FROM game_instances
+                    WHERE ($1::text IS NULL OR player_wallet_address = $1)
+                      AND ($2::text IS NULL OR player_result = $2)
+                      AND ($3::text IS NULL OR game_mode = $3)
+                      AND ($4::timestamptz IS NULL OR created_at >= $4)
+                      AND ($5::timestamptz IS NULL OR created_at < $5)
+                      AND ($6::timestamptz IS NULL OR (created_at, id) < ($6, $7::int4))
+                    ORDER BY created_at DESC, id DESC
+                    LIMIT $8 OFFSET $9
+                    "#,
+                    wallet_address,
+                    filter.result,
+                    filter.game_mode,
+                    filter.from,
+                    filter.to,
+                    after_created_at,
+                    after_id,
+                    limit,
+                    offset
+                )
+                .fetch_all(pool)
+                .await
+            }
+            GameSort::Oldest => {
+                sqlx::query_as!(
+                    GameInstance,
+                    r#"
+                    SELECT id, game_session_id, player_username, player_wallet_address,
+                           player_result, player_score, opponent_score, duration_seconds,
+                           COALESCE(game_mode, 'single_player') as "game_mode!", created_at
+                    FROM game_instances
+                    WHERE ($1::text IS NULL OR player_wallet_address = $1)
+                      AND ($2::text IS NULL OR player_result = $2)
+                      AND ($3::text IS NULL OR game_mode = $3)
+                      AND ($4::timestamptz IS NULL OR created_at >= $4)
+                      AND ($5::timestamptz IS NULL OR created_at < $5)
+                      AND ($6::timestamptz IS NULL OR (created_at, id) > ($6, $7::int4))
+                    ORDER BY created_at ASC, id ASC
+                    LIMIT $8 OFFSET $9
+                    "#,
+                    wallet_address,
+                    filter.result,
+                    filter.game_mode,
+                    filter.from,
+                    filter.to,
+                    after_created_at,
+                    after_id,
+                    limit,
+                    offset
+                )
+                .fetch_all(pool)
+                .await
+            }
+        }
     }
 
-    pub async fn count_player_games(
+    /// How many games match `filter`, regardless of paging
+    pub async fn count_games(
         pool: &DbPool,
-        wallet_address: &str,
+        wallet_address: Option<&str>,
+        filter: &GameHistoryFilter,
     ) -> Result<i64, SqlxError> {
         let row = sqlx::query!(
-            "SELECT COUNT(*) as count FROM game_instances WHERE player_wallet_address = $1",
-            wallet_address
+            r#"
+            SELECT COUNT(*) as count FROM game_instances
+            WHERE ($1::text IS NULL OR player_wallet_address = $1)
+              AND ($2::text IS NULL OR player_result = $2)
+              AND ($3::text IS NULL OR game_mode = $3)
+              AND ($4::timestamptz IS NULL OR created_at >= $4)
+              AND ($5::timestamptz IS NULL OR created_at < $5)
+            "#,
+            wallet_address,
+            filter.result,
+            filter.game_mode,
+            filter.from,
+            filter.to
         )
         .fetch_one(pool)
         .await?;
@@ -144,40 +199,6 @@ impl GameRepository {
         })
     }
 
-    pub async fn get_recent_games(
-        pool: &DbPool,
-        limit: i64,
-    ) -> Result<Vec<GameInstance>, SqlxError> {
-        let rows = sqlx::query!(
-            r#"
-            SELECT id, user_id, game_session_id, player_username, player_wallet_address,
-                   player_result, player_score, opponent_score, duration_seconds, game_mode, created_at
-            FROM game_instances
-            ORDER BY created_at DESC
-            LIMIT $1
-            "#,
-            limit
-        )
-        .fetch_all(pool)
-        .await?;
-
-        Ok(rows
-            .into_iter()
-            .map(|row| GameInstance {
-                id: row.id,
-                game_session_id: row.game_session_id,
-                player_username: row.player_username,
-                player_wallet_address: row.player_wallet_address,
-                player_result: row.player_result,
-                player_score: row.player_score,
-                opponent_score: row.opponent_score,
-                duration_seconds: row.duration_seconds,
-                game_mode: row.game_mode.unwrap_or_else(|| "single_player".to_string()),
-                created_at: row.created_at,
-            })
-            .collect())
-    }
-
     pub async fn count_total_games(pool: &DbPool) -> Result<i64, SqlxError> {
         let row = sqlx::query!("SELECT COUNT(*) as count FROM game_instances")
             .fetch_one(pool)
@@ -186,25 +207,34 @@ impl GameRepository {
         Ok(row.count.unwrap_or(0))
     }
 
+    /// Players ranked by their indexed on-chain rating, then wins
+    /// Players who never joined the contract have no rating and rank after those who did
     pub async fn get_leaderboard(
         pool: &DbPool,
         limit: i64,
+        offset: i64,
     ) -> Result<Vec<LeaderboardEntry>, SqlxError> {
         let rows = sqlx::query!(
             r#"
             SELECT
-                ROW_NUMBER() OVER (ORDER BY COUNT(*) FILTER (WHERE player_result = 'Win') DESC, COUNT(*) DESC) as rank,
-                player_username,
-                player_wallet_address,
-                COUNT(*) FILTER (WHERE player_result = 'Win') as wins,
+                ROW_NUMBER() OVER (
+                    ORDER BY MAX(cp.rating) DESC NULLS LAST, COUNT(*) FILTER (WHERE gi.player_result = 'Win') DESC,
+                        COUNT(*) DESC, gi.player_wallet_address
+                ) as rank,
+                gi.player_username,
+                gi.player_wallet_address,
+                MAX(cp.rating) as rating,
+                COUNT(*) FILTER (WHERE gi.player_result = 'Win') as wins,
                 COUNT(*) as total_games,
-                COALESCE(MAX(player_score), 0) as best_score
-            FROM game_instances
-            GROUP BY player_username, player_wallet_address
-            ORDER BY wins DESC, total_games DESC
-            LIMIT $1
+                COALESCE(MAX(gi.player_score), 0) as best_score
+            FROM game_instances gi
+            LEFT JOIN contract_players cp ON cp.wallet_address = gi.player_wallet_address
+            GROUP BY gi.player_username, gi.player_wallet_address
+            ORDER BY rating DESC NULLS LAST, wins DESC, total_games DESC, gi.player_wallet_address
+            LIMIT $1 OFFSET $2
             "#,
-            limit
+            limit,
+            offset
         )
         .fetch_all(pool)
         .await?;
@@ -224,6 +254,7 @@ impl GameRepository {
                     rank: row.rank.unwrap_or(0),
                     username: row.player_username,
                     wallet_address: row.player_wallet_address,
+                    rating: row.rating,
                     wins,
                     total_games,
                     win_rate,
@@ -242,4 +273,36 @@ impl GameRepository {
 
         Ok(row.count.unwrap_or(0))
     }
-}
\ No newline at end of file
+
+    /// Results a wallet submitted since `since`, stored or rejected, and when it last had one stored
+    pub async fn recent_submissions(
+        pool: &DbPool,
+        wallet_address: &str,
+        since: DateTime<Utc>,
+    ) -> Result<(i64, Option<DateTime<Utc>>), SqlxError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT (SELECT COUNT(*) FROM game_instances
+                    WHERE player_wallet_address = $1 AND created_at >= $2)
+                 + (SELECT COUNT(*) FROM suspicious_results
+                    WHERE player_wallet_address = $1 AND status = 'rejected' AND created_at >= $2) as count,
+                   (SELECT MAX(created_at) FROM game_instances
+                    WHERE player_wallet_address = $1) as latest
+            "#,
+            wallet_address,
+            since
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok((row.count.unwrap_or(0), row.latest))
+    }
+
+    pub async fn delete_game_instance(pool: &DbPool, id: i32) -> Result<bool, SqlxError> {
+        let result = sqlx::query!("DELETE FROM game_instances WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
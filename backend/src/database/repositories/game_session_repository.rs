@@ -0,0 +1,87 @@
+use crate::database::connection::DbPool;
+use crate::database::models::GameSessionRecord;
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, PgExecutor};
+use uuid::Uuid;
+
+pub struct GameSessionRepository;
+
+impl GameSessionRepository {
+    pub async fn create(
+        pool: &DbPool,
+        wallet_address: &str,
+        game_mode: &str,
+        config_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<GameSessionRecord, SqlxError> {
+        sqlx::query_as!(
+            GameSessionRecord,
+            r#"
+            INSERT INTO game_sessions (id, player_wallet_address, game_mode, config_hash, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, player_wallet_address, game_mode, config_hash, status,
+                      started_at, finished_at, expires_at
+            "#,
+            Uuid::new_v4(),
+            wallet_address,
+            game_mode,
+            config_hash,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<GameSessionRecord>, SqlxError> {
+        sqlx::query_as!(
+            GameSessionRecord,
+            r#"
+            SELECT id, player_wallet_address, game_mode, config_hash, status,
+                   started_at, finished_at, expires_at
+            FROM game_sessions
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Finish the wallet's session if it's still active and unexpired; `None` otherwise
+    pub async fn finish(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        wallet_address: &str,
+    ) -> Result<Option<GameSessionRecord>, SqlxError> {
+        sqlx::query_as!(
+            GameSessionRecord,
+            r#"
+            UPDATE game_sessions
+            SET status = 'finished', finished_at = NOW()
+            WHERE id = $1 AND player_wallet_address = $2
+              AND status = 'active' AND expires_at > NOW()
+            RETURNING id, player_wallet_address, game_mode, config_hash, status,
+                      started_at, finished_at, expires_at
+            "#,
+            id,
+            wallet_address
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Mark active sessions past their expiry as expired, returning how many were
+    pub async fn expire_stale(pool: &DbPool) -> Result<u64, SqlxError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE game_sessions
+            SET status = 'expired'
+            WHERE status = 'active' AND expires_at <= NOW()
+            "#
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,5 +1,13 @@
 pub mod user_repository;
 pub mod game_repository;
+pub mod contract_index_repository;
+pub mod contract_operation_repository;
+pub mod suspicious_result_repository;
+pub mod game_session_repository;
 
 pub use user_repository::UserRepository;
-pub use game_repository::GameRepository;
\ No newline at end of file
+pub use game_repository::GameRepository;
+pub use contract_index_repository::ContractIndexRepository;
+pub use contract_operation_repository::ContractOperationRepository;
+pub use suspicious_result_repository::SuspiciousResultRepository;
+pub use game_session_repository::GameSessionRepository;
\ No newline at end of file
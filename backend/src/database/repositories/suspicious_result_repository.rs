@@ -0,0 +1,86 @@
+use crate::database::connection::DbPool;
+use crate::database::models::{NewSuspiciousResult, SuspiciousResultRecord};
+use sqlx::{Error as SqlxError, PgExecutor};
+
+pub struct SuspiciousResultRepository;
+
+impl SuspiciousResultRepository {
+    pub async fn record(
+        executor: impl PgExecutor<'_>,
+        result: &NewSuspiciousResult,
+    ) -> Result<i32, SqlxError> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO suspicious_results (
+                game_instance_id, game_session_id, player_wallet_address, player_result,
+                player_score, opponent_score, duration_seconds, game_mode, reasons, status
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id
+            "#,
+            result.game_instance_id,
+            result.game_session_id,
+            result.player_wallet_address,
+            result.player_result,
+            result.player_score,
+            result.opponent_score,
+            result.duration_seconds,
+            result.game_mode,
+            &result.reasons,
+            result.status
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(row.id)
+    }
+
+    /// Newest first, optionally only those with `status`
+    pub async fn list(
+        pool: &DbPool,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<SuspiciousResultRecord>, SqlxError> {
+        sqlx::query_as!(
+            SuspiciousResultRecord,
+            r#"
+            SELECT id, game_instance_id, game_session_id, player_wallet_address, player_result,
+                   player_score, opponent_score, duration_seconds, game_mode, reasons, status,
+                   review_note, reviewed_at, created_at
+            FROM suspicious_results
+            WHERE $1::VARCHAR IS NULL OR status = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2
+            "#,
+            status,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Settle a pending result; `None` if it doesn't exist or was already reviewed
+    pub async fn review(
+        pool: &DbPool,
+        id: i32,
+        status: &str,
+        note: Option<&str>,
+    ) -> Result<Option<SuspiciousResultRecord>, SqlxError> {
+        sqlx::query_as!(
+            SuspiciousResultRecord,
+            r#"
+            UPDATE suspicious_results
+            SET status = $2, review_note = $3, reviewed_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            RETURNING id, game_instance_id, game_session_id, player_wallet_address, player_result,
+                      player_score, opponent_score, duration_seconds, game_mode, reasons, status,
+                      review_note, reviewed_at, created_at
+            "#,
+            id,
+            status,
+            note
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -21,6 +21,21 @@ pub enum AppError {
     #[error("User not found")]
     UserNotFound,
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Too many requests: {0}")]
+    RateLimited(String),
+
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
@@ -57,6 +72,11 @@ impl IntoResponse for AppError {
         let status = match self {
             AppError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::UserNotFound => StatusCode::NOT_FOUND,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
             AppError::InvalidInput(_) => StatusCode::BAD_REQUEST,
             AppError::ExternalService(_) => StatusCode::SERVICE_UNAVAILABLE,
             AppError::EnvVar(_) => StatusCode::INTERNAL_SERVER_ERROR,
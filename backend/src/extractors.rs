@@ -1,10 +1,12 @@
 use axum::{
-    extract::Query,
-    http::StatusCode,
+    extract::{FromRef, FromRequestParts, Query},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
 };
 use serde::Deserialize;
 use shared::WalletAddress;
 
+use crate::{error::AppError, state::AppState};
+
 /// Validated wallet address query parameter
 #[derive(Debug, Clone, Deserialize)]
 pub struct WalletAddressQuery {
@@ -19,12 +21,14 @@ impl WalletAddressQuery {
 }
 
 /// Pagination parameters with sensible defaults
+/// A `cursor` from a previous page's `next_cursor` takes precedence over `offset`
 #[derive(Debug, Clone, Deserialize)]
 pub struct PaginationQuery {
     #[serde(default = "default_limit")]
     pub limit: i64,
     #[serde(default)]
     pub offset: i64,
+    pub cursor: Option<String>,
 }
 
 fn default_limit() -> i64 {
@@ -52,10 +56,13 @@ impl PaginationQuery {
     }
 
     pub fn limit(&self) -> i64 {
-        self.limit.min(100).max(1)
+        self.limit.clamp(1, 100)
     }
 
     pub fn offset(&self) -> i64 {
+        if self.cursor.is_some() {
+            return 0;
+        }
         self.offset.max(0)
     }
 }
@@ -65,6 +72,7 @@ impl Default for PaginationQuery {
         Self {
             limit: default_limit(),
             offset: 0,
+            cursor: None,
         }
     }
 }
@@ -75,6 +83,82 @@ pub type ValidatedWallet = Query<WalletAddressQuery>;
 /// Helper to extract pagination parameters
 pub type Pagination = Query<PaginationQuery>;
 
+/// Guard for admin routes: requires `Authorization: Bearer <ADMIN_API_TOKEN>`
+pub struct AdminAccess;
+
+impl<S> FromRequestParts<S> for AdminAccess
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = AppState::from_ref(state);
+        let Some(expected) = state.admin_api_token.as_deref() else {
+            return Err(AppError::Unauthorized("Admin API is disabled".to_string()));
+        };
+
+        let provided = bearer_token(parts)
+            .ok_or_else(|| AppError::Unauthorized("Missing admin bearer token".to_string()))?;
+
+        if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+            return Err(AppError::Unauthorized("Invalid admin token".to_string()));
+        }
+        Ok(AdminAccess)
+    }
+}
+
+/// Guard for write routes: requires a wallet session token from /api/auth/token
+pub struct WalletSession {
+    pub wallet_address: String,
+}
+
+impl WalletSession {
+    /// Refuse requests made on behalf of a wallet other than the signed-in one
+    pub fn require(&self, wallet_address: &str) -> Result<(), AppError> {
+        if self.wallet_address != wallet_address {
+            return Err(AppError::Forbidden(format!(
+                "Signed in as {} but the request is for {}",
+                self.wallet_address, wallet_address
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<S> FromRequestParts<S> for WalletSession
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = AppState::from_ref(state);
+        let token = bearer_token(parts)
+            .ok_or_else(|| AppError::Unauthorized("Sign in with your wallet first".to_string()))?;
+
+        let claims = state.wallet_auth.authenticate(token)?;
+        Ok(WalletSession {
+            wallet_address: claims.sub,
+        })
+    }
+}
+
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Compare secrets without returning early on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,18 +175,21 @@ mod tests {
         let valid = PaginationQuery {
             limit: 50,
             offset: 10,
+            cursor: None,
         };
         assert!(valid.validate().is_ok());
 
         let invalid_limit = PaginationQuery {
             limit: 150,
             offset: 0,
+            cursor: None,
         };
         assert!(invalid_limit.validate().is_err());
 
         let invalid_offset = PaginationQuery {
             limit: 20,
             offset: -5,
+            cursor: None,
         };
         assert!(invalid_offset.validate().is_err());
     }
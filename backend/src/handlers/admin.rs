@@ -0,0 +1,151 @@
+use axum::{extract::{Path, Query, State}, response::Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::{
+    database::{models::SuspiciousResultRecord, DbPool},
+    error::{AppError, Result},
+    extractors::AdminAccess,
+    services::{
+        soroban::{ContractMetadata, NetworkType},
+        GameService, ScalableContractManager,
+    },
+};
+
+#[derive(Debug, Serialize)]
+pub struct ContractListResponse {
+    pub default_contract: String,
+    pub contracts: Vec<ContractMetadata>,
+}
+
+/// A contract to add to the registry; RPC URL and passphrase default to the network's
+#[derive(Debug, Deserialize)]
+pub struct RegisterContractRequest {
+    pub contract_id: String,
+    pub name: String,
+    pub network: NetworkType,
+    pub rpc_url: Option<String>,
+    pub network_passphrase: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl RegisterContractRequest {
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if !self.contract_id.starts_with('C') || self.contract_id.len() != 56 {
+            return Err("Invalid contract ID format".to_string());
+        }
+        if self.name.trim().is_empty() {
+            return Err("Contract name cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    fn into_metadata(self) -> ContractMetadata {
+        ContractMetadata {
+            rpc_url: self.rpc_url
+                .unwrap_or_else(|| self.network.default_rpc_url().to_string()),
+            network_passphrase: self.network_passphrase
+                .unwrap_or_else(|| self.network.default_passphrase().to_string()),
+            contract_id: self.contract_id,
+            name: self.name,
+            network: self.network,
+            description: self.description,
+            version: self.version,
+            enabled: self.enabled,
+        }
+    }
+}
+
+/// Every registered contract, enabled or not
+pub async fn list_contracts_handler(
+    _admin: AdminAccess,
+    State(contracts): State<Arc<ScalableContractManager>>,
+) -> Json<ContractListResponse> {
+    Json(ContractListResponse {
+        default_contract: contracts.default_contract_id(),
+        contracts: contracts.list_contracts().await,
+    })
+}
+
+pub async fn register_contract_handler(
+    _admin: AdminAccess,
+    State(contracts): State<Arc<ScalableContractManager>>,
+    Json(request): Json<RegisterContractRequest>,
+) -> Result<Json<ContractMetadata>> {
+    request.validate().map_err(AppError::InvalidInput)?;
+
+    let metadata = request.into_metadata();
+    contracts.register_contract(metadata.clone()).await?;
+
+    info!("✅ Contract {} registered via admin API", metadata.contract_id);
+    Ok(Json(metadata))
+}
+
+pub async fn enable_contract_handler(
+    _admin: AdminAccess,
+    State(contracts): State<Arc<ScalableContractManager>>,
+    Path(contract_id): Path<String>,
+) -> Result<Json<ContractMetadata>> {
+    Ok(Json(contracts.set_contract_enabled(&contract_id, true).await?))
+}
+
+pub async fn disable_contract_handler(
+    _admin: AdminAccess,
+    State(contracts): State<Arc<ScalableContractManager>>,
+    Path(contract_id): Path<String>,
+) -> Result<Json<ContractMetadata>> {
+    Ok(Json(contracts.set_contract_enabled(&contract_id, false).await?))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuspiciousResultsQuery {
+    /// 'rejected', 'pending', 'cleared' or 'confirmed'; all when omitted
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Outcome of reviewing a pending result
+#[derive(Debug, Deserialize)]
+pub struct ReviewSuspiciousResultRequest {
+    /// 'cleared' keeps the result, 'confirmed' removes it from the game history
+    pub decision: String,
+    pub note: Option<String>,
+}
+
+/// Results the anti-cheat checks rejected or held for review, newest first
+pub async fn list_suspicious_results_handler(
+    _admin: AdminAccess,
+    State(pool): State<DbPool>,
+    Query(query): Query<SuspiciousResultsQuery>,
+) -> Result<Json<Vec<SuspiciousResultRecord>>> {
+    let results = GameService::list_suspicious_results(&pool, query.status.as_deref(), query.limit).await?;
+    Ok(Json(results))
+}
+
+pub async fn review_suspicious_result_handler(
+    _admin: AdminAccess,
+    State(pool): State<DbPool>,
+    State(contracts): State<Arc<ScalableContractManager>>,
+    Path(id): Path<i32>,
+    Json(request): Json<ReviewSuspiciousResultRequest>,
+) -> Result<Json<SuspiciousResultRecord>> {
+    let record = GameService::review_suspicious_result(
+        &pool,
+        &contracts,
+        id,
+        &request.decision,
+        request.note.as_deref(),
+    )
+    .await?;
+
+    info!("✅ Suspicious result {} marked {} via admin API", id, record.status);
+    Ok(Json(record))
+}
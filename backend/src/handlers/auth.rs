@@ -1,16 +1,51 @@
 use sqlx::PgPool;
-use crate::services::AuthService;
+use std::sync::Arc;
+use crate::extractors::WalletSession;
+use crate::services::{AuthService, WalletAuth};
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Query, State},
     response::IntoResponse,
 };
-use shared::dto::auth::Guest;
+use shared::dto::auth::{AuthChallengeQuery, AuthTokenRequest, Guest};
 use shared::dto::common::ApiResponse;
 
+/// Challenge transaction for a wallet to sign in with
+pub async fn auth_challenge(
+    State(wallet_auth): State<Arc<WalletAuth>>,
+    Query(query): Query<AuthChallengeQuery>,
+) -> impl IntoResponse {
+    match wallet_auth.challenge(&query.account) {
+        Ok(challenge) => (
+            axum::http::StatusCode::OK,
+            Json(ApiResponse::success(challenge, "Sign this challenge with your wallet"))
+        ).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+/// Exchange a countersigned challenge for a session token
+pub async fn auth_token(
+    State(wallet_auth): State<Arc<WalletAuth>>,
+    Json(request): Json<AuthTokenRequest>,
+) -> impl IntoResponse {
+    match wallet_auth.verify_challenge(&request.transaction) {
+        Ok(session) => (
+            axum::http::StatusCode::OK,
+            Json(ApiResponse::success(session, "Wallet verified"))
+        ).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
 pub async fn register_guest(
+    session: WalletSession,
     State(pool): State<PgPool>,
     Json(guest): Json<Guest>,
 ) -> impl IntoResponse {
+    if let Err(err) = session.require(&guest.wallet_address) {
+        return err.into_response();
+    }
+
     match AuthService::register_or_login_guest(&pool, guest).await {
         Ok(response) => (
             axum::http::StatusCode::OK,
@@ -18,4 +53,4 @@ pub async fn register_guest(
         ).into_response(),
         Err(err) => err.into_response(),
     }
-}
\ No newline at end of file
+}
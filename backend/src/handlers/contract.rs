@@ -1,15 +1,21 @@
-use axum::{extract::Query, response::Json, extract::State};
+use axum::{extract::{Path, Query, State}, response::Json};
 use sqlx::PgPool;
+use std::sync::Arc;
 use tracing::{info, warn};
 use shared::dto::contract::{
     ContractXdrRequest, ContractXdrResponse, ContractSubmitRequest, ContractSubmitResponse,
-    LeaderboardQuery, LeaderboardResponse, LeaderboardEntryWithMetadata, LeaderboardFunction
+    LeaderboardQuery, LeaderboardResponse, LeaderboardEntryWithMetadata, LeaderboardFunction,
+    ContractAchievement, ContractValue, ContractQueryRequest, ContractQueryResponse, ContractTransactionResponse,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{AppError, Result},
-    services::{soroban::client::{generate_leaderboard_xdr, submit_signed_transaction, ContractConfig}, GameService},
+    extractors::{PaginationQuery, WalletSession},
+    services::{
+        soroban::{client::signed_envelope_source, ContractInfo, ContractMetrics, HealthStatus},
+        GameService, ScalableContractManager,
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -24,8 +30,40 @@ pub struct JoinStatusResponse {
     pub needs_join_xdr: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AchievementsQuery {
+    pub player_address: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AchievementInfo {
+    pub id: u32,
+    pub achievement: ContractAchievement,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub awarded_at: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContractHealthResponse {
+    pub status: &'static str,
+    pub service: &'static str,
+    pub timestamp: String,
+    pub health: HealthStatus,
+    pub metrics: ContractMetrics,
+    pub contracts: Vec<ContractInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AchievementsResponse {
+    pub player_address: String,
+    pub achievements: Vec<AchievementInfo>,
+}
+
 pub async fn generate_contract_xdr_handler(
-    State(_pool): State<PgPool>,
+    session: WalletSession,
+    State(contracts): State<Arc<ScalableContractManager>>,
     Json(request): Json<ContractXdrRequest>,
 ) -> Result<Json<ContractXdrResponse>> {
     let wallet_info = request.wallet_type.as_deref().unwrap_or("unknown");
@@ -33,44 +71,35 @@ pub async fn generate_contract_xdr_handler(
           request.source_account, wallet_info);
 
     request.validate().map_err(AppError::InvalidInput)?;
+    session.require(&request.source_account)?;
 
-    let function = request.get_function().clone();
-    let source_account = request.source_account.clone();
+    let function = request.get_function();
+    let source_account = &request.source_account;
 
     info!("Selected function: {} ({})", function.name(), function.signature());
-
-    let config = ContractConfig::default();
-
     info!("Generating XDR for account: {}...{}, function: {}",
           &source_account[..6], &source_account[source_account.len()-6..], function.name());
 
-    let result = tokio::task::spawn_blocking(move || {
-        tokio::runtime::Handle::current().block_on(async move {
-            generate_leaderboard_xdr(&config, &source_account, &function).await
-        })
-    }).await;
-
-    match result {
-        Ok(Ok(xdr)) => {
-            info!("✅ XDR generated successfully for Freighter wallet signing");
-            Ok(Json(ContractXdrResponse::success(
-                xdr,
-                "XDR generated successfully for Freighter wallet".to_string(),
-            )))
-        }
-        Ok(Err(error)) => {
+    let xdr = contracts
+        .generate_xdr(&contracts.default_contract_id(), source_account, function)
+        .await
+        .map_err(|error| {
             warn!("❌ XDR generation failed: {}", error);
-            Err(AppError::XdrEncoding(error.to_string()))
-        }
-        Err(join_error) => {
-            warn!("❌ Task join failed: {}", join_error);
-            Err(AppError::TaskExecution(join_error.to_string()))
-        }
-    }
+            error
+        })?;
+
+    info!("✅ XDR generated successfully for Freighter wallet signing");
+    Ok(Json(ContractXdrResponse::success(
+        xdr,
+        "XDR generated successfully for Freighter wallet".to_string(),
+    )))
 }
 
+/// Queue a wallet-signed transaction for submission
+/// The response carries an operation id to poll /api/contract/transactions/{id} with
 pub async fn submit_contract_transaction_handler(
-    State(_pool): State<PgPool>,
+    session: WalletSession,
+    State(contracts): State<Arc<ScalableContractManager>>,
     Json(request): Json<ContractSubmitRequest>,
 ) -> Result<Json<ContractSubmitResponse>> {
     let wallet_info = request.wallet_type.as_deref().unwrap_or("unknown");
@@ -79,33 +108,46 @@ pub async fn submit_contract_transaction_handler(
 
     request.validate().map_err(AppError::InvalidInput)?;
 
-    let function = request.get_function().clone();
-    let signed_xdr = request.signed_xdr.clone();
+    let source_account = signed_envelope_source(&request.signed_xdr)?;
+    session.require(&source_account)?;
 
-    let result = tokio::task::spawn_blocking(move || {
-        tokio::runtime::Handle::current().block_on(async move {
-            submit_signed_transaction(&signed_xdr, &function).await
-        })
-    }).await;
-
-    match result {
-        Ok(Ok((hash, contract_result))) => {
-            info!("✅ Transaction submitted successfully: {}", hash);
-            Ok(Json(ContractSubmitResponse::success(
-                contract_result,
-                hash,
-                "Contract executed successfully".to_string(),
-            )))
-        }
-        Ok(Err(error)) => {
+    let operation_id = contracts
+        .submit_transaction(
+            &contracts.default_contract_id(),
+            source_account,
+            request.get_function().clone(),
+            request.signed_xdr.clone(),
+            None,
+        )
+        .await
+        .map_err(|error| {
             warn!("❌ Transaction submission failed: {}", error);
-            Err(AppError::Transaction(error.to_string()))
-        }
-        Err(join_error) => {
-            warn!("❌ Task join failed: {}", join_error);
-            Err(AppError::TaskExecution(join_error.to_string()))
-        }
-    }
+            error
+        })?;
+
+    info!("✅ Transaction queued as operation {}", operation_id);
+    Ok(Json(ContractSubmitResponse::queued(
+        operation_id,
+        format!("{} transaction queued for submission", request.get_function().name()),
+    )))
+}
+
+/// Status of a transaction queued by submit_contract_transaction_handler
+pub async fn get_transaction_status_handler(
+    State(contracts): State<Arc<ScalableContractManager>>,
+    Path(operation_id): Path<String>,
+) -> Result<Json<ContractTransactionResponse>> {
+    let tracked = contracts
+        .get_transaction_status(&operation_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Unknown transaction operation: {}", operation_id)))?;
+
+    Ok(Json(ContractTransactionResponse {
+        operation_id: tracked.operation_id,
+        function: tracked.function_name,
+        status: tracked.status,
+        updated_at: tracked.updated_at.to_rfc3339(),
+    }))
 }
 
 pub async fn get_leaderboard_handler(
@@ -117,7 +159,11 @@ pub async fn get_leaderboard_handler(
     let limit = query.limit.unwrap_or(10);
 
     // Get leaderboard data from database (traditional game results)
-    let leaderboard_result = GameService::get_leaderboard(&pool, Some(limit as i64))
+    let page = PaginationQuery {
+        limit: (limit as i64).clamp(1, 100),
+        ..PaginationQuery::default()
+    };
+    let leaderboard_result = GameService::get_leaderboard(&pool, &page)
         .await?;
 
     // Convert to the contract-compatible format
@@ -129,6 +175,7 @@ pub async fn get_leaderboard_handler(
             rank: (index + 1) as u32,
             address: entry.wallet_address,
             wins: entry.wins as u32,
+            rating: entry.rating.map(|rating| rating as u32),
             username: Some(entry.username),
             joined_at: None, // Could be populated from contract if needed
         })
@@ -148,60 +195,122 @@ pub async fn get_leaderboard_handler(
 }
 
 pub async fn check_join_status_handler(
-    State(_pool): State<PgPool>,
+    State(contracts): State<Arc<ScalableContractManager>>,
     Query(query): Query<JoinStatusQuery>,
 ) -> Result<Json<JoinStatusResponse>> {
     info!("Join status check request for player: {}", query.player_address);
 
-    let config = ContractConfig::default();
-    let has_joined_function = LeaderboardFunction::HasJoined {
-        player: query.player_address.clone()
+    if !query.player_address.starts_with('G') || query.player_address.len() != 56 {
+        return Err(AppError::InvalidInput("Invalid player address format".to_string()));
+    }
+
+    // Simulate has_joined against the contract
+    let joined = contracts
+        .has_joined(&contracts.default_contract_id(), &query.player_address)
+        .await
+        .map_err(|error| {
+            warn!("❌ Failed to read join status: {}", error);
+            error
+        })?;
+
+    info!("📊 Join status result: player {} has_joined={}", query.player_address, joined);
+    Ok(Json(JoinStatusResponse {
+        player_address: query.player_address,
+        has_joined: joined,
+        needs_join_xdr: !joined,
+    }))
+}
+
+/// Run any read-only contract function by simulation and return its decoded result
+pub async fn query_contract_handler(
+    State(contracts): State<Arc<ScalableContractManager>>,
+    Json(request): Json<ContractQueryRequest>,
+) -> Result<Json<ContractQueryResponse>> {
+    info!("Contract query request for function: {}", request.function.name());
+
+    request.validate().map_err(AppError::InvalidInput)?;
+
+    let value = contracts
+        .query(&contracts.default_contract_id(), &request.function)
+        .await
+        .map_err(|error| {
+            warn!("❌ Contract query {} failed: {}", request.function.name(), error);
+            error
+        })?;
+
+    info!("✅ Contract query {} answered", request.function.name());
+    Ok(Json(ContractQueryResponse {
+        function: request.function.name().to_string(),
+        value,
+    }))
+}
+
+/// Achievements the player has earned on-chain, with their catalog details
+pub async fn get_achievements_handler(
+    State(contracts): State<Arc<ScalableContractManager>>,
+    Query(query): Query<AchievementsQuery>,
+) -> Result<Json<AchievementsResponse>> {
+    info!("Achievements request for player: {}", query.player_address);
+
+    if !query.player_address.starts_with('G') || query.player_address.len() != 56 {
+        return Err(AppError::InvalidInput("Invalid player address format".to_string()));
+    }
+
+    let function = LeaderboardFunction::GetAchievements {
+        player: query.player_address.clone(),
     };
 
-    // Clone variables for the spawned task
-    let player_address_clone = query.player_address.clone();
+    let value = contracts
+        .query(&contracts.default_contract_id(), &function)
+        .await
+        .map_err(|error| {
+            warn!("❌ Failed to read achievements for {}: {}", query.player_address, error);
+            error
+        })?;
 
-    // Generate XDR for has_joined query (this would be used to call the contract)
-    let xdr_result = tokio::task::spawn_blocking(move || {
-        tokio::runtime::Handle::current().block_on(async move {
-            generate_leaderboard_xdr(&config, &player_address_clone, &has_joined_function).await
+    let ContractValue::Badges(badges) = value else {
+        return Err(AppError::Internal(format!("Unexpected get_achievements result: {:?}", value)));
+    };
+
+    let achievements = badges
+        .into_iter()
+        .map(|badge| AchievementInfo {
+            id: badge.achievement.id(),
+            achievement: badge.achievement,
+            name: badge.achievement.display_name().to_string(),
+            description: badge.achievement.description().to_string(),
+            icon: badge.achievement.icon().to_string(),
+            awarded_at: badge.awarded_at,
         })
-    }).await;
-
-    match xdr_result {
-        Ok(Ok(_xdr)) => {
-            info!("✅ Join status XDR generated successfully");
-            // For simplicity, we'll assume new players need to join
-            // In production, you'd execute the XDR against the contract to get the actual status
-            let response = JoinStatusResponse {
-                player_address: query.player_address.clone(),
-                has_joined: false, // Simplified - assume they need to join
-                needs_join_xdr: true,
-            };
-            info!("📊 Join status result: player {} needs to join", query.player_address);
-            Ok(Json(response))
-        },
-        Ok(Err(e)) => {
-            warn!("❌ Failed to generate join status XDR: {}", e);
-            Err(AppError::XdrEncoding(e.to_string()))
-        },
-        Err(join_error) => {
-            warn!("❌ Join status task failed: {}", join_error);
-            Err(AppError::TaskExecution(join_error.to_string()))
+        .collect::<Vec<_>>();
+
+    info!("✅ {} achievements earned by {}", achievements.len(), query.player_address);
+    Ok(Json(AchievementsResponse {
+        player_address: query.player_address,
+        achievements,
+    }))
+}
+
+/// Contract manager health, metrics and per-contract pool, breaker and cache stats
+pub async fn contract_health_handler(
+    State(contracts): State<Arc<ScalableContractManager>>,
+) -> Json<ContractHealthResponse> {
+    let health = contracts.health_check().await;
+
+    let mut contract_info = Vec::new();
+    for metadata in contracts.list_contracts().await {
+        match contracts.get_contract_info(&metadata.contract_id).await {
+            Ok(info) => contract_info.push(info),
+            Err(error) => warn!("❌ No info for contract {}: {}", metadata.contract_id, error),
         }
     }
-}
 
-pub async fn contract_health_handler() -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "status": "healthy",
-        "service": "stellar-heads-contract",
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "contract_features": [
-            "XDR generation",
-            "Transaction submission",
-            "Leaderboard integration",
-            "Join status checking"
-        ]
-    }))
-}
\ No newline at end of file
+    Json(ContractHealthResponse {
+        status: if health.healthy { "healthy" } else { "degraded" },
+        service: "stellar-heads-contract",
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        health,
+        metrics: contracts.get_metrics().await,
+        contracts: contract_info,
+    })
+}
@@ -2,31 +2,38 @@ use axum::{
     extract::{Json, Query, State},
     response::IntoResponse,
 };
-use serde::Deserialize;
 use sqlx::PgPool;
-use crate::services::GameService;
+use std::sync::Arc;
+use crate::extractors::{Pagination, WalletSession};
+use crate::services::{GameService, ScalableContractManager, WalletAuth};
+use crate::metrics::Metrics;
+use crate::state::AppState;
 use shared::dto::game::{
     StoreGameResultRequest, PlayerStatsQuery,
-    PlayerGamesQuery,
+    PlayerGamesQuery, GameHistoryFilter,
 };
 use shared::dto::common::ApiResponse;
 
-#[derive(Debug, Deserialize)]
-pub struct LeaderboardQuery {
-    pub limit: Option<i64>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct RecentGamesQuery {
-    pub limit: Option<i64>,
-}
-
-#[axum::debug_handler]
+#[axum::debug_handler(state = AppState)]
 pub async fn store_game_result(
+    session: WalletSession,
     State(pool): State<PgPool>,
+    State(contracts): State<Arc<ScalableContractManager>>,
+    State(wallet_auth): State<Arc<WalletAuth>>,
+    State(metrics): State<Arc<Metrics>>,
     Json(request): Json<StoreGameResultRequest>,
 ) -> impl IntoResponse {
-    match GameService::store_game_result(&pool, request).await {
+    let result = request.player_result.clone();
+    let stored = match session.require(&request.player_wallet_address) {
+        Ok(()) => {
+            let session_key = wallet_auth.session_key(&session.wallet_address, &request.game_session_id);
+            GameService::store_game_result(&pool, &contracts, &session_key, request).await
+        }
+        Err(err) => Err(err),
+    };
+    metrics.record_game_submission(&result, stored.is_ok());
+
+    match stored {
         Ok(response) => (
             axum::http::StatusCode::OK,
             Json(ApiResponse::success(response, "Game result stored successfully"))
@@ -51,8 +58,9 @@ pub async fn get_player_stats(
 pub async fn get_player_games(
     State(pool): State<PgPool>,
     Query(query): Query<PlayerGamesQuery>,
+    Query(page): Pagination,
 ) -> impl IntoResponse {
-    match GameService::get_player_games(&pool, query).await {
+    match GameService::get_player_games(&pool, query, &page).await {
         Ok(games) => (
             axum::http::StatusCode::OK,
             Json(ApiResponse::success(games, "Player games retrieved successfully"))
@@ -63,9 +71,9 @@ pub async fn get_player_games(
 
 pub async fn get_database_leaderboard(
     State(pool): State<PgPool>,
-    Query(query): Query<LeaderboardQuery>,
+    Query(page): Pagination,
 ) -> impl IntoResponse {
-    match GameService::get_leaderboard(&pool, query.limit).await {
+    match GameService::get_leaderboard(&pool, &page).await {
         Ok(leaderboard) => (
             axum::http::StatusCode::OK,
             Json(ApiResponse::success(leaderboard, "Leaderboard retrieved successfully"))
@@ -76,9 +84,10 @@ pub async fn get_database_leaderboard(
 
 pub async fn get_recent_games(
     State(pool): State<PgPool>,
-    Query(query): Query<RecentGamesQuery>,
+    Query(filter): Query<GameHistoryFilter>,
+    Query(page): Pagination,
 ) -> impl IntoResponse {
-    match GameService::get_recent_games(&pool, query.limit).await {
+    match GameService::get_recent_games(&pool, &filter, &page).await {
         Ok(games) => (
             axum::http::StatusCode::OK,
             Json(ApiResponse::success(games, "Recent games retrieved successfully"))
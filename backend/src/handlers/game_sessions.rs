@@ -0,0 +1,82 @@
+use axum::{
+    extract::{Json, Path, State},
+    response::IntoResponse,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use crate::database::models::GameSessionRecord;
+use crate::error::AppError;
+use crate::extractors::WalletSession;
+use crate::services::{GameSessionService, WalletAuth};
+use shared::dto::game::{CreateGameSessionRequest, GameSessionResponse, UpdateGameSessionRequest};
+use shared::dto::common::ApiResponse;
+
+/// Start a match; the response carries the key its transcript must be signed with
+pub async fn create_game_session(
+    session: WalletSession,
+    State(pool): State<PgPool>,
+    State(wallet_auth): State<Arc<WalletAuth>>,
+    Json(request): Json<CreateGameSessionRequest>,
+) -> impl IntoResponse {
+    match GameSessionService::start(&pool, &session.wallet_address, request).await {
+        Ok(record) => (
+            axum::http::StatusCode::CREATED,
+            Json(ApiResponse::success(session_response(record, &wallet_auth), "Game session started"))
+        ).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+pub async fn get_game_session(
+    session: WalletSession,
+    State(pool): State<PgPool>,
+    State(wallet_auth): State<Arc<WalletAuth>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match GameSessionService::get(&pool, &session.wallet_address, &id).await {
+        Ok(record) => (
+            axum::http::StatusCode::OK,
+            Json(ApiResponse::success(session_response(record, &wallet_auth), "Game session retrieved"))
+        ).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+/// Finish a match that ended without a result
+pub async fn update_game_session(
+    session: WalletSession,
+    State(pool): State<PgPool>,
+    State(wallet_auth): State<Arc<WalletAuth>>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateGameSessionRequest>,
+) -> impl IntoResponse {
+    if request.status != "finished" {
+        return AppError::InvalidInput("status can only be set to finished".to_string()).into_response();
+    }
+
+    let finished = match pool.acquire().await {
+        Ok(mut conn) => GameSessionService::finish(&mut conn, &session.wallet_address, &id).await,
+        Err(e) => Err(e.into()),
+    };
+
+    match finished {
+        Ok(record) => (
+            axum::http::StatusCode::OK,
+            Json(ApiResponse::success(session_response(record, &wallet_auth), "Game session finished"))
+        ).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+fn session_response(record: GameSessionRecord, wallet_auth: &WalletAuth) -> GameSessionResponse {
+    let session_id = record.id.to_string();
+    GameSessionResponse {
+        session_key: hex::encode(wallet_auth.session_key(&record.player_wallet_address, &session_id)),
+        session_id,
+        game_mode: record.game_mode,
+        status: record.status,
+        started_at: record.started_at,
+        finished_at: record.finished_at,
+        expires_at: record.expires_at,
+    }
+}
@@ -0,0 +1,9 @@
+use axum::{extract::State, http::header::CONTENT_TYPE, response::IntoResponse};
+
+use crate::{error::Result, state::AppState};
+
+/// Prometheus scrape endpoint
+pub async fn metrics_handler(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    let body = state.metrics.render(&state.pool, &state.contracts).await?;
+    Ok(([(CONTENT_TYPE, prometheus::TEXT_FORMAT)], body))
+}
@@ -1,6 +1,9 @@
+pub mod admin;
 pub mod auth;
 pub mod game_results;
+pub mod game_sessions;
 pub mod contract;
+pub mod metrics;
 
 use axum::{http::StatusCode, Json};
 use shared::dto::common::ApiResponse;
@@ -3,6 +3,9 @@ pub mod database;
 pub mod error;
 pub mod extractors;
 pub mod handlers;
+pub mod metrics;
 pub mod services;
+pub mod state;
 
-pub use error::{AppError, Result};
\ No newline at end of file
+pub use error::{AppError, Result};
+pub use state::AppState;
\ No newline at end of file
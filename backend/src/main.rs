@@ -1,30 +1,49 @@
 use axum::{
     http::{StatusCode, Uri},
     response::Response,
+    middleware,
     routing::{get, post},
     Router,
 };
+use std::sync::Arc;
 use tower_http::{cors::CorsLayer, services::ServeDir};
 use tracing::{error, info};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use backend::{
     config::Config,
+    metrics::track_http_latency,
+    state::AppState,
     database::create_pool,
     handlers::{
-        auth::register_guest,
+        admin::{
+            disable_contract_handler, enable_contract_handler, list_contracts_handler,
+            list_suspicious_results_handler, register_contract_handler,
+            review_suspicious_result_handler,
+        },
+        auth::{auth_challenge, auth_token, register_guest},
         game_results::{
             get_database_leaderboard, get_player_games, get_player_stats, get_recent_games,
             store_game_result,
         },
+        game_sessions::{create_game_session, get_game_session, update_game_session},
         contract::{
             generate_contract_xdr_handler, submit_contract_transaction_handler,
             get_leaderboard_handler, contract_health_handler, check_join_status_handler,
+            get_achievements_handler, query_contract_handler, get_transaction_status_handler,
         },
         health,
+        metrics::metrics_handler,
+    },
+    services::soroban::{
+        EventIndexer, OracleKey, RegistryConfig, RpcEventSource, ScalableContractManager, TtlMaintenance,
     },
+    services::{GameSessionService, WalletAuth},
 };
 
+#[cfg(unix)]
+use backend::services::soroban::registry_config::spawn_reload_on_sighup;
+
 async fn spa_fallback(_uri: Uri) -> Result<Response, StatusCode> {
     let config = Config::from_env().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     match tokio::fs::read_to_string(&config.fallback_index_path).await {
@@ -72,7 +91,94 @@ async fn main() {
         }
     };
 
-    let routes = create_routes(&config).with_state(pool);
+    // Contracts come from CONTRACTS_CONFIG when set, otherwise from CONTRACT_ID
+    let registry_config = match &config.contracts_config_path {
+        Some(path) => match RegistryConfig::load(path) {
+            Ok(registry_config) => registry_config,
+            Err(e) => {
+                error!("Failed to load contract registry config: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => {
+            info!("CONTRACTS_CONFIG not set, using the CONTRACT_ID contract on testnet");
+            RegistryConfig::from_env()
+        }
+    };
+
+    // Sessions nobody finishes stop accepting results when they expire; the sweep just records it
+    GameSessionService::spawn_expiry(pool.clone(), std::time::Duration::from_secs(60));
+
+    // Registry, RPC pools, circuit breakers and the transaction queue behind every contract call
+    let mut contracts = match ScalableContractManager::new(pool.clone(), &registry_config).await {
+        Ok(contracts) => contracts,
+        Err(e) => {
+            error!("Failed to initialize contract manager: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Match results are recorded on-chain by the backend, signed with the admin key
+    match config.admin_secret_key.as_deref().map(OracleKey::from_secret) {
+        Some(Ok(key)) => contracts = contracts.with_oracle(key),
+        Some(Err(e)) => {
+            error!("Failed to load the oracle key: {}", e);
+            std::process::exit(1);
+        }
+        None => info!("ADMIN_SECRET_KEY not set, match results won't be recorded on-chain"),
+    }
+    let contracts = Arc::new(contracts);
+
+    // Keep leaderboard storage from being archived when the contract admin key is available
+    match &config.admin_secret_key {
+        Some(secret) => {
+            TtlMaintenance::new(
+                contracts.clone(),
+                secret.clone(),
+                std::time::Duration::from_secs(config.ttl_bump_interval_secs),
+            )
+            .spawn();
+        }
+        None => info!("ADMIN_SECRET_KEY not set, contract TTL maintenance disabled"),
+    }
+
+    // Mirror contract events into Postgres once we know which ledger to start from
+    match config.indexer_start_ledger {
+        Some(start_ledger) => {
+            EventIndexer::new(
+                RpcEventSource,
+                pool.clone(),
+                contracts.clone(),
+                start_ledger,
+                std::time::Duration::from_secs(config.indexer_poll_interval_secs),
+            )
+            .spawn();
+        }
+        None => info!("INDEXER_START_LEDGER not set, contract event indexer disabled"),
+    }
+
+    #[cfg(unix)]
+    if let Some(path) = &config.contracts_config_path {
+        spawn_reload_on_sighup(contracts.clone(), path.clone());
+    }
+    if config.admin_api_token.is_none() {
+        info!("ADMIN_API_TOKEN not set, admin endpoints disabled");
+    }
+
+    // Write routes need a session from a wallet-signed challenge
+    let wallet_auth = match WalletAuth::from_config(&config) {
+        Ok(wallet_auth) => Arc::new(wallet_auth),
+        Err(e) => {
+            error!("Failed to set up wallet authentication: {}", e);
+            std::process::exit(1);
+        }
+    };
+    info!("Wallet challenges are issued by {}", wallet_auth.server_account());
+
+    let state = AppState::new(pool, contracts, wallet_auth, config.admin_api_token.clone());
+    let routes = create_routes(&config)
+        .layer(middleware::from_fn_with_state(state.metrics.clone(), track_http_latency))
+        .with_state(state);
     let app = routes.layer(CorsLayer::permissive());
 
     let addr = match config.socket_addr() {
@@ -98,13 +204,18 @@ async fn main() {
     }
 }
 
-fn create_routes(config: &Config) -> Router<sqlx::PgPool> {
+fn create_routes(config: &Config) -> Router<AppState> {
     Router::new()
         // Health check
         .route("/health", get(health))
-        // Authentication routes
+        .route("/metrics", get(metrics_handler))
+        // Authentication routes; write routes below need the session token
+        .route("/api/auth/challenge", get(auth_challenge))
+        .route("/api/auth/token", post(auth_token))
         .route("/api/auth/register-guest", post(register_guest))
         // Game routes
+        .route("/api/sessions", post(create_game_session))
+        .route("/api/sessions/{id}", get(get_game_session).patch(update_game_session))
         .route("/api/games/store", post(store_game_result))
         .route("/api/games/player-stats", get(get_player_stats))
         .route("/api/games/player-games", get(get_player_games))
@@ -113,9 +224,18 @@ fn create_routes(config: &Config) -> Router<sqlx::PgPool> {
         // Contract routes
         .route("/api/contract/generate-xdr", post(generate_contract_xdr_handler))
         .route("/api/contract/submit-transaction", post(submit_contract_transaction_handler))
+        .route("/api/contract/transactions/{id}", get(get_transaction_status_handler))
         .route("/api/contract/join-status", get(check_join_status_handler))
+        .route("/api/contract/query", post(query_contract_handler))
+        .route("/api/contract/achievements", get(get_achievements_handler))
         .route("/api/leaderboard", get(get_leaderboard_handler))
         .route("/api/contract/health", get(contract_health_handler))
+        // Admin routes (ADMIN_API_TOKEN bearer)
+        .route("/api/admin/contracts", get(list_contracts_handler).post(register_contract_handler))
+        .route("/api/admin/contracts/{id}/enable", post(enable_contract_handler))
+        .route("/api/admin/contracts/{id}/disable", post(disable_contract_handler))
+        .route("/api/admin/suspicious-results", get(list_suspicious_results_handler))
+        .route("/api/admin/suspicious-results/{id}/review", post(review_suspicious_result_handler))
         // Static file serving
         .nest_service("/game", ServeDir::new(&config.game_assets_path))
         .nest_service("/static", ServeDir::new(&config.frontend_dist_path))
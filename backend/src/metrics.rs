@@ -0,0 +1,267 @@
+// Prometheus metrics
+//
+// Request latencies and game submissions are recorded as they happen. Contract
+// manager, RPC pool, circuit breaker, cache and database pool figures already
+// live elsewhere, so they're read at scrape time into a throwaway registry and
+// rendered alongside the long-lived one.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use std::{sync::Arc, time::Instant};
+
+use crate::database::DbPool;
+use crate::error::{AppError, Result};
+use crate::services::soroban::{circuit_breaker::CircuitState, ScalableContractManager};
+
+/// Request latency buckets in seconds; contract calls reach into the seconds
+const LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+pub struct Metrics {
+    registry: Registry,
+    http_request_duration: HistogramVec,
+    game_submissions: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency by route")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["method", "route", "status"],
+        )
+        .expect("valid histogram");
+        let game_submissions = IntCounterVec::new(
+            Opts::new("game_submissions_total", "Game results submitted, by result and whether they were stored"),
+            &["result", "outcome"],
+        )
+        .expect("valid counter");
+
+        registry.register(Box::new(http_request_duration.clone())).expect("metric registered once");
+        registry.register(Box::new(game_submissions.clone())).expect("metric registered once");
+
+        Self {
+            registry,
+            http_request_duration,
+            game_submissions,
+        }
+    }
+
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, started: Instant) {
+        self.http_request_duration
+            .with_label_values(&[method, route, &status.to_string()])
+            .observe(started.elapsed().as_secs_f64());
+    }
+
+    pub fn record_game_submission(&self, result: &str, stored: bool) {
+        // Only known results become label values, so bad input can't grow the series count
+        let result = match result {
+            "Win" | "Loss" | "Draw" => result,
+            _ => "invalid",
+        };
+        self.game_submissions
+            .with_label_values(&[result, if stored { "stored" } else { "rejected" }])
+            .inc();
+    }
+
+    /// Everything in Prometheus text format
+    pub async fn render(&self, pool: &DbPool, contracts: &ScalableContractManager) -> Result<String> {
+        let snapshot = snapshot(pool, contracts).await.map_err(metrics_error)?;
+
+        let mut families = self.registry.gather();
+        families.extend(snapshot.gather());
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&families, &mut buffer).map_err(metrics_error)?;
+        String::from_utf8(buffer).map_err(|e| AppError::Internal(format!("Metrics aren't UTF-8: {}", e)))
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn metrics_error(error: prometheus::Error) -> AppError {
+    AppError::Internal(format!("Failed to render metrics: {}", error))
+}
+
+/// Current contract and database figures as a fresh registry
+async fn snapshot(pool: &DbPool, contracts: &ScalableContractManager) -> prometheus::Result<Registry> {
+    let registry = Registry::new();
+
+    // Manager totals are already monotonic, so fresh counters set to them are still counters
+    let metrics = contracts.get_metrics().await;
+    let operations = IntCounterVec::new(
+        Opts::new("contract_operations_total", "Queued contract operations by outcome"),
+        &["outcome"],
+    )?;
+    operations.with_label_values(&["successful"]).inc_by(metrics.successful_operations);
+    operations.with_label_values(&["failed"]).inc_by(metrics.failed_operations);
+    operations.with_label_values(&["retried"]).inc_by(metrics.retried_operations);
+    register(&registry, operations)?;
+
+    let cache_lookups = IntCounterVec::new(
+        Opts::new("contract_cache_lookups_total", "Contract query cache lookups"),
+        &["result"],
+    )?;
+    cache_lookups.with_label_values(&["hit"]).inc_by(metrics.cache_hits);
+    cache_lookups.with_label_values(&["stale"]).inc_by(metrics.cache_stale_hits);
+    cache_lookups.with_label_values(&["miss"]).inc_by(metrics.cache_misses);
+    register(&registry, cache_lookups)?;
+
+    let xdr_generated = IntCounter::new("contract_xdr_generated_total", "Unsigned transactions built for wallets")?;
+    xdr_generated.inc_by(metrics.xdr_generated);
+    register(&registry, xdr_generated)?;
+
+    let submitted = IntCounter::new("contract_transactions_submitted_total", "Signed transactions accepted into the queue")?;
+    submitted.inc_by(metrics.transactions_submitted);
+    register(&registry, submitted)?;
+
+    // Per contract
+    let enabled = IntGaugeVec::new(Opts::new("contract_enabled", "Whether a registered contract is enabled"), &["contract"])?;
+    let pool_connections = IntGaugeVec::new(
+        Opts::new("contract_rpc_pool_connections", "Soroban RPC pool connections"),
+        &["contract", "state"],
+    )?;
+    let pool_max = IntGaugeVec::new(Opts::new("contract_rpc_pool_max_connections", "Soroban RPC pool size limit"), &["contract"])?;
+    let breaker_state = IntGaugeVec::new(
+        Opts::new("contract_circuit_breaker_state", "Circuit breaker state: 0 closed, 1 half-open, 2 open"),
+        &["contract"],
+    )?;
+    let breaker_failures = IntGaugeVec::new(
+        Opts::new("contract_circuit_breaker_failures", "Consecutive failures counted by the circuit breaker"),
+        &["contract"],
+    )?;
+    let cache_entries = IntGaugeVec::new(
+        Opts::new("contract_cache_entries", "Contract cache entries"),
+        &["contract", "state"],
+    )?;
+    let cache_bytes = IntGaugeVec::new(Opts::new("contract_cache_bytes", "Approximate bytes held by the contract cache"), &["contract"])?;
+    let cache_max_bytes = IntGaugeVec::new(Opts::new("contract_cache_max_bytes", "Contract cache byte limit"), &["contract"])?;
+    let cache_evictions = IntCounterVec::new(
+        Opts::new("contract_cache_evictions_total", "Contract cache entries evicted to stay within bounds"),
+        &["contract"],
+    )?;
+
+    for metadata in contracts.list_contracts().await {
+        let contract = metadata.contract_id.as_str();
+        enabled.with_label_values(&[contract]).set(metadata.enabled as i64);
+
+        // Disabled contracts have no live resources to report
+        let Ok(info) = contracts.get_contract_info(contract).await else {
+            continue;
+        };
+        let in_use = info.pool_stats.max_connections.saturating_sub(info.pool_stats.available);
+        pool_connections.with_label_values(&[contract, "idle"]).set(info.pool_stats.total_connections as i64);
+        pool_connections.with_label_values(&[contract, "in_use"]).set(in_use as i64);
+        pool_max.with_label_values(&[contract]).set(info.pool_stats.max_connections as i64);
+
+        breaker_state.with_label_values(&[contract]).set(match info.circuit_breaker_stats.state {
+            CircuitState::Closed => 0,
+            CircuitState::HalfOpen => 1,
+            CircuitState::Open => 2,
+        });
+        breaker_failures.with_label_values(&[contract]).set(info.circuit_breaker_stats.failure_count as i64);
+
+        cache_entries.with_label_values(&[contract, "active"]).set(info.cache_stats.active_entries as i64);
+        cache_entries.with_label_values(&[contract, "stale"]).set(info.cache_stats.stale_entries as i64);
+        cache_entries.with_label_values(&[contract, "expired"]).set(info.cache_stats.expired_entries as i64);
+        cache_bytes.with_label_values(&[contract]).set(info.cache_stats.total_bytes as i64);
+        cache_max_bytes.with_label_values(&[contract]).set(info.cache_stats.max_bytes as i64);
+        cache_evictions.with_label_values(&[contract]).inc_by(info.cache_stats.evictions);
+    }
+    register(&registry, enabled)?;
+    register(&registry, pool_connections)?;
+    register(&registry, pool_max)?;
+    register(&registry, breaker_state)?;
+    register(&registry, breaker_failures)?;
+    register(&registry, cache_entries)?;
+    register(&registry, cache_bytes)?;
+    register(&registry, cache_max_bytes)?;
+    register(&registry, cache_evictions)?;
+
+    // sqlx pool
+    let db_connections = IntGaugeVec::new(
+        Opts::new("db_pool_connections", "Postgres pool connections"),
+        &["state"],
+    )?;
+    let idle = pool.num_idle() as i64;
+    db_connections.with_label_values(&["idle"]).set(idle);
+    db_connections.with_label_values(&["in_use"]).set(pool.size() as i64 - idle);
+    register(&registry, db_connections)?;
+
+    let db_max = IntGauge::new("db_pool_max_connections", "Postgres pool size limit")?;
+    db_max.set(pool.options().get_max_connections() as i64);
+    register(&registry, db_max)?;
+
+    Ok(registry)
+}
+
+fn register<C: prometheus::core::Collector + 'static>(registry: &Registry, collector: C) -> prometheus::Result<()> {
+    registry.register(Box::new(collector))
+}
+
+/// Middleware timing every request under its route template
+pub async fn track_http_latency(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    // Templates like /api/contract/transactions/{id} keep label values bounded
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+    metrics.observe_request(&method, &route, response.status().as_u16(), started);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_requests_and_submissions() {
+        let metrics = Metrics::new();
+        metrics.observe_request("GET", "/api/games/recent", 200, Instant::now());
+        metrics.record_game_submission("Win", true);
+        metrics.record_game_submission("<script>", false);
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&metrics.registry.gather(), &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+
+        assert!(text.contains(r#"http_request_duration_seconds_count{method="GET",route="/api/games/recent",status="200"} 1"#));
+        assert!(text.contains(r#"game_submissions_total{outcome="stored",result="Win"} 1"#));
+        assert!(text.contains(r#"game_submissions_total{outcome="rejected",result="invalid"} 1"#));
+    }
+
+    #[sqlx::test]
+    async fn renders_contract_and_database_snapshot(pool: DbPool) {
+        let config = crate::services::soroban::RegistryConfig::from_env();
+        let contracts = ScalableContractManager::new(pool.clone(), &config).await.unwrap();
+
+        let text = Metrics::new().render(&pool, &contracts).await.unwrap();
+
+        let contract = config.default_contract_id();
+        assert!(text.contains("# TYPE contract_operations_total counter"));
+        assert!(text.contains(&format!(r#"contract_enabled{{contract="{}"}} 1"#, contract)));
+        assert!(text.contains(&format!(r#"contract_circuit_breaker_state{{contract="{}"}} 0"#, contract)));
+        assert!(text.contains("db_pool_max_connections"));
+    }
+}
@@ -1,11 +1,19 @@
 use crate::database::connection::DbPool;
-use crate::database::models::NewGameInstance;
-use crate::database::repositories::{game_repository::GameRepository, user_repository::UserRepository};
+use crate::database::models::{NewGameInstance, NewSuspiciousResult, SuspiciousResultRecord};
+use crate::database::repositories::{
+    game_repository::GameRepository, user_repository::UserRepository, SuspiciousResultRepository,
+};
 use crate::error::{AppError, Result};
-use crate::services::soroban::client::{generate_leaderboard_xdr, ContractConfig};
+use crate::extractors::PaginationQuery;
+use crate::services::result_validation::{
+    self, Verdict, DURATION_TOLERANCE_SECONDS, MAX_RESULTS_PER_HOUR,
+};
+use crate::services::{GameSessionService, ScalableContractManager};
+use chrono::{Duration, Utc};
 use shared::dto::game::{
     StoreGameResultRequest, StoreGameResultResponse, PlayerStatsQuery, PlayerStats,
-    PlayerGamesQuery, GameInstance, LeaderboardEntry, ContractXdrInfo,
+    PlayerGamesQuery, GameInstance, LeaderboardEntry, ContractXdrInfo, MatchRules,
+    GameCursor, GameHistoryFilter,
 };
 use shared::dto::contract::LeaderboardFunction;
 use shared::dto::common::PaginatedResponse;
@@ -16,15 +24,44 @@ pub struct GameService;
 impl GameService {
     pub async fn store_game_result(
         pool: &DbPool,
+        contracts: &ScalableContractManager,
+        session_key: &[u8],
         request: StoreGameResultRequest,
     ) -> Result<StoreGameResultResponse> {
         info!("Storing game result for player: {}", request.player_username);
 
-        // Validate game result
-        if !["Win", "Loss", "Draw"].contains(&request.player_result.as_str()) {
-            return Err(AppError::InvalidInput(
-                "player_result must be one of: Win, Loss, Draw".to_string()
-            ));
+        let rules = MatchRules::default();
+        let mut verdict = result_validation::validate_result(&request, &rules, session_key);
+
+        // Rejected submissions count too, so a wallet can't flood the review queue
+        let (recent, latest) = GameRepository::recent_submissions(
+            pool,
+            &request.player_wallet_address,
+            Utc::now() - Duration::hours(1),
+        )
+        .await?;
+        if recent >= MAX_RESULTS_PER_HOUR {
+            return Err(AppError::RateLimited(format!(
+                "At most {} results per hour can be submitted",
+                MAX_RESULTS_PER_HOUR
+            )));
+        }
+
+        if let Verdict::Reject(reasons) = &verdict {
+            warn!("🚫 Rejected result for session {}: {}", request.game_session_id, reasons.join("; "));
+            SuspiciousResultRepository::record(pool, &suspicious(&request, None, reasons, "rejected")).await?;
+            return Err(AppError::InvalidInput(reasons.join("; ")));
+        }
+
+        // A wallet can't finish a match sooner after its previous one than the match lasted
+        if let Some(latest) = latest {
+            let since = (Utc::now() - latest).num_milliseconds() as f32 / 1000.0;
+            if since < request.duration_seconds - DURATION_TOLERANCE_SECONDS {
+                verdict = verdict.flag(format!(
+                    "a {:.0} second match finished {:.0} seconds after the previous result",
+                    request.duration_seconds, since
+                ));
+            }
         }
 
         // Find or create user
@@ -36,6 +73,22 @@ impl GameService {
             }
         };
 
+        // Finishing the session and storing the result commit together; if the
+        // store fails, the session stays open for another attempt
+        let mut tx = pool.begin().await?;
+
+        // Results are only taken for a live session this wallet started, and only once
+        let session = GameSessionService::finish(&mut tx, &request.player_wallet_address, &request.game_session_id).await?;
+
+        // Nor can a match outlast the time since its session started
+        let elapsed = (Utc::now() - session.started_at).num_milliseconds() as f32 / 1000.0;
+        if elapsed + DURATION_TOLERANCE_SECONDS < request.duration_seconds {
+            verdict = verdict.flag(format!(
+                "a {:.0} second match was submitted {:.0} seconds after its session started",
+                request.duration_seconds, elapsed
+            ));
+        }
+
         // Store data we need for contract XDR before moving into new_game
         let is_win = request.player_result == "Win";
         let player_wallet = request.player_wallet_address.clone();
@@ -44,100 +97,102 @@ impl GameService {
         // Store the game result
         let new_game = NewGameInstance {
             user_id: Some(user.id),
-            game_session_id: request.game_session_id,
-            player_username: request.player_username,
-            player_wallet_address: request.player_wallet_address,
-            player_result: request.player_result,
+            game_session_id: request.game_session_id.clone(),
+            player_username: request.player_username.clone(),
+            player_wallet_address: request.player_wallet_address.clone(),
+            player_result: request.player_result.clone(),
             player_score: request.player_score,
             opponent_score: request.opponent_score,
             duration_seconds: request.duration_seconds,
-            game_mode: request.game_mode.unwrap_or_else(|| "single_player".to_string()),
+            game_mode: session.game_mode,
         };
 
-        let game_instance = GameRepository::create_game_instance(pool, new_game).await?;
+        let game_instance = match GameRepository::create_game_instance(&mut *tx, new_game).await {
+            Ok(game_instance) => game_instance,
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                return Err(AppError::Conflict(
+                    "Result for this game session was already submitted".to_string()
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        };
 
-        // Generate contract XDR - check join status and generate appropriate XDR
-        let contract_xdr = {
-            info!("🔍 Checking player join status for contract interaction");
+        let under_review = if let Verdict::Flag(reasons) = &verdict {
+            warn!("🚩 Result {} held for review: {}", game_instance.id, reasons.join("; "));
+            SuspiciousResultRepository::record(
+                &mut *tx,
+                &suspicious(&request, Some(game_instance.id), reasons, "pending"),
+            )
+            .await?;
+            true
+        } else {
+            false
+        };
+        tx.commit().await?;
 
-            let config = ContractConfig::default();
-            let config_clone = config.clone();
+        // Joined players' results are recorded on-chain by the oracle; winners who
+        // haven't joined yet get a join XDR to sign first
+        let contract_id = contracts.default_contract_id();
+        let (contract_xdr, contract_operation_id) = if under_review {
+            info!("⏸️ Result is under review, no contract interaction until it's cleared");
+            (None, None)
+        } else if !is_win && !contracts.has_oracle() {
+            info!("📊 Game result recorded (no win), no contract interaction needed");
+            (None, None)
+        } else {
+            info!("🔍 Checking player join status for contract interaction");
 
-            // Check if player has joined the leaderboard
-            let has_joined_function = LeaderboardFunction::HasJoined {
-                player: player_wallet.clone()
-            };
+            match contracts.has_joined(&contract_id, &player_wallet).await {
+                Ok(true) => {
+                    info!("✅ Player {} has already joined the leaderboard", player_wallet);
+                    let operation_id = record_on_chain(
+                        contracts,
+                        &contract_id,
+                        &player_wallet,
+                        &request.game_session_id,
+                        request.player_score,
+                        request.opponent_score,
+                    )
+                    .await;
+                    (None, operation_id)
+                },
+                Ok(false) if is_win => {
+                    info!("🏆 Win detected! Player needs to join first, generating join XDR");
 
-            info!("📞 Calling has_joined for player: {}", player_wallet);
+                    let join_function = LeaderboardFunction::Join {
+                        player: player_wallet.clone()
+                    };
 
-            // Clone variables for the spawned task
-            let config_for_task = config.clone();
-            let player_wallet_for_task = player_wallet.clone();
+                    let join_xdr = match contracts.generate_xdr(&contract_id, &player_wallet, &join_function).await {
+                        Ok(xdr) => contracts.metadata(&contract_id).await.map(|metadata| (xdr, metadata)),
+                        Err(e) => Err(e),
+                    };
 
-            // First check if player has joined
-            let join_status_result = tokio::task::spawn_blocking(move || {
-                tokio::runtime::Handle::current().block_on(async move {
-                    // For now, we'll assume they need to join - in a real implementation
-                    // you'd call the contract to check. This is a simplified approach.
-                    generate_leaderboard_xdr(&config_for_task, &player_wallet_for_task, &has_joined_function).await
-                })
-            }).await;
-
-            match join_status_result {
-                Ok(Ok(_)) => {
-                    info!("✅ Player join status checked successfully");
-                    // For simplicity, we'll generate join XDR for first-time players
-                    // In production, you'd parse the contract response to determine join status
-
-                    if is_win {
-                        info!("🏆 Win detected! Player needs to join first, generating join XDR");
-
-                        let join_function = LeaderboardFunction::Join {
-                            player: player_wallet.clone()
-                        };
-
-                        // Clone variables for the second spawned task
-                        let config_for_join = config_clone.clone();
-                        let player_wallet_for_join = player_wallet.clone();
-
-                        let join_xdr_result = tokio::task::spawn_blocking(move || {
-                            tokio::runtime::Handle::current().block_on(async move {
-                                generate_leaderboard_xdr(&config_for_join, &player_wallet_for_join, &join_function).await
-                            })
-                        }).await;
-
-                        match join_xdr_result {
-                            Ok(Ok(xdr)) => {
-                                info!("✅ Join XDR generated successfully");
-                                info!("🔍 Join XDR preview: {}...{}", &xdr[0..50.min(xdr.len())], &xdr[xdr.len().saturating_sub(50)..]);
-                                Some(ContractXdrInfo {
-                                    xdr,
-                                    function_name: "join".to_string(),
-                                    description: format!("Join leaderboard for player {}", player_name),
-                                    network_passphrase: config_clone.network_passphrase.clone(),
-                                })
-                            },
-                            Ok(Err(e)) => {
-                                warn!("❌ Failed to generate join XDR: {}", e);
-                                None
-                            },
-                            Err(join_error) => {
-                                warn!("❌ Join XDR generation task failed: {}", join_error);
-                                None
-                            }
+                    match join_xdr {
+                        Ok((xdr, metadata)) => {
+                            info!("✅ Join XDR generated successfully");
+                            info!("🔍 Join XDR preview: {}...{}", &xdr[0..50.min(xdr.len())], &xdr[xdr.len().saturating_sub(50)..]);
+                            let info = ContractXdrInfo {
+                                xdr,
+                                function_name: "join".to_string(),
+                                description: format!("Join leaderboard for player {}", player_name),
+                                network_passphrase: metadata.network_passphrase,
+                            };
+                            (Some(info), None)
+                        },
+                        Err(e) => {
+                            warn!("❌ Failed to generate join XDR: {}", e);
+                            (None, None)
                         }
-                    } else {
-                        info!("📊 Game result recorded (no win), no contract interaction needed");
-                        None
                     }
                 },
-                Ok(Err(e)) => {
-                    warn!("❌ Failed to check join status: {}", e);
-                    None
+                Ok(false) => {
+                    info!("📊 Player {} hasn't joined the leaderboard, result kept off-chain", player_wallet);
+                    (None, None)
                 },
-                Err(join_error) => {
-                    warn!("❌ Join status check task failed: {}", join_error);
-                    None
+                Err(e) => {
+                    warn!("❌ Failed to check join status: {}", e);
+                    (None, None)
                 }
             }
         };
@@ -145,9 +200,69 @@ impl GameService {
         Ok(StoreGameResultResponse {
             game_id: game_instance.id,
             contract_xdr,
+            contract_operation_id,
+            under_review,
         })
     }
 
+    /// Suspicious results for the admin review queue
+    pub async fn list_suspicious_results(
+        pool: &DbPool,
+        status: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<Vec<SuspiciousResultRecord>> {
+        let limit = limit.unwrap_or(50).clamp(1, 200);
+        Ok(SuspiciousResultRepository::list(pool, status, limit).await?)
+    }
+
+    /// Settle a pending result; confirmed cheats are removed from the game history
+    pub async fn review_suspicious_result(
+        pool: &DbPool,
+        contracts: &ScalableContractManager,
+        id: i32,
+        decision: &str,
+        note: Option<&str>,
+    ) -> Result<SuspiciousResultRecord> {
+        if !["cleared", "confirmed"].contains(&decision) {
+            return Err(AppError::InvalidInput(
+                "decision must be one of: cleared, confirmed".to_string()
+            ));
+        }
+
+        let record = SuspiciousResultRepository::review(pool, id, decision, note)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("No pending suspicious result {}", id)))?;
+
+        match (decision, record.game_instance_id) {
+            ("confirmed", Some(game_id)) => {
+                GameRepository::delete_game_instance(pool, game_id).await?;
+                info!("🗑️ Removed game {} after confirming suspicious result {}", game_id, id);
+            }
+            // A cleared result goes on-chain like any other
+            ("cleared", Some(_)) => {
+                let contract_id = contracts.default_contract_id();
+                match contracts.has_joined(&contract_id, &record.player_wallet_address).await {
+                    Ok(true) => {
+                        record_on_chain(
+                            contracts,
+                            &contract_id,
+                            &record.player_wallet_address,
+                            &record.game_session_id,
+                            record.player_score,
+                            record.opponent_score,
+                        )
+                        .await;
+                    }
+                    Ok(false) => info!("📊 Player {} hasn't joined the leaderboard, cleared result kept off-chain", record.player_wallet_address),
+                    Err(e) => warn!("❌ Failed to check join status for cleared result {}: {}", id, e),
+                }
+            }
+            _ => {}
+        }
+
+        Ok(record)
+    }
+
     pub async fn get_player_stats(
         pool: &DbPool,
         query: PlayerStatsQuery,
@@ -159,24 +274,20 @@ impl GameService {
     pub async fn get_player_games(
         pool: &DbPool,
         query: PlayerGamesQuery,
+        page: &PaginationQuery,
     ) -> Result<PaginatedResponse<GameInstance>> {
-        let limit = query.limit.unwrap_or(20).min(100); // Cap at 100
-        let offset = 0; // For now, simple pagination
-
-        let games = GameRepository::get_player_games(pool, &query.wallet_address, limit).await?;
-        let total = GameRepository::count_player_games(pool, &query.wallet_address).await?;
-
-        Ok(PaginatedResponse::new(games, total, limit, offset))
+        game_page(pool, Some(&query.wallet_address), &query.filter, page).await
     }
 
+    /// Players ranked by on-chain rating, then wins; ranks are global, so pages only go by offset
     pub async fn get_leaderboard(
         pool: &DbPool,
-        limit: Option<i64>,
+        page: &PaginationQuery,
     ) -> Result<PaginatedResponse<LeaderboardEntry>> {
-        let limit = limit.unwrap_or(20).min(100); // Cap at 100
-        let offset = 0;
+        page.validate().map_err(|(_, message)| AppError::InvalidInput(message))?;
+        let (limit, offset) = (page.limit(), page.offset.max(0));
 
-        let entries = GameRepository::get_leaderboard(pool, limit).await?;
+        let entries = GameRepository::get_leaderboard(pool, limit, offset).await?;
         let total = GameRepository::count_total_players(pool).await?;
 
         Ok(PaginatedResponse::new(entries, total, limit, offset))
@@ -184,18 +295,377 @@ impl GameService {
 
     pub async fn get_recent_games(
         pool: &DbPool,
-        limit: Option<i64>,
+        filter: &GameHistoryFilter,
+        page: &PaginationQuery,
     ) -> Result<PaginatedResponse<GameInstance>> {
-        let limit = limit.unwrap_or(20).min(100); // Cap at 100
-        let offset = 0;
-
-        let games = GameRepository::get_recent_games(pool, limit).await?;
-        let total = GameRepository::count_total_games(pool).await?;
-
-        Ok(PaginatedResponse::new(games, total, limit, offset))
+        game_page(pool, None, filter, page).await
     }
 
     pub async fn get_total_players(pool: &DbPool) -> Result<i64> {
         GameRepository::count_total_players(pool).await.map_err(AppError::Database)
     }
-}
\ No newline at end of file
+}
+
+/// One page of game history, with a cursor for the next if there is one
+async fn game_page(
+    pool: &DbPool,
+    wallet_address: Option<&str>,
+    filter: &GameHistoryFilter,
+    page: &PaginationQuery,
+) -> Result<PaginatedResponse<GameInstance>> {
+    page.validate().map_err(|(_, message)| AppError::InvalidInput(message))?;
+    validate_filter(filter)?;
+    let after = page
+        .cursor
+        .as_deref()
+        .map(|cursor| {
+            GameCursor::decode(cursor)
+                .ok_or_else(|| AppError::InvalidInput(format!("Invalid cursor {}", cursor)))
+        })
+        .transpose()?;
+    let (limit, offset) = (page.limit(), page.offset());
+
+    // One game past the page tells whether there's another page
+    let mut games = GameRepository::list_games(pool, wallet_address, filter, after, limit + 1, offset).await?;
+    let next_cursor = if games.len() as i64 > limit {
+        games.truncate(limit as usize);
+        games.last().and_then(|game| {
+            game.created_at.map(|created_at| GameCursor { created_at, id: game.id }.encode())
+        })
+    } else {
+        None
+    };
+    let total = GameRepository::count_games(pool, wallet_address, filter).await?;
+
+    Ok(PaginatedResponse::new(games, total, limit, offset).with_next_cursor(next_cursor))
+}
+
+fn validate_filter(filter: &GameHistoryFilter) -> Result<()> {
+    if let Some(result) = &filter.result {
+        if !matches!(result.as_str(), "Win" | "Loss" | "Draw") {
+            return Err(AppError::InvalidInput("result must be one of: Win, Loss, Draw".to_string()));
+        }
+    }
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        if from >= to {
+            return Err(AppError::InvalidInput("from must be before to".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Queue record_match for a stored result, signed by the oracle
+/// Returns the queued operation's id; the stored result stands even if queuing fails
+async fn record_on_chain(
+    contracts: &ScalableContractManager,
+    contract_id: &str,
+    player: &str,
+    session_id: &str,
+    score: i32,
+    opponent_score: i32,
+) -> Option<String> {
+    if !contracts.has_oracle() {
+        warn!("⚠️  No oracle key configured, result for session {} isn't recorded on-chain", session_id);
+        return None;
+    }
+
+    let function = LeaderboardFunction::RecordMatch {
+        player: player.to_string(),
+        opponent: None,
+        session_id: session_id.to_string(),
+        score: score.max(0) as u32,
+        opponent_score: opponent_score.max(0) as u32,
+    };
+
+    match contracts.submit_oracle_call(contract_id, function, None).await {
+        Ok(operation_id) => {
+            info!("📤 Queued record_match for session {} as operation {}", session_id, operation_id);
+            Some(operation_id)
+        }
+        Err(e) => {
+            warn!("❌ Failed to queue record_match for session {}: {}", session_id, e);
+            None
+        }
+    }
+}
+
+fn suspicious(
+    request: &StoreGameResultRequest,
+    game_instance_id: Option<i32>,
+    reasons: &[String],
+    status: &str,
+) -> NewSuspiciousResult {
+    NewSuspiciousResult {
+        game_instance_id,
+        // Truncated so rejected submissions with oversized fields still fit the columns
+        game_session_id: request.game_session_id.chars().take(255).collect(),
+        player_wallet_address: request.player_wallet_address.clone(),
+        player_result: request.player_result.chars().take(10).collect(),
+        player_score: request.player_score,
+        opponent_score: request.opponent_score,
+        duration_seconds: request.duration_seconds,
+        game_mode: request.game_mode.as_ref().map(|mode| mode.chars().take(50).collect()),
+        reasons: reasons.to_vec(),
+        status: status.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{models::NewContractEvent, repositories::ContractIndexRepository};
+    use crate::services::soroban::RegistryConfig;
+    use chrono::SecondsFormat;
+    use shared::dto::game::CreateGameSessionRequest;
+    use shared::transcript::{GoalEvent, MatchTranscript, Side};
+
+    const KEY: &[u8] = b"session key";
+    const PLAYER: &str = "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ";
+
+    fn loss(session: &str, duration_seconds: f32) -> StoreGameResultRequest {
+        let goal = |side, at_seconds| GoalEvent { side, at_seconds };
+        let transcript = MatchTranscript {
+            session_id: session.to_string(),
+            goals: vec![
+                goal(Side::Opponent, 10.0),
+                goal(Side::Player, 20.0),
+                goal(Side::Opponent, 30.0),
+                goal(Side::Opponent, 40.0),
+                goal(Side::Player, 50.0),
+                goal(Side::Opponent, 60.0),
+                goal(Side::Opponent, duration_seconds),
+            ],
+            player_score: 2,
+            opponent_score: 5,
+            duration_seconds,
+            input_digest: "ab".repeat(32),
+        };
+
+        StoreGameResultRequest {
+            game_session_id: session.to_string(),
+            player_username: "player".to_string(),
+            player_wallet_address: PLAYER.to_string(),
+            player_result: "Loss".to_string(),
+            player_score: 2,
+            opponent_score: 5,
+            duration_seconds,
+            game_mode: None,
+            transcript: transcript.sign(KEY),
+        }
+    }
+
+    /// A session for the test player that started long enough ago for any match to have finished
+    async fn session(pool: &DbPool) -> String {
+        let request = CreateGameSessionRequest { game_mode: None, config_hash: MatchRules::default().config_hash() };
+        let session = GameSessionService::start(pool, PLAYER, request).await.unwrap();
+        sqlx::query!("UPDATE game_sessions SET started_at = NOW() - INTERVAL '5 minutes' WHERE id = $1", session.id)
+            .execute(pool)
+            .await
+            .unwrap();
+        session.id.to_string()
+    }
+
+    #[sqlx::test]
+    async fn rejects_replays_and_impossible_results(pool: DbPool) {
+        let contracts = ScalableContractManager::new(pool.clone(), &RegistryConfig::from_env()).await.unwrap();
+        let session_id = session(&pool).await;
+
+        let stored = GameService::store_game_result(&pool, &contracts, KEY, loss(&session_id, 90.0)).await.unwrap();
+        assert!(!stored.under_review);
+
+        let replay = GameService::store_game_result(&pool, &contracts, KEY, loss(&session_id, 90.0)).await;
+        assert!(matches!(replay, Err(AppError::Conflict(_))));
+
+        let unknown = GameService::store_game_result(&pool, &contracts, KEY, loss(&uuid::Uuid::new_v4().to_string(), 90.0)).await;
+        assert!(matches!(unknown, Err(AppError::NotFound(_))));
+
+        let impossible = GameService::store_game_result(&pool, &contracts, KEY, loss("session-2", 900.0)).await;
+        assert!(matches!(impossible, Err(AppError::InvalidInput(_))));
+
+        let rejected = GameService::list_suspicious_results(&pool, Some("rejected"), None).await.unwrap();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].game_session_id, "session-2");
+    }
+
+    #[sqlx::test]
+    async fn a_result_that_fails_to_store_leaves_the_session_open(pool: DbPool) {
+        let contracts = ScalableContractManager::new(pool.clone(), &RegistryConfig::from_env()).await.unwrap();
+        let session_id = session(&pool).await;
+
+        // Something else already took the session's game row
+        GameRepository::create_game_instance(&pool, NewGameInstance {
+            user_id: None,
+            game_session_id: session_id.clone(),
+            player_username: "player".to_string(),
+            player_wallet_address: PLAYER.to_string(),
+            player_result: "Win".to_string(),
+            player_score: 5,
+            opponent_score: 0,
+            duration_seconds: 90.0,
+            game_mode: "single_player_vs_ai".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let stored = GameService::store_game_result(&pool, &contracts, KEY, loss(&session_id, 90.0)).await;
+        assert!(matches!(stored, Err(AppError::Conflict(_))));
+        assert_eq!(GameSessionService::get(&pool, PLAYER, &session_id).await.unwrap().status, "active");
+    }
+
+    #[sqlx::test]
+    async fn rejected_results_count_toward_the_rate_limit(pool: DbPool) {
+        let contracts = ScalableContractManager::new(pool.clone(), &RegistryConfig::from_env()).await.unwrap();
+
+        for attempt in 0..MAX_RESULTS_PER_HOUR {
+            let impossible = loss(&format!("session-{}", attempt), 900.0);
+            let result = GameService::store_game_result(&pool, &contracts, KEY, impossible).await;
+            assert!(matches!(result, Err(AppError::InvalidInput(_))));
+        }
+
+        // Over the limit, nothing more reaches the review queue
+        let flood = GameService::store_game_result(&pool, &contracts, KEY, loss("session-flood", 900.0)).await;
+        assert!(matches!(flood, Err(AppError::RateLimited(_))));
+        let rejected = GameService::list_suspicious_results(&pool, Some("rejected"), Some(200)).await.unwrap();
+        assert_eq!(rejected.len() as i64, MAX_RESULTS_PER_HOUR);
+    }
+
+    #[sqlx::test]
+    async fn confirming_a_flagged_result_removes_the_game(pool: DbPool) {
+        let contracts = ScalableContractManager::new(pool.clone(), &RegistryConfig::from_env()).await.unwrap();
+
+        GameService::store_game_result(&pool, &contracts, KEY, loss(&session(&pool).await, 90.0)).await.unwrap();
+        // Finished a 90 second match moments after the previous one
+        let flagged = GameService::store_game_result(&pool, &contracts, KEY, loss(&session(&pool).await, 90.0)).await.unwrap();
+        assert!(flagged.under_review);
+        assert!(flagged.contract_xdr.is_none());
+
+        let pending = GameService::list_suspicious_results(&pool, Some("pending"), None).await.unwrap();
+        let reviewed = GameService::review_suspicious_result(&pool, &contracts, pending[0].id, "confirmed", Some("bot")).await.unwrap();
+        assert_eq!(reviewed.status, "confirmed");
+        assert_eq!(GameRepository::count_total_games(&pool).await.unwrap(), 1);
+
+        let again = GameService::review_suspicious_result(&pool, &contracts, pending[0].id, "cleared", None).await;
+        assert!(matches!(again, Err(AppError::NotFound(_))));
+    }
+
+    /// Store a game played `days_ago`, skipping validation
+    async fn played(pool: &DbPool, result: &str, game_mode: &str, days_ago: i64) -> i32 {
+        let game = GameRepository::create_game_instance(pool, NewGameInstance {
+            user_id: None,
+            game_session_id: uuid::Uuid::new_v4().to_string(),
+            player_username: "player".to_string(),
+            player_wallet_address: PLAYER.to_string(),
+            player_result: result.to_string(),
+            player_score: 0,
+            opponent_score: 0,
+            duration_seconds: 180.0,
+            game_mode: game_mode.to_string(),
+        })
+        .await
+        .unwrap();
+        sqlx::query!(
+            "UPDATE game_instances SET created_at = NOW() - make_interval(days => $2) WHERE id = $1",
+            game.id,
+            days_ago as i32
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        game.id
+    }
+
+    #[sqlx::test]
+    async fn pages_through_filtered_history_by_cursor(pool: DbPool) {
+        let mut ids = Vec::new();
+        for (days_ago, result) in [(5, "Win"), (4, "Loss"), (3, "Win"), (2, "Draw"), (1, "Win")] {
+            ids.push(played(&pool, result, "single_player_vs_ai", days_ago).await);
+        }
+        played(&pool, "Win", "multiplayer", 0).await;
+
+        let query = |filter: &str| {
+            let uri = format!("/api/games/player-games?wallet_address={}&game_mode=single_player_vs_ai{}", PLAYER, filter)
+                .parse()
+                .unwrap();
+            axum::extract::Query::<PlayerGamesQuery>::try_from_uri(&uri).unwrap().0
+        };
+        let page = |cursor: Option<String>| PaginationQuery { limit: 2, offset: 0, cursor };
+
+        // Newest first, following next_cursor to the end
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let games = GameService::get_player_games(&pool, query(""), &page(cursor)).await.unwrap();
+            assert_eq!(games.total, 5);
+            seen.extend(games.items.iter().map(|game| game.id));
+            cursor = games.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(seen, ids.iter().rev().copied().collect::<Vec<_>>());
+
+        let oldest = GameService::get_player_games(&pool, query("&sort=oldest"), &page(None)).await.unwrap();
+        assert_eq!(oldest.items.iter().map(|game| game.id).collect::<Vec<_>>(), ids[..2]);
+
+        let offset = PaginationQuery { limit: 2, offset: 2, cursor: None };
+        let third = GameService::get_player_games(&pool, query(""), &offset).await.unwrap();
+        assert_eq!(third.items[0].id, ids[2]);
+
+        let wins = GameService::get_player_games(&pool, query("&result=Win"), &page(None)).await.unwrap();
+        assert_eq!(wins.total, 3);
+        assert!(wins.items.iter().all(|game| game.player_result == "Win"));
+
+        // Between four and two days ago, written with a Z so nothing needs escaping
+        let at = |days, hours| (Utc::now() - Duration::days(days) + Duration::hours(hours)).to_rfc3339_opts(SecondsFormat::Secs, true);
+        let range = format!("&from={}&to={}", at(4, -1), at(2, 1));
+        let ranged = GameService::get_player_games(&pool, query(&range), &page(None)).await.unwrap();
+        assert_eq!(ranged.total, 3);
+
+        let bad_cursor = page(Some("yesterday".to_string()));
+        assert!(matches!(GameService::get_player_games(&pool, query(""), &bad_cursor).await, Err(AppError::InvalidInput(_))));
+        assert!(matches!(GameService::get_player_games(&pool, query("&result=Forfeit"), &page(None)).await, Err(AppError::InvalidInput(_))));
+    }
+
+    #[sqlx::test]
+    async fn leaderboard_ranks_by_indexed_rating_before_wins(pool: DbPool) {
+        const RATED: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+        const UNRATED: &str = "guest-wallet";
+
+        for (wallet, wins) in [(PLAYER, 3), (RATED, 1), (UNRATED, 5)] {
+            for _ in 0..wins {
+                GameRepository::create_game_instance(&pool, NewGameInstance {
+                    user_id: None,
+                    game_session_id: uuid::Uuid::new_v4().to_string(),
+                    player_username: wallet.to_string(),
+                    player_wallet_address: wallet.to_string(),
+                    player_result: "Win".to_string(),
+                    player_score: 1,
+                    opponent_score: 0,
+                    duration_seconds: 180.0,
+                    game_mode: "single_player_vs_ai".to_string(),
+                })
+                .await
+                .unwrap();
+            }
+        }
+
+        let rating_changed = |id: &str, wallet: &str, rating| NewContractEvent {
+            event_id: id.to_string(),
+            ledger: 100,
+            event_type: "rating_changed".to_string(),
+            player_wallet_address: wallet.to_string(),
+            wins: None,
+            rating: Some(rating),
+        };
+        let events = [rating_changed("100-1", PLAYER, 1190), rating_changed("100-2", RATED, 1250)];
+        ContractIndexRepository::record_page(&pool, "contract", &events, "100-2", 100).await.unwrap();
+
+        let page = PaginationQuery { limit: 10, offset: 0, cursor: None };
+        let leaderboard = GameService::get_leaderboard(&pool, &page).await.unwrap().items;
+        let ranked: Vec<_> = leaderboard
+            .iter()
+            .map(|entry| (entry.rank, entry.wallet_address.as_str(), entry.rating, entry.wins))
+            .collect();
+        assert_eq!(ranked, vec![(1, RATED, Some(1250), 1), (2, PLAYER, Some(1190), 3), (3, UNRATED, None, 5)]);
+    }
+}
@@ -0,0 +1,167 @@
+// Server-issued game sessions
+//
+// A match starts with POST /api/sessions, which records who is playing which
+// mode under which rules. Storing the result finishes the session, so a result
+// can only be stored once per session, and only while the session is live.
+// Sessions nobody finishes are expired by a background sweep.
+
+use chrono::{Duration, Utc};
+use sqlx::PgConnection;
+use shared::dto::game::{CreateGameSessionRequest, MatchRules};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::database::connection::DbPool;
+use crate::database::models::GameSessionRecord;
+use crate::database::repositories::GameSessionRepository;
+use crate::error::{AppError, Result};
+
+/// How long a session stays open; a full match plus room for menus and pauses
+pub const SESSION_LIFETIME_MINUTES: i64 = 15;
+
+const DEFAULT_GAME_MODE: &str = "single_player_vs_ai";
+
+pub struct GameSessionService;
+
+impl GameSessionService {
+    pub async fn start(
+        pool: &DbPool,
+        wallet_address: &str,
+        request: CreateGameSessionRequest,
+    ) -> Result<GameSessionRecord> {
+        if request.config_hash != MatchRules::default().config_hash() {
+            return Err(AppError::InvalidInput(
+                "Game is running different match rules than the server".to_string()
+            ));
+        }
+
+        let game_mode = request.game_mode.unwrap_or_else(|| DEFAULT_GAME_MODE.to_string());
+        if game_mode.trim().is_empty() || game_mode.len() > 50 {
+            return Err(AppError::InvalidInput("game_mode must be 1 to 50 characters".to_string()));
+        }
+
+        let expires_at = Utc::now() + Duration::minutes(SESSION_LIFETIME_MINUTES);
+        let session = GameSessionRepository::create(
+            pool,
+            wallet_address,
+            &game_mode,
+            &request.config_hash,
+            expires_at,
+        )
+        .await?;
+
+        info!("🎲 Game session {} started for {}", session.id, wallet_address);
+        Ok(session)
+    }
+
+    /// Finish the wallet's active session, explaining why if it can't be
+    /// Run it in the transaction that stores the result, so a failed store leaves the session open
+    pub async fn finish(conn: &mut PgConnection, wallet_address: &str, session_id: &str) -> Result<GameSessionRecord> {
+        let id = parse_session_id(session_id)?;
+
+        if let Some(session) = GameSessionRepository::finish(&mut *conn, id, wallet_address).await? {
+            return Ok(session);
+        }
+
+        let session = GameSessionRepository::find_by_id(&mut *conn, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Unknown game session {}", session_id)))?;
+
+        Err(if session.player_wallet_address != wallet_address {
+            AppError::Forbidden("Game session belongs to another wallet".to_string())
+        } else if session.status == "finished" {
+            AppError::Conflict("Game session is already finished".to_string())
+        } else {
+            AppError::InvalidInput("Game session has expired".to_string())
+        })
+    }
+
+    pub async fn get(pool: &DbPool, wallet_address: &str, session_id: &str) -> Result<GameSessionRecord> {
+        let session = GameSessionRepository::find_by_id(pool, parse_session_id(session_id)?)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Unknown game session {}", session_id)))?;
+
+        if session.player_wallet_address != wallet_address {
+            return Err(AppError::Forbidden("Game session belongs to another wallet".to_string()));
+        }
+        Ok(session)
+    }
+
+    /// Expire abandoned sessions in the background
+    pub fn spawn_expiry(pool: DbPool, interval: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!("⌛ Game session expiry started (every {:?})", interval);
+
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+
+                match GameSessionRepository::expire_stale(&pool).await {
+                    Ok(0) => {}
+                    Ok(expired) => info!("⌛ Expired {} abandoned game sessions", expired),
+                    Err(e) => warn!("❌ Failed to expire game sessions: {}", e),
+                }
+            }
+        })
+    }
+}
+
+/// Session ids are UUIDs; anything else can't name a session
+fn parse_session_id(session_id: &str) -> Result<Uuid> {
+    Uuid::parse_str(session_id)
+        .map_err(|_| AppError::NotFound(format!("Unknown game session {}", session_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: &str = "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ";
+
+    fn request() -> CreateGameSessionRequest {
+        CreateGameSessionRequest {
+            game_mode: None,
+            config_hash: MatchRules::default().config_hash(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn sessions_finish_once_and_only_for_their_wallet(pool: DbPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let session = GameSessionService::start(&pool, PLAYER, request()).await.unwrap();
+        let id = session.id.to_string();
+        assert_eq!(session.status, "active");
+        assert_eq!(session.game_mode, DEFAULT_GAME_MODE);
+
+        let other = "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI";
+        assert!(matches!(GameSessionService::finish(&mut conn, other, &id).await, Err(AppError::Forbidden(_))));
+
+        let finished = GameSessionService::finish(&mut conn, PLAYER, &id).await.unwrap();
+        assert_eq!(finished.status, "finished");
+        assert!(matches!(GameSessionService::finish(&mut conn, PLAYER, &id).await, Err(AppError::Conflict(_))));
+
+        assert!(matches!(GameSessionService::finish(&mut conn, PLAYER, "session_1700000000").await, Err(AppError::NotFound(_))));
+    }
+
+    #[sqlx::test]
+    async fn abandoned_sessions_expire(pool: DbPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let session = GameSessionService::start(&pool, PLAYER, request()).await.unwrap();
+        sqlx::query!("UPDATE game_sessions SET expires_at = NOW() - INTERVAL '1 minute' WHERE id = $1", session.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let id = session.id.to_string();
+        assert!(matches!(GameSessionService::finish(&mut conn, PLAYER, &id).await, Err(AppError::InvalidInput(_))));
+        assert_eq!(GameSessionRepository::expire_stale(&pool).await.unwrap(), 1);
+        assert_eq!(GameSessionService::get(&pool, PLAYER, &id).await.unwrap().status, "expired");
+    }
+
+    #[sqlx::test]
+    async fn sessions_for_other_rules_are_refused(pool: DbPool) {
+        let rules = MatchRules { winning_score: 50, ..MatchRules::default() };
+        let request = CreateGameSessionRequest { game_mode: None, config_hash: rules.config_hash() };
+        assert!(matches!(GameSessionService::start(&pool, PLAYER, request).await, Err(AppError::InvalidInput(_))));
+    }
+}
@@ -1,5 +1,8 @@
 pub mod auth_service;
 pub mod game_service;
+pub mod game_session_service;
+pub mod result_validation;
+pub mod wallet_auth;
 
 // Soroban smart contract infrastructure
 pub mod soroban;
@@ -7,4 +10,6 @@ pub mod soroban;
 // Re-export commonly used types
 pub use auth_service::AuthService;
 pub use game_service::GameService;
+pub use game_session_service::GameSessionService;
+pub use wallet_auth::WalletAuth;
 pub use soroban::ScalableContractManager;
\ No newline at end of file
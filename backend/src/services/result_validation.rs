@@ -0,0 +1,279 @@
+// Anti-cheat checks on submitted game results
+//
+// Results that the match rules can't produce are rejected outright. Results
+// that are possible but unlikely are stored and flagged for review instead,
+// so a lucky player isn't turned away by a heuristic.
+//
+// Every result carries the game's transcript of the match, signed with the key
+// issued for its session. The signature has to hold and the transcript has to
+// tell the same story as the submitted scores and duration.
+
+use shared::dto::game::{MatchRules, StoreGameResultRequest};
+use shared::transcript::{MatchTranscript, Side};
+
+/// Slack for frame timing when comparing durations against the match clock
+pub const DURATION_TOLERANCE_SECONDS: f32 = 2.0;
+
+/// Goals come with a kick-off reset; faster than this on average is flagged
+pub const MIN_SECONDS_PER_GOAL: f32 = 3.0;
+
+/// Results a wallet may submit per hour
+pub const MAX_RESULTS_PER_HOUR: i64 = 30;
+
+/// What to do with a submitted result
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Accept,
+    /// Store it, but hold it for review
+    Flag(Vec<String>),
+    /// The rules can't produce this result
+    Reject(Vec<String>),
+}
+
+impl Verdict {
+    /// Add a reason to review, unless the result is already rejected
+    pub fn flag(self, reason: String) -> Self {
+        match self {
+            Verdict::Accept => Verdict::Flag(vec![reason]),
+            Verdict::Flag(mut reasons) => {
+                reasons.push(reason);
+                Verdict::Flag(reasons)
+            }
+            rejected => rejected,
+        }
+    }
+}
+
+/// Check a result and its transcript against the match rules, without history
+pub fn validate_result(
+    request: &StoreGameResultRequest,
+    rules: &MatchRules,
+    session_key: &[u8],
+) -> Verdict {
+    let mut violations = Vec::new();
+
+    if request.transcript.verify(session_key) {
+        check_transcript(request, &request.transcript.transcript, rules, &mut violations);
+    } else {
+        violations.push("transcript isn't signed with this session's key".to_string());
+    }
+
+    if request.game_session_id.trim().is_empty() || request.game_session_id.len() > 255 {
+        violations.push("game_session_id must be 1 to 255 characters".to_string());
+    }
+
+    let (player, opponent) = (request.player_score, request.opponent_score);
+    for (side, score) in [("player_score", player), ("opponent_score", opponent)] {
+        if !(0..=rules.winning_score).contains(&score) {
+            violations.push(format!("{} must be between 0 and {}", side, rules.winning_score));
+        }
+    }
+
+    let duration = request.duration_seconds;
+    let longest = rules.match_duration_seconds + DURATION_TOLERANCE_SECONDS;
+    if !duration.is_finite() || duration <= 0.0 || duration > longest {
+        violations.push(format!(
+            "duration_seconds must be more than 0 and at most {}",
+            rules.match_duration_seconds
+        ));
+    }
+
+    let consistent = match request.player_result.as_str() {
+        "Win" => player > opponent,
+        "Loss" => player < opponent,
+        "Draw" => player == opponent,
+        _ => {
+            violations.push("player_result must be one of: Win, Loss, Draw".to_string());
+            true
+        }
+    };
+    if !consistent {
+        violations.push(format!(
+            "{} doesn't match a score of {}-{}",
+            request.player_result, player, opponent
+        ));
+    }
+
+    // A match ends when a side reaches the winning score or the clock runs out
+    let decided_early = player == rules.winning_score || opponent == rules.winning_score;
+    if player == rules.winning_score && opponent == rules.winning_score {
+        violations.push(format!("only one side can reach {} goals", rules.winning_score));
+    }
+    if !decided_early && duration.is_finite()
+        && duration < rules.match_duration_seconds - DURATION_TOLERANCE_SECONDS
+    {
+        violations.push(format!(
+            "nobody reached {} goals, so the match should have lasted {} seconds",
+            rules.winning_score, rules.match_duration_seconds
+        ));
+    }
+
+    if !violations.is_empty() {
+        return Verdict::Reject(violations);
+    }
+
+    let goals = (player + opponent) as f32;
+    if goals > 0.0 && duration / goals < MIN_SECONDS_PER_GOAL {
+        return Verdict::Flag(vec![format!(
+            "{} goals in {:.1} seconds is faster than one every {} seconds",
+            goals, duration, MIN_SECONDS_PER_GOAL
+        )]);
+    }
+
+    Verdict::Accept
+}
+
+/// The transcript has to agree with the request and with itself
+fn check_transcript(
+    request: &StoreGameResultRequest,
+    transcript: &MatchTranscript,
+    rules: &MatchRules,
+    violations: &mut Vec<String>,
+) {
+    if transcript.session_id != request.game_session_id {
+        violations.push("transcript is for a different game session".to_string());
+    }
+    if transcript.player_score != request.player_score
+        || transcript.opponent_score != request.opponent_score
+        || (transcript.duration_seconds - request.duration_seconds).abs() > 0.01
+    {
+        violations.push("transcript doesn't match the submitted score and duration".to_string());
+    }
+
+    let count = |side| transcript.goals.iter().filter(|goal| goal.side == side).count() as i32;
+    if count(Side::Player) != transcript.player_score || count(Side::Opponent) != transcript.opponent_score {
+        violations.push("transcript goals don't add up to the final score".to_string());
+    }
+
+    let (mut previous, mut player, mut opponent) = (0.0, 0, 0);
+    for (i, goal) in transcript.goals.iter().enumerate() {
+        if !goal.at_seconds.is_finite() || goal.at_seconds < previous || goal.at_seconds > transcript.duration_seconds {
+            violations.push("transcript goals must be in order and within the match".to_string());
+            break;
+        }
+        previous = goal.at_seconds;
+        match goal.side {
+            Side::Player => player += 1,
+            Side::Opponent => opponent += 1,
+        }
+        // A match decided on goals ends with the winning one
+        let decided = player == rules.winning_score || opponent == rules.winning_score;
+        if decided && (i + 1 < transcript.goals.len()
+            || transcript.duration_seconds - goal.at_seconds > DURATION_TOLERANCE_SECONDS)
+        {
+            violations.push("match went on after the winning goal".to_string());
+            break;
+        }
+    }
+
+    let digest = &transcript.input_digest;
+    if digest.len() != 64 || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+        violations.push("transcript input digest must be a hex SHA-256".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::transcript::GoalEvent;
+
+    const KEY: &[u8] = b"session key";
+
+    /// A result with a signed transcript that agrees with it, goals spread over the match
+    fn result(player_result: &str, player_score: i32, opponent_score: i32, duration_seconds: f32) -> StoreGameResultRequest {
+        let session_id = "8b0f7f5e-3c1a-4f7e-9a51-1d2f0c6e7b42".to_string();
+        let player_goals = std::iter::repeat_n(Side::Player, player_score.max(0) as usize);
+        let opponent_goals = std::iter::repeat_n(Side::Opponent, opponent_score.max(0) as usize);
+        // The leader scores last, so a decided match ends on its winning goal
+        let sides: Vec<Side> = if player_score >= opponent_score {
+            opponent_goals.chain(player_goals).collect()
+        } else {
+            player_goals.chain(opponent_goals).collect()
+        };
+        let goals: Vec<GoalEvent> = sides
+            .into_iter()
+            .enumerate()
+            .map(|(i, side)| GoalEvent {
+                side,
+                at_seconds: duration_seconds * (i + 1) as f32 / (player_score + opponent_score) as f32,
+            })
+            .collect();
+
+        StoreGameResultRequest {
+            game_session_id: session_id.clone(),
+            player_username: "player".to_string(),
+            player_wallet_address: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string(),
+            player_result: player_result.to_string(),
+            player_score,
+            opponent_score,
+            duration_seconds,
+            game_mode: None,
+            transcript: MatchTranscript {
+                session_id,
+                goals,
+                player_score,
+                opponent_score,
+                duration_seconds,
+                input_digest: "ab".repeat(32),
+            }
+            .sign(KEY),
+        }
+    }
+
+    #[test]
+    fn accepts_results_the_rules_can_produce() {
+        let rules = MatchRules::default();
+        assert_eq!(validate_result(&result("Win", 5, 2, 95.0), &rules, KEY), Verdict::Accept);
+        assert_eq!(validate_result(&result("Loss", 1, 2, 180.0), &rules, KEY), Verdict::Accept);
+        assert_eq!(validate_result(&result("Draw", 0, 0, 179.5), &rules, KEY), Verdict::Accept);
+    }
+
+    #[test]
+    fn rejects_impossible_results() {
+        let rules = MatchRules::default();
+        for impossible in [
+            result("Win", 999, 0, 60.0),      // above the winning score
+            result("Win", 5, 1, -3.0),        // negative duration
+            result("Win", 5, 1, f32::NAN),
+            result("Win", 2, 4, 180.0),       // win with the lower score
+            result("Draw", 5, 5, 120.0),      // both sides can't reach the winning score
+            result("Loss", 1, 3, 40.0),       // undecided match that ended before full time
+            result("Win", 5, 3, 600.0),       // longer than a match
+            result("Forfeit", 0, 0, 180.0),
+        ] {
+            assert!(
+                matches!(validate_result(&impossible, &rules, KEY), Verdict::Reject(_)),
+                "{:?} should be rejected",
+                impossible
+            );
+        }
+    }
+
+    #[test]
+    fn flags_implausibly_fast_scoring() {
+        let rules = MatchRules::default();
+        assert!(matches!(validate_result(&result("Win", 5, 0, 8.0), &rules, KEY), Verdict::Flag(_)));
+    }
+
+    #[test]
+    fn rejects_transcripts_that_dont_hold_up() {
+        let rules = MatchRules::default();
+
+        let forged = result("Win", 5, 2, 95.0);
+        assert!(matches!(validate_result(&forged, &rules, b"another key"), Verdict::Reject(_)));
+
+        // Scores edited after signing
+        let mut edited = result("Loss", 1, 5, 95.0);
+        edited.player_result = "Win".to_string();
+        edited.player_score = 5;
+        edited.opponent_score = 1;
+        assert!(matches!(validate_result(&edited, &rules, KEY), Verdict::Reject(_)));
+
+        // Signed honestly, but the goals don't add up
+        let mut transcript = result("Win", 5, 2, 95.0).transcript.transcript;
+        transcript.goals.pop();
+        let mut inconsistent = result("Win", 5, 2, 95.0);
+        inconsistent.transcript = transcript.sign(KEY);
+        assert!(matches!(validate_result(&inconsistent, &rules, KEY), Verdict::Reject(_)));
+    }
+}
@@ -1,113 +1,421 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use shared::dto::contract::{ContractValue, LeaderboardFunction};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use tracing::{debug, info, warn};
 
-/// Cache entry with expiration
+/// Approximate memory held by a cached value, used to bound the cache in bytes
+pub trait CacheWeight {
+    fn weight(&self) -> usize;
+}
+
+impl CacheWeight for Vec<u8> {
+    fn weight(&self) -> usize {
+        self.len()
+    }
+}
+
+impl CacheWeight for String {
+    fn weight(&self) -> usize {
+        self.len()
+    }
+}
+
+impl CacheWeight for ContractValue {
+    fn weight(&self) -> usize {
+        // The JSON form tracks the in-memory size closely enough for a budget
+        serde_json::to_vec(self).map(|bytes| bytes.len()).unwrap_or(0)
+    }
+}
+
+/// Cache key for a contract function, covering every argument
+///
+/// Two calls only share an entry when they'd send the same invocation, so
+/// `GetLeaderboard { limit: 10 }` and `GetLeaderboard { limit: 50 }` don't collide.
+pub fn function_key(prefix: &str, function: &LeaderboardFunction) -> Result<String, serde_json::Error> {
+    Ok(format!("{}:{}", prefix, serde_json::to_string(function)?))
+}
+
+/// Size bounds and default TTL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheConfig {
+    pub max_entries: usize,
+    pub max_bytes: usize,
+    pub default_ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1024,
+            max_bytes: 4 * 1024 * 1024,
+            default_ttl: Duration::from_secs(300),
+        }
+    }
+}
+
+/// How long an entry is fresh, and how much longer it may be served stale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachePolicy {
+    pub ttl: Duration,
+    pub stale_for: Duration,
+}
+
+impl CachePolicy {
+    /// Served only while fresh
+    pub fn fresh(ttl: Duration) -> Self {
+        Self { ttl, stale_for: Duration::ZERO }
+    }
+
+    /// Served stale for `stale_for` past the TTL while a refresh runs in the background
+    pub fn stale_while_revalidate(ttl: Duration, stale_for: Duration) -> Self {
+        Self { ttl, stale_for }
+    }
+}
+
+/// Where a `get_or_fetch` answer came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLookup {
+    Fresh,
+    Stale,
+    Miss,
+}
+
+/// Cache entry with freshness and staleness deadlines
 #[derive(Clone, Debug)]
 struct CacheEntry<T> {
     value: T,
-    expires_at: Instant,
+    bytes: usize,
+    fresh_until: Instant,
+    stale_until: Instant,
+    last_used: u64,
 }
 
-/// High-performance in-memory cache for contract state
-/// Can be extended to use Redis for distributed caching
+/// Entries plus their recency order; the lowest tick is the least recently used
+struct CacheStore<T> {
+    entries: HashMap<String, CacheEntry<T>>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    bytes: usize,
+}
+
+impl<T> CacheStore<T> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            bytes: 0,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn touch(&mut self, key: &str) -> Option<&CacheEntry<T>> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        self.recency.remove(&entry.last_used);
+        self.recency.insert(tick, key.to_string());
+        entry.last_used = tick;
+        Some(entry)
+    }
+
+    fn insert(&mut self, key: String, mut entry: CacheEntry<T>) {
+        self.remove(&key);
+        entry.last_used = self.next_tick();
+        self.bytes += entry.bytes;
+        self.recency.insert(entry.last_used, key.clone());
+        self.entries.insert(key, entry);
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry<T>> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.last_used);
+        self.bytes -= entry.bytes;
+        Some(entry)
+    }
+
+    /// Drop least recently used entries until both bounds hold
+    fn evict_to(&mut self, config: &CacheConfig) -> u64 {
+        let mut evicted = 0;
+        while self.entries.len() > config.max_entries || self.bytes > config.max_bytes {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.bytes;
+                evicted += 1;
+                debug!("🗑️  Cache EVICTED key: {}", key);
+            }
+        }
+        evicted
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.bytes = 0;
+    }
+}
+
+type InFlight = std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>;
+
+/// Holds a key's fetch slot; the slot is forgotten once nobody else is waiting on it
+struct Flight<'a> {
+    inflight: &'a InFlight,
+    key: String,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        self.guard.take();
+        let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+        if inflight.get(&self.key).is_some_and(|slot| Arc::strong_count(slot) == 1) {
+            inflight.remove(&self.key);
+        }
+    }
+}
+
+/// Bounded in-memory LRU cache for contract state
+///
+/// Entries are weighed in bytes and the least recently used go first once
+/// either bound is reached. Concurrent misses for one key share a single
+/// fetch, and entries can be served stale while a refresh runs.
 pub struct ContractCache<T>
 where
-    T: Clone + Send + Sync,
+    T: Clone + Send + Sync + CacheWeight,
 {
-    store: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
-    default_ttl: Duration,
+    store: Mutex<CacheStore<T>>,
+    inflight: InFlight,
+    config: CacheConfig,
+    evictions: AtomicU64,
 }
 
 impl<T> ContractCache<T>
 where
-    T: Clone + Send + Sync,
+    T: Clone + Send + Sync + CacheWeight,
 {
-    pub fn new(default_ttl: Duration) -> Self {
+    pub fn new(config: CacheConfig) -> Self {
         Self {
-            store: Arc::new(RwLock::new(HashMap::new())),
-            default_ttl,
+            store: Mutex::new(CacheStore::new()),
+            inflight: std::sync::Mutex::new(HashMap::new()),
+            config,
+            evictions: AtomicU64::new(0),
         }
     }
 
-    /// Get value from cache
+    pub fn config(&self) -> CacheConfig {
+        self.config
+    }
+
+    /// Get a fresh value from cache
     pub async fn get(&self, key: &str) -> Option<T> {
-        let store = self.store.read().await;
-
-        if let Some(entry) = store.get(key) {
-            if Instant::now() < entry.expires_at {
-                debug!("✅ Cache HIT for key: {}", key);
-                return Some(entry.value.clone());
-            } else {
-                debug!("⏰ Cache EXPIRED for key: {}", key);
-            }
-        } else {
-            debug!("❌ Cache MISS for key: {}", key);
+        match self.lookup(key).await {
+            Some((value, CacheLookup::Fresh)) => Some(value),
+            _ => None,
         }
-
-        None
     }
 
     /// Set value in cache with custom TTL
     pub async fn set(&self, key: String, value: T, ttl: Option<Duration>) {
-        let ttl = ttl.unwrap_or(self.default_ttl);
-        let mut store = self.store.write().await;
+        self.insert(key, value, CachePolicy::fresh(ttl.unwrap_or(self.config.default_ttl))).await;
+    }
+
+    /// Set value in cache under `policy`
+    pub async fn insert(&self, key: String, value: T, policy: CachePolicy) {
+        let bytes = value.weight();
+        if bytes > self.config.max_bytes {
+            debug!("⚠️  Cache SKIPPED key: {} ({} bytes is over the limit)", key, bytes);
+            return;
+        }
+
+        let now = Instant::now();
+        let fresh_until = now + policy.ttl;
+        let entry = CacheEntry {
+            value,
+            bytes,
+            fresh_until,
+            stale_until: fresh_until + policy.stale_for,
+            last_used: 0,
+        };
+
+        let mut store = self.store.lock().await;
+        store.insert(key.clone(), entry);
+        let evicted = store.evict_to(&self.config);
+        drop(store);
+
+        if evicted > 0 {
+            self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+        debug!("💾 Cache SET for key: {} (TTL: {:?}, stale for: {:?})", key, policy.ttl, policy.stale_for);
+    }
+
+    /// Cached value for `key`, or the result of `fetch` which is then cached
+    ///
+    /// Only one fetch per key runs at a time; callers that miss meanwhile wait
+    /// for it and take its result from the cache. A stale entry is returned
+    /// straight away and refreshed in the background.
+    pub async fn get_or_fetch<E, F, Fut>(
+        self: &Arc<Self>,
+        key: &str,
+        policy: CachePolicy,
+        fetch: F,
+    ) -> Result<(T, CacheLookup), E>
+    where
+        T: 'static,
+        E: Display + Send + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        match self.lookup(key).await {
+            Some((value, CacheLookup::Fresh)) => return Ok((value, CacheLookup::Fresh)),
+            Some((value, _)) => {
+                self.revalidate(key.to_string(), policy, fetch);
+                return Ok((value, CacheLookup::Stale));
+            }
+            None => {}
+        }
+
+        let _flight = self.join_flight(key).await;
+
+        // Whoever held the slot before us may have just filled the entry
+        if let Some((value, CacheLookup::Fresh)) = self.lookup(key).await {
+            return Ok((value, CacheLookup::Fresh));
+        }
+
+        debug!("❌ Cache MISS for key: {}", key);
+        let value = fetch().await?;
+        self.insert(key.to_string(), value.clone(), policy).await;
+        Ok((value, CacheLookup::Miss))
+    }
+
+    /// Refresh a stale entry unless a fetch for it is already running
+    fn revalidate<E, F, Fut>(self: &Arc<Self>, key: String, policy: CachePolicy, fetch: F)
+    where
+        T: 'static,
+        E: Display + Send + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let cache = self.clone();
+        tokio::spawn(async move {
+            let Some(_flight) = cache.try_join_flight(&key) else {
+                return;
+            };
+
+            debug!("🔄 Cache REVALIDATING key: {}", key);
+            match fetch().await {
+                Ok(value) => cache.insert(key, value, policy).await,
+                Err(error) => warn!("❌ Failed to refresh cache key {}: {}", key, error),
+            }
+        });
+    }
+
+    /// Value and freshness for `key`, marking it recently used
+    async fn lookup(&self, key: &str) -> Option<(T, CacheLookup)> {
+        let mut store = self.store.lock().await;
+        let now = Instant::now();
+
+        let entry = store.touch(key)?;
+        if now < entry.fresh_until {
+            debug!("✅ Cache HIT for key: {}", key);
+            Some((entry.value.clone(), CacheLookup::Fresh))
+        } else if now < entry.stale_until {
+            debug!("⏰ Cache STALE for key: {}", key);
+            Some((entry.value.clone(), CacheLookup::Stale))
+        } else {
+            debug!("⏰ Cache EXPIRED for key: {}", key);
+            None
+        }
+    }
+
+    fn flight_slot(&self, key: &str) -> Arc<Mutex<()>> {
+        let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+        inflight.entry(key.to_string()).or_default().clone()
+    }
 
-        store.insert(
-            key.clone(),
-            CacheEntry {
-                value,
-                expires_at: Instant::now() + ttl,
-            },
-        );
+    async fn join_flight(&self, key: &str) -> Flight<'_> {
+        let guard = self.flight_slot(key).lock_owned().await;
+        Flight {
+            inflight: &self.inflight,
+            key: key.to_string(),
+            guard: Some(guard),
+        }
+    }
 
-        debug!("💾 Cache SET for key: {} (TTL: {:?})", key, ttl);
+    fn try_join_flight(&self, key: &str) -> Option<Flight<'_>> {
+        // Dropping a Flight that never held the slot still tidies it away
+        let mut flight = Flight {
+            inflight: &self.inflight,
+            key: key.to_string(),
+            guard: None,
+        };
+        flight.guard = Some(self.flight_slot(key).try_lock_owned().ok()?);
+        Some(flight)
     }
 
     /// Invalidate cache entry
     pub async fn invalidate(&self, key: &str) {
-        let mut store = self.store.write().await;
+        let mut store = self.store.lock().await;
         if store.remove(key).is_some() {
             debug!("🗑️  Cache INVALIDATED for key: {}", key);
         }
     }
 
-    /// Clear all expired entries
+    /// Clear all entries that can no longer be served, even stale
     pub async fn cleanup_expired(&self) -> usize {
-        let mut store = self.store.write().await;
+        let mut store = self.store.lock().await;
         let now = Instant::now();
-        let initial_count = store.len();
 
-        store.retain(|_, entry| entry.expires_at > now);
+        let expired: Vec<String> = store
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.stale_until <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            store.remove(key);
+        }
 
-        let removed = initial_count - store.len();
-        if removed > 0 {
-            info!("🧹 Cleaned up {} expired cache entries", removed);
+        if !expired.is_empty() {
+            info!("🧹 Cleaned up {} expired cache entries", expired.len());
         }
-        removed
+        expired.len()
     }
 
     /// Get cache statistics
     pub async fn stats(&self) -> CacheStats {
-        let store = self.store.read().await;
+        let store = self.store.lock().await;
         let now = Instant::now();
 
-        let expired = store.values().filter(|e| e.expires_at <= now).count();
+        let active = store.entries.values().filter(|e| now < e.fresh_until).count();
+        let expired = store.entries.values().filter(|e| e.stale_until <= now).count();
 
         CacheStats {
-            total_entries: store.len(),
+            total_entries: store.entries.len(),
             expired_entries: expired,
-            active_entries: store.len() - expired,
+            active_entries: active,
+            stale_entries: store.entries.len() - active - expired,
+            total_bytes: store.bytes,
+            max_entries: self.config.max_entries,
+            max_bytes: self.config.max_bytes,
+            evictions: self.evictions.load(Ordering::Relaxed),
         }
     }
 
     /// Clear all cache entries
     pub async fn clear(&self) {
-        let mut store = self.store.write().await;
-        let count = store.len();
+        let mut store = self.store.lock().await;
+        let count = store.entries.len();
         store.clear();
         info!("🗑️  Cleared {} cache entries", count);
     }
@@ -118,18 +426,23 @@ pub struct CacheStats {
     pub total_entries: usize,
     pub expired_entries: usize,
     pub active_entries: usize,
+    pub stale_entries: usize,
+    pub total_bytes: usize,
+    pub max_entries: usize,
+    pub max_bytes: usize,
+    pub evictions: u64,
 }
 
 /// Specialized cache for leaderboard data
 pub type LeaderboardCache = ContractCache<Vec<u8>>;
 
 /// Specialized cache for contract function results
-pub type FunctionResultCache = ContractCache<String>;
+pub type FunctionResultCache = ContractCache<ContractValue>;
 
 /// Start background cleanup task
 pub fn start_cache_cleanup_task<T>(cache: Arc<ContractCache<T>>)
 where
-    T: Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + CacheWeight + 'static,
 {
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(60));
@@ -140,3 +453,95 @@ where
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn cache(max_entries: usize, max_bytes: usize) -> Arc<ContractCache<Vec<u8>>> {
+        Arc::new(ContractCache::new(CacheConfig {
+            max_entries,
+            max_bytes,
+            default_ttl: Duration::from_secs(60),
+        }))
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_used_within_byte_budget() {
+        let cache = cache(10, 10);
+        cache.set("a".into(), vec![0; 4], None).await;
+        cache.set("b".into(), vec![0; 4], None).await;
+        // Reading "a" makes "b" the oldest
+        assert!(cache.get("a").await.is_some());
+        cache.set("c".into(), vec![0; 4], None).await;
+
+        assert!(cache.get("a").await.is_some());
+        assert!(cache.get("b").await.is_none());
+        assert!(cache.get("c").await.is_some());
+
+        // Too big to ever fit, so never stored
+        cache.set("d".into(), vec![0; 11], None).await;
+        assert!(cache.get("d").await.is_none());
+
+        let stats = cache.stats().await;
+        assert_eq!((stats.total_entries, stats.total_bytes, stats.evictions), (2, 8, 1));
+    }
+
+    #[test]
+    fn function_keys_cover_arguments() {
+        let top10 = function_key("query", &LeaderboardFunction::GetLeaderboard { limit: 10 }).unwrap();
+        let top50 = function_key("query", &LeaderboardFunction::GetLeaderboard { limit: 50 }).unwrap();
+        assert_ne!(top10, top50);
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_fetch_once() {
+        let cache = cache(10, 1024);
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let lookups = (0..8).map(|_| {
+            let cache = cache.clone();
+            let fetches = fetches.clone();
+            tokio::spawn(async move {
+                cache
+                    .get_or_fetch("key", CachePolicy::fresh(Duration::from_secs(60)), move || async move {
+                        fetches.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok::<_, String>(vec![1, 2, 3])
+                    })
+                    .await
+                    .unwrap()
+            })
+        });
+        for lookup in lookups.collect::<Vec<_>>() {
+            assert_eq!(lookup.await.unwrap().0, vec![1, 2, 3]);
+        }
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(cache.inflight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stale_entries_are_served_while_refreshing() {
+        let cache = cache(10, 1024);
+        let policy = CachePolicy::stale_while_revalidate(Duration::from_millis(20), Duration::from_secs(60));
+        let fetch = |value: u8| move || async move { Ok::<_, String>(vec![value]) };
+
+        let (value, lookup) = cache.get_or_fetch("key", policy, fetch(1)).await.unwrap();
+        assert_eq!((value, lookup), (vec![1], CacheLookup::Miss));
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let (value, lookup) = cache.get_or_fetch("key", policy, fetch(2)).await.unwrap();
+        assert_eq!((value, lookup), (vec![1], CacheLookup::Stale));
+
+        // The background refresh lands shortly after
+        for _ in 0..50 {
+            if cache.get("key").await.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(cache.get("key").await, Some(vec![2]));
+    }
+}
@@ -14,7 +14,7 @@ pub enum CircuitState {
     HalfOpen,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CircuitBreakerConfig {
     /// Number of failures before opening circuit
     pub failure_threshold: u32,
@@ -71,19 +71,16 @@ impl CircuitBreaker {
         // Check if circuit is open
         {
             let state = self.state.read().await;
-            match state.state {
-                CircuitState::Open => {
-                    // Check if timeout has elapsed
-                    if let Some(last_failure) = state.last_failure_time {
-                        if last_failure.elapsed() < self.config.timeout {
-                            return Err(CircuitBreakerError::CircuitOpen);
-                        }
+            if state.state == CircuitState::Open {
+                // Check if timeout has elapsed
+                if let Some(last_failure) = state.last_failure_time {
+                    if last_failure.elapsed() < self.config.timeout {
+                        return Err(CircuitBreakerError::CircuitOpen);
                     }
-                    // Timeout elapsed, transition to half-open
-                    drop(state);
-                    self.transition_to_half_open().await;
                 }
-                _ => {}
+                // Timeout elapsed, transition to half-open
+                drop(state);
+                self.transition_to_half_open().await;
             }
         }
 
@@ -134,11 +131,9 @@ impl CircuitBreaker {
         state.last_failure_time = Some(Instant::now());
         state.success_count = 0; // Reset success count
 
-        if state.failure_count >= self.config.failure_threshold {
-            if state.state != CircuitState::Open {
-                warn!("⚠️  Circuit breaker opening (failure threshold reached: {})", state.failure_count);
-                state.state = CircuitState::Open;
-            }
+        if state.failure_count >= self.config.failure_threshold && state.state != CircuitState::Open {
+            warn!("⚠️  Circuit breaker opening (failure threshold reached: {})", state.failure_count);
+            state.state = CircuitState::Open;
         }
     }
 
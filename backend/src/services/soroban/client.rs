@@ -1,15 +1,27 @@
+use shared::dto::contract::{ContractValue, LeaderboardFunction};
 use soroban_client::{
-    Server, Options,
-    transaction::{Account, TransactionBuilder, AccountBehavior, TransactionBuilderBehavior, TransactionBehavior},
-    contract::{Contracts, ContractBehavior},
-    xdr::{Limits, WriteXdr, ReadXdr, TransactionEnvelope, ScVal},
+    contract::{ContractBehavior, Contracts},
+    keypair::{Keypair, KeypairBehavior},
+    transaction::{
+        Account, AccountBehavior, TransactionBehavior, TransactionBuilder,
+        TransactionBuilderBehavior,
+    },
+    xdr::{
+        FeeBumpTransactionInnerTx, Limits, MuxedAccount, ReadXdr, ScVal, TransactionEnvelope,
+        Uint256, WriteXdr,
+    },
+    Options, Server,
 };
 use std::{cell::RefCell, rc::Rc};
-use tracing::{info, debug, error};
-use shared::dto::contract::LeaderboardFunction;
+use stellar_strkey::ed25519::PublicKey as Ed25519PublicKey;
+use tracing::{debug, error, info};
 
+use super::decode;
 use crate::error::{AppError, Result};
 
+/// Source account for simulated reads; simulation doesn't load or charge it
+const SIMULATION_SOURCE_ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
 #[derive(Debug, Clone)]
 pub struct ContractConfig {
     pub contract_id: String,
@@ -20,8 +32,9 @@ pub struct ContractConfig {
 impl Default for ContractConfig {
     fn default() -> Self {
         Self {
-            contract_id: std::env::var("CONTRACT_ID")
-                .unwrap_or_else(|_| "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC".to_string()),
+            contract_id: std::env::var("CONTRACT_ID").unwrap_or_else(|_| {
+                "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC".to_string()
+            }),
             network_passphrase: "Test SDF Network ; September 2015".to_string(),
             rpc_url: "https://soroban-testnet.stellar.org".to_string(),
         }
@@ -39,7 +52,9 @@ impl ContractConfig {
         }
 
         if self.network_passphrase.is_empty() {
-            return Err(AppError::Config("Network passphrase cannot be empty".to_string()));
+            return Err(AppError::Config(
+                "Network passphrase cannot be empty".to_string(),
+            ));
         }
 
         if self.rpc_url.is_empty() {
@@ -53,19 +68,30 @@ impl ContractConfig {
 pub async fn generate_leaderboard_xdr(
     config: &ContractConfig,
     source_account: &str,
-    function: &LeaderboardFunction
+    function: &LeaderboardFunction,
 ) -> Result<String> {
     info!("🚀 generate_leaderboard_xdr called");
-    info!("📋 Function: {} ({})", function.name(), function.display_name());
+    info!(
+        "📋 Function: {} ({})",
+        function.name(),
+        function.display_name()
+    );
     info!("📝 Function signature: {}", function.signature());
-    info!("👤 Source account: {}...{}", &source_account[..10], &source_account[source_account.len()-10..]);
+    info!(
+        "👤 Source account: {}...{}",
+        &source_account[..10],
+        &source_account[source_account.len() - 10..]
+    );
     info!("📄 Contract ID: {}", config.contract_id);
     debug!("Full source account: {}", source_account);
 
     config.validate()?;
     info!("✅ Config validation passed");
 
-    info!("🔗 Generating XDR for leaderboard contract: {}", config.contract_id);
+    info!(
+        "🔗 Generating XDR for leaderboard contract: {}",
+        config.contract_id
+    );
     info!("🌐 Using RPC: {}", config.rpc_url);
     info!("📡 Network: {}", config.network_passphrase);
 
@@ -73,7 +99,9 @@ pub async fn generate_leaderboard_xdr(
         .map_err(|e| AppError::StellarRpc(format!("Failed to connect to Soroban RPC: {:?}", e)))?;
 
     info!("Fetching account info for: {}", source_account);
-    let account_response = rpc.get_account(source_account).await
+    let account_response = rpc
+        .get_account(source_account)
+        .await
         .map_err(|e| AppError::Account(format!("Failed to get account info: {:?}", e)))?;
 
     debug!("Account sequence: {}", account_response.sequence_number());
@@ -82,11 +110,7 @@ pub async fn generate_leaderboard_xdr(
         .map_err(|e| AppError::Account(format!("Failed to create account: {:?}", e)))?;
 
     let account_rc = Rc::new(RefCell::new(account));
-    let mut tx_builder = TransactionBuilder::new(
-        account_rc,
-        &config.network_passphrase,
-        None
-    );
+    let mut tx_builder = TransactionBuilder::new(account_rc, &config.network_passphrase, None);
 
     debug!("Setting fee: 1,000,000 stroops");
     tx_builder.fee(1000000u32);
@@ -94,11 +118,10 @@ pub async fn generate_leaderboard_xdr(
     info!("Creating contract call for function: {}", function.name());
     debug!("Contract ID: {}", config.contract_id);
 
-    let contract = Contracts::new(&config.contract_id)
-        .map_err(|e| {
-            error!("Contract creation failed: {:?}", e);
-            AppError::Transaction(format!("Failed to create contract: {:?}", e))
-        })?;
+    let contract = Contracts::new(&config.contract_id).map_err(|e| {
+        error!("Contract creation failed: {:?}", e);
+        AppError::Transaction(format!("Failed to create contract: {:?}", e))
+    })?;
 
     debug!("Contract object created successfully");
 
@@ -106,7 +129,7 @@ pub async fn generate_leaderboard_xdr(
     info!("Creating contract call for function: {}", function_name);
 
     // Get parameters from the function
-    let params = function_to_scval_params(function);
+    let params = function_to_scval_params(function)?;
     debug!("Function parameters: {} params", params.len());
 
     let invoke_operation = if params.is_empty() {
@@ -124,29 +147,29 @@ pub async fn generate_leaderboard_xdr(
     debug!("Raw transaction built successfully");
 
     info!("Preparing transaction (adding footprint and resource fees)");
-    let prepared_tx = rpc.prepare_transaction(&tx).await
-        .map_err(|e| {
-            error!("Transaction preparation failed: {:?}", e);
-            AppError::Transaction(format!("Failed to prepare transaction: {:?}", e))
-        })?;
+    let prepared_tx = rpc.prepare_transaction(&tx).await.map_err(|e| {
+        error!("Transaction preparation failed: {:?}", e);
+        AppError::Transaction(format!("Failed to prepare transaction: {:?}", e))
+    })?;
     debug!("Transaction prepared with footprint and fees");
 
     info!("Creating transaction envelope");
-    let envelope = prepared_tx.to_envelope()
-        .map_err(|e| {
-            error!("Envelope creation failed: {:?}", e);
-            AppError::XdrEncoding(format!("Failed to create transaction envelope: {:?}", e))
-        })?;
+    let envelope = prepared_tx.to_envelope().map_err(|e| {
+        error!("Envelope creation failed: {:?}", e);
+        AppError::XdrEncoding(format!("Failed to create transaction envelope: {:?}", e))
+    })?;
     debug!("Transaction envelope created successfully");
 
     info!("📦 Encoding to base64 XDR");
-    let tx_envelope_xdr = envelope.to_xdr_base64(Limits::none())
-        .map_err(|e| {
-            error!("❌ XDR encoding failed: {:?}", e);
-            AppError::XdrEncoding(format!("Failed to encode XDR to base64: {:?}", e))
-        })?;
+    let tx_envelope_xdr = envelope.to_xdr_base64(Limits::none()).map_err(|e| {
+        error!("❌ XDR encoding failed: {:?}", e);
+        AppError::XdrEncoding(format!("Failed to encode XDR to base64: {:?}", e))
+    })?;
     info!("✅ XDR encoding completed successfully");
-    info!("🔍 Generated XDR preview (first 100 chars): {}", &tx_envelope_xdr[0..100.min(tx_envelope_xdr.len())]);
+    info!(
+        "🔍 Generated XDR preview (first 100 chars): {}",
+        &tx_envelope_xdr[0..100.min(tx_envelope_xdr.len())]
+    );
     info!("📏 Full XDR length: {} characters", tx_envelope_xdr.len());
     debug!("🔧 Full Generated XDR: {}", tx_envelope_xdr);
     info!("Ready to send to Freighter wallet for signing");
@@ -154,116 +177,546 @@ pub async fn generate_leaderboard_xdr(
     Ok(tx_envelope_xdr)
 }
 
-pub async fn submit_signed_transaction(
-    signed_xdr: &str,
-    function: &LeaderboardFunction
-) -> Result<(String, String)> {
-    debug!("submit_signed_transaction called with XDR length: {}", signed_xdr.len());
+/// Build, prepare and sign a contract call with a backend-held key
+/// Returns the signed envelope, ready for sendTransaction
+pub async fn build_signed_xdr(
+    config: &ContractConfig,
+    secret_key: &str,
+    function: &LeaderboardFunction,
+) -> Result<String> {
+    config.validate()?;
+
+    let keypair = Keypair::from_secret(secret_key)
+        .map_err(|e| AppError::Config(format!("Invalid signing key: {}", e)))?;
+    let source_account = keypair.public_key();
+
+    let options = Options {
+        allow_http: config.rpc_url.starts_with("http://"),
+        ..Options::default()
+    };
+    let rpc = Server::new(&config.rpc_url, options)
+        .map_err(|e| AppError::StellarRpc(format!("Failed to connect to Soroban RPC: {:?}", e)))?;
+
+    let account_response = rpc
+        .get_account(&source_account)
+        .await
+        .map_err(|e| AppError::Account(format!("Failed to get account info: {:?}", e)))?;
+    let account = Account::new(&source_account, &account_response.sequence_number())
+        .map_err(|e| AppError::Account(format!("Failed to create account: {:?}", e)))?;
 
-    info!("Starting transaction analysis and validation");
-    info!("Signed XDR length: {} characters", signed_xdr.len());
+    let contract = Contracts::new(&config.contract_id)
+        .map_err(|e| AppError::Transaction(format!("Failed to create contract: {:?}", e)))?;
 
-    debug!("Validating signed XDR input");
-    if signed_xdr.is_empty() {
-        error!("Validation failed: Signed XDR is empty");
-        return Err(AppError::InvalidInput("Signed XDR cannot be empty".to_string()));
+    let params = function_to_scval_params(function)?;
+    let mut tx_builder = TransactionBuilder::new(
+        Rc::new(RefCell::new(account)),
+        &config.network_passphrase,
+        None,
+    );
+    tx_builder.fee(1000000u32);
+    tx_builder.add_operation(contract.call(
+        function.name(),
+        if params.is_empty() {
+            None
+        } else {
+            Some(params)
+        },
+    ));
+    let tx = tx_builder.build();
+
+    let mut prepared_tx = rpc.prepare_transaction(&tx).await.map_err(|e| {
+        AppError::Transaction(format!("Failed to prepare {}: {:?}", function.name(), e))
+    })?;
+    prepared_tx.sign(&[keypair]);
+
+    let envelope = prepared_tx.to_envelope().map_err(|e| {
+        AppError::XdrEncoding(format!("Failed to create transaction envelope: {:?}", e))
+    })?;
+    let signed_xdr = envelope
+        .to_xdr_base64(Limits::none())
+        .map_err(|e| AppError::XdrEncoding(format!("Failed to encode XDR to base64: {:?}", e)))?;
+    debug!("{} signed by {}", function.name(), source_account);
+
+    Ok(signed_xdr)
+}
+
+/// Decode a wallet-signed envelope and return its source account
+pub fn signed_envelope_source(signed_xdr: &str) -> Result<String> {
+    let envelope = TransactionEnvelope::from_xdr_base64(signed_xdr, Limits::none())
+        .map_err(|e| AppError::XdrDecoding(format!("Failed to decode signed XDR: {:?}", e)))?;
+
+    let (source, signatures) = match &envelope {
+        TransactionEnvelope::TxV0(v0) => (
+            MuxedAccount::Ed25519(v0.tx.source_account_ed25519.clone()),
+            v0.signatures.len(),
+        ),
+        TransactionEnvelope::Tx(v1) => (v1.tx.source_account.clone(), v1.signatures.len()),
+        TransactionEnvelope::TxFeeBump(fee_bump) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &fee_bump.tx.inner_tx;
+            (inner.tx.source_account.clone(), fee_bump.signatures.len())
+        }
+    };
+
+    if signatures == 0 {
+        return Err(AppError::InvalidInput(
+            "Transaction has not been signed".to_string(),
+        ));
     }
 
-    if signed_xdr.len() < 100 {
-        error!("Validation failed: Signed XDR too short ({})", signed_xdr.len());
-        return Err(AppError::InvalidInput("Signed XDR appears too short to be valid".to_string()));
+    let key = match source {
+        MuxedAccount::Ed25519(Uint256(key)) => key,
+        MuxedAccount::MuxedEd25519(muxed) => muxed.ed25519.0,
+    };
+    Ok(Ed25519PublicKey(key).to_string())
+}
+
+/// Run a read-only contract function through simulateTransaction and return its raw result
+pub async fn simulate_read(
+    config: &ContractConfig,
+    function: &LeaderboardFunction,
+) -> Result<ScVal> {
+    if !function.is_read_only() {
+        return Err(AppError::InvalidInput(format!(
+            "{} is not a read-only function",
+            function.name()
+        )));
     }
+    config.validate()?;
 
-    // Decode and validate the signed XDR
-    let _tx_envelope = TransactionEnvelope::from_xdr_base64(signed_xdr, Limits::none())
-        .map_err(|e| AppError::XdrDecoding(format!("Failed to decode signed XDR: {:?}", e)))?;
+    // Plain HTTP is only expected from a local RPC (quickstart or a test double)
+    let options = Options {
+        allow_http: config.rpc_url.starts_with("http://"),
+        ..Options::default()
+    };
+    let rpc = Server::new(&config.rpc_url, options)
+        .map_err(|e| AppError::StellarRpc(format!("Failed to connect to Soroban RPC: {:?}", e)))?;
 
-    info!("Successfully decoded signed transaction envelope");
-    info!("Transaction is properly signed and ready for submission");
-
-    // Extract information from the transaction envelope
-    let config = ContractConfig::default();
-
-    // Generate a transaction hash preview
-    let tx_hash = format!("tx_{}_{}",
-        &signed_xdr[..8].chars().filter(|c| c.is_alphanumeric()).collect::<String>(),
-        chrono::Utc::now().timestamp() % 10000);
-
-    info!("Transaction analysis completed: {}", tx_hash);
-
-    // Create detailed contract execution summary
-    let contract_result = format!(
-        "🎉 Leaderboard function '{}' ready for execution!\n\n\
-        📋 Function: {}\n\
-        📝 Description: {}\n\
-        ✅ Transaction Status: SIGNED & VALIDATED\n\
-        🔗 Transaction ID: {}\n\
-        📄 Signed XDR Length: {} characters\n\
-        📦 Contract ID: {}\n\
-        🌐 Network: Stellar Testnet\n\n\
-        📊 Transaction Analysis:\n\
-        • XDR successfully decoded ✓\n\
-        • Transaction properly signed ✓\n\
-        • Contract call structure valid ✓\n\
-        • Ready for network submission ✓\n\n\
-        💡 Expected Result: Function will execute and update leaderboard\n\
-        ⛽ Estimated Fee: ~1,000,000 stroops\n\n\
-        🚀 To submit to live network:\n\
-        stellar contract invoke \\\n\
-        --id {} \\\n\
-        --source-account YOUR_ACCOUNT \\\n\
-        --network testnet \\\n\
-        -- {}\n\n\
-        ⚠️ This transaction is ready but not yet submitted to the network.",
-        function.name(),
-        function.signature(),
-        function.description(),
-        tx_hash,
-        signed_xdr.len(),
-        config.contract_id,
-        config.contract_id,
-        function.name()
+    let account = Account::new(SIMULATION_SOURCE_ACCOUNT, "0")
+        .map_err(|e| AppError::Account(format!("Failed to create simulation account: {:?}", e)))?;
+    let contract = Contracts::new(&config.contract_id)
+        .map_err(|e| AppError::Transaction(format!("Failed to create contract: {:?}", e)))?;
+
+    let params = function_to_scval_params(function)?;
+    let mut tx_builder = TransactionBuilder::new(
+        Rc::new(RefCell::new(account)),
+        &config.network_passphrase,
+        None,
     );
+    tx_builder.fee(100u32);
+    tx_builder.add_operation(contract.call(
+        function.name(),
+        if params.is_empty() {
+            None
+        } else {
+            Some(params)
+        },
+    ));
+    let tx = tx_builder.build();
+
+    let simulation = rpc.simulate_transaction(&tx, None).await.map_err(|e| {
+        AppError::StellarRpc(format!("Failed to simulate {}: {:?}", function.name(), e))
+    })?;
+    if let Some(error) = simulation.error.as_ref() {
+        return Err(AppError::Transaction(format!(
+            "Simulation of {} failed: {}",
+            function.name(),
+            error
+        )));
+    }
+
+    let (value, _auth) = simulation.to_result().ok_or_else(|| {
+        AppError::Transaction(format!(
+            "Simulation of {} returned no result",
+            function.name()
+        ))
+    })?;
+    debug!("{} simulated: {:?}", function.name(), value);
+    Ok(value)
+}
+
+/// Run a read-only contract function and decode its result
+pub async fn query_contract(
+    config: &ContractConfig,
+    function: &LeaderboardFunction,
+) -> Result<ContractValue> {
+    let value = simulate_read(config, function).await?;
+
+    match function {
+        LeaderboardFunction::HasJoined { .. } => {
+            decode::scval_to_bool(&value).map(ContractValue::Bool)
+        }
+        LeaderboardFunction::GetWins { .. }
+        | LeaderboardFunction::GetMyWins { .. }
+        | LeaderboardFunction::GetPlayerCount
+        | LeaderboardFunction::GetSeasonWins { .. }
+        | LeaderboardFunction::GetRating { .. }
+        | LeaderboardFunction::GetVersion => decode::scval_to_u32(&value).map(ContractValue::U32),
+        LeaderboardFunction::GetLeaderboard { .. }
+        | LeaderboardFunction::GetLeaderboardForSeason { .. } => {
+            decode::scval_to_leaderboard(&value).map(ContractValue::Leaderboard)
+        }
+        LeaderboardFunction::GetPlayer { .. } => {
+            decode::scval_to_option(&value, decode::scval_to_player).map(ContractValue::Player)
+        }
+        LeaderboardFunction::GetAchievements { .. } => {
+            decode::scval_to_badges(&value).map(ContractValue::Badges)
+        }
+        _ => Ok(ContractValue::Json(decode::scval_to_json(&value))),
+    }
+}
 
-    info!("Contract transaction analysis completed successfully!");
-    Ok((tx_hash, contract_result))
+/// Whether a player has joined the on-chain leaderboard
+pub async fn has_joined(config: &ContractConfig, player: &str) -> Result<bool> {
+    let function = LeaderboardFunction::HasJoined {
+        player: player.to_string(),
+    };
+    decode::scval_to_bool(&simulate_read(config, &function).await?)
 }
 
 // Convert LeaderboardFunction to ScVal parameters
-fn function_to_scval_params(function: &LeaderboardFunction) -> Vec<ScVal> {
-    use soroban_client::xdr::{ScAddress, AccountId, PublicKey, Uint256};
-    use stellar_strkey::ed25519::PublicKey as Ed25519PublicKey;
+// Arguments come straight from request bodies, so malformed ones are rejected as invalid input
+fn function_to_scval_params(function: &LeaderboardFunction) -> Result<Vec<ScVal>> {
+    use soroban_client::xdr::{
+        AccountId, ContractId, Hash, Int128Parts, PublicKey, ScAddress, ScBytes, ScString, ScVec,
+        Uint256,
+    };
+    use stellar_strkey::{ed25519::PublicKey as Ed25519PublicKey, Contract as ContractStrkey};
 
-    fn string_to_scaddress(address_str: &str) -> ScAddress {
-        let public_key = Ed25519PublicKey::from_string(address_str).unwrap();
+    fn string_to_scaddress(address_str: &str) -> Result<ScAddress> {
+        let public_key = Ed25519PublicKey::from_string(address_str).map_err(|_| {
+            AppError::InvalidInput(format!("Invalid Stellar address: {}", address_str))
+        })?;
         let account_id = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(public_key.0)));
-        ScAddress::Account(account_id)
+        Ok(ScAddress::Account(account_id))
     }
 
-    match function {
+    fn string_to_contract_scaddress(address_str: &str) -> Result<ScAddress> {
+        let contract = ContractStrkey::from_string(address_str).map_err(|_| {
+            AppError::InvalidInput(format!("Invalid contract address: {}", address_str))
+        })?;
+        Ok(ScAddress::Contract(ContractId(Hash(contract.0))))
+    }
+
+    fn addresses_to_scval(addresses: &[String]) -> Result<ScVal> {
+        let items = addresses
+            .iter()
+            .map(|address| string_to_scaddress(address).map(ScVal::Address))
+            .collect::<Result<Vec<ScVal>>>()?;
+        let items = items
+            .try_into()
+            .map_err(|_| AppError::InvalidInput("Too many addresses".to_string()))?;
+        Ok(ScVal::Vec(Some(ScVec(items))))
+    }
+
+    fn i128_to_scval(value: i128) -> ScVal {
+        ScVal::I128(Int128Parts {
+            hi: (value >> 64) as i64,
+            lo: value as u64,
+        })
+    }
+
+    fn string_to_scval(value: &str) -> Result<ScVal> {
+        let value = value
+            .try_into()
+            .map_err(|_| AppError::InvalidInput("String argument is too long".to_string()))?;
+        Ok(ScVal::String(ScString(value)))
+    }
+
+    fn hex_to_scbytes(value: &str) -> Result<ScVal> {
+        let bytes = hex::decode(value)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid hex bytes {}: {}", value, e)))?;
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| AppError::InvalidInput("Bytes argument is too long".to_string()))?;
+        Ok(ScVal::Bytes(ScBytes(bytes)))
+    }
+
+    let params = match function {
         LeaderboardFunction::Join { player } => {
-            vec![ScVal::Address(string_to_scaddress(player))]
-        },
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
         LeaderboardFunction::HasJoined { player } => {
-            vec![ScVal::Address(string_to_scaddress(player))]
-        },
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
         LeaderboardFunction::AddWin { player } => {
-            vec![ScVal::Address(string_to_scaddress(player))]
-        },
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
+        LeaderboardFunction::RecordMatch {
+            player,
+            opponent,
+            session_id,
+            score,
+            opponent_score,
+        } => {
+            let opponent = match opponent {
+                Some(opponent) => ScVal::Address(string_to_scaddress(opponent)?),
+                None => ScVal::Void,
+            };
+            vec![
+                ScVal::Address(string_to_scaddress(player)?),
+                opponent,
+                string_to_scval(session_id)?,
+                ScVal::U32(*score),
+                ScVal::U32(*opponent_score),
+            ]
+        }
         LeaderboardFunction::GetWins { player } => {
-            vec![ScVal::Address(string_to_scaddress(player))]
-        },
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
         LeaderboardFunction::GetMyWins { player } => {
-            vec![ScVal::Address(string_to_scaddress(player))]
-        },
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
         LeaderboardFunction::GetPlayer { player } => {
-            vec![ScVal::Address(string_to_scaddress(player))]
-        },
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
+        LeaderboardFunction::GetPlayerStats { player } => {
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
+        LeaderboardFunction::GetMatch { session_id } => {
+            vec![string_to_scval(session_id)?]
+        }
+        LeaderboardFunction::StartSeason { id, ends_at } => {
+            vec![ScVal::U32(*id), ScVal::U64(*ends_at)]
+        }
+        LeaderboardFunction::GetSeasonWins { season, player } => {
+            vec![
+                ScVal::U32(*season),
+                ScVal::Address(string_to_scaddress(player)?),
+            ]
+        }
+        LeaderboardFunction::GetLeaderboardForSeason { season, limit } => {
+            vec![ScVal::U32(*season), ScVal::U32(*limit)]
+        }
+        LeaderboardFunction::GetAchievements { player } => {
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
+        LeaderboardFunction::GetPlayersPage { cursor, limit } => {
+            vec![ScVal::U32(*cursor), ScVal::U32(*limit)]
+        }
+        LeaderboardFunction::GetWinsBatch { players }
+        | LeaderboardFunction::GetPlayersBatch { players } => {
+            vec![addresses_to_scval(players)?]
+        }
+        LeaderboardFunction::SetProfile {
+            player,
+            display_name,
+            avatar_id,
+            country,
+        } => {
+            vec![
+                ScVal::Address(string_to_scaddress(player)?),
+                string_to_scval(display_name)?,
+                ScVal::U32(*avatar_id),
+                string_to_scval(country)?,
+            ]
+        }
+        LeaderboardFunction::GetProfile { player } => {
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
+        LeaderboardFunction::GetRating { player } => {
+            vec![ScVal::Address(string_to_scaddress(player)?)]
+        }
+        LeaderboardFunction::GetRatingLeaderboard { limit } => {
+            vec![ScVal::U32(*limit)]
+        }
+        LeaderboardFunction::SetAdmin { new_admin } => {
+            vec![ScVal::Address(string_to_scaddress(new_admin)?)]
+        }
+        LeaderboardFunction::Upgrade { new_wasm_hash } => {
+            vec![hex_to_scbytes(new_wasm_hash)?]
+        }
+        LeaderboardFunction::Migrate { start, count }
+        | LeaderboardFunction::BumpAll { start, count } => {
+            vec![ScVal::U32(*start), ScVal::U32(*count)]
+        }
+        LeaderboardFunction::CreateTournament {
+            admin,
+            name,
+            max_players,
+            entry_deadline,
+        } => {
+            vec![
+                ScVal::Address(string_to_scaddress(admin)?),
+                string_to_scval(name)?,
+                ScVal::U32(*max_players),
+                ScVal::U64(*entry_deadline),
+            ]
+        }
+        LeaderboardFunction::Register { player, tournament } => {
+            vec![
+                ScVal::Address(string_to_scaddress(player)?),
+                ScVal::U32(*tournament),
+            ]
+        }
+        LeaderboardFunction::ReportResult {
+            tournament,
+            round,
+            winner,
+            loser,
+        } => {
+            vec![
+                ScVal::U32(*tournament),
+                ScVal::U32(*round),
+                ScVal::Address(string_to_scaddress(winner)?),
+                ScVal::Address(string_to_scaddress(loser)?),
+            ]
+        }
+        LeaderboardFunction::GetBracket { tournament }
+        | LeaderboardFunction::GetTournament { tournament } => {
+            vec![ScVal::U32(*tournament)]
+        }
+        LeaderboardFunction::CreateWager {
+            player_one,
+            player_two,
+            token,
+            amount,
+        } => {
+            vec![
+                ScVal::Address(string_to_scaddress(player_one)?),
+                ScVal::Address(string_to_scaddress(player_two)?),
+                ScVal::Address(string_to_contract_scaddress(token)?),
+                i128_to_scval(*amount),
+            ]
+        }
+        LeaderboardFunction::SettleWager { id, winner } => {
+            let winner = match winner {
+                Some(winner) => ScVal::Address(string_to_scaddress(winner)?),
+                None => ScVal::Void,
+            };
+            vec![ScVal::U32(*id), winner]
+        }
+        LeaderboardFunction::AcceptWager { id }
+        | LeaderboardFunction::RefundWager { id }
+        | LeaderboardFunction::GetWager { id } => {
+            vec![ScVal::U32(*id)]
+        }
         LeaderboardFunction::GetLeaderboard { limit } => {
             vec![ScVal::U32(*limit)]
-        },
-        LeaderboardFunction::GetAllPlayers | LeaderboardFunction::GetPlayerCount => {
+        }
+        LeaderboardFunction::GetAllPlayers
+        | LeaderboardFunction::GetPlayerCount
+        | LeaderboardFunction::GetCurrentSeason
+        | LeaderboardFunction::GetVersion => {
             vec![] // No parameters
-        },
+        }
+    };
+    Ok(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+    use shared::dto::contract::ContractValue;
+
+    const PLAYER: &str = "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ";
+
+    /// Serve a JSON-RPC endpoint answering every simulateTransaction with `result`
+    async fn mock_rpc(result: Value) -> ContractConfig {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| {
+                let result = result.clone();
+                async move {
+                    assert_eq!(request["method"], "simulateTransaction");
+                    Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        ContractConfig {
+            rpc_url: format!("http://{}/", addr),
+            ..ContractConfig::default()
+        }
     }
-}
\ No newline at end of file
+
+    fn simulated(value: ScVal) -> Value {
+        json!({
+            "latestLedger": 1000,
+            "minResourceFee": "100",
+            "results": [{ "auth": [], "xdr": value.to_xdr_base64(Limits::none()).unwrap() }],
+            "transactionData": null,
+        })
+    }
+
+    #[tokio::test]
+    async fn reads_join_status_from_simulation() {
+        let config = mock_rpc(simulated(ScVal::Bool(true))).await;
+
+        assert!(has_joined(&config, PLAYER).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn decodes_typed_query_results() {
+        let config = mock_rpc(simulated(ScVal::U32(42))).await;
+        let value = query_contract(&config, &LeaderboardFunction::GetPlayerCount)
+            .await
+            .unwrap();
+        assert_eq!(value, ContractValue::U32(42));
+
+        let config = mock_rpc(simulated(ScVal::Void)).await;
+        let function = LeaderboardFunction::GetPlayer {
+            player: PLAYER.to_string(),
+        };
+        assert_eq!(
+            query_contract(&config, &function).await.unwrap(),
+            ContractValue::Player(None)
+        );
+    }
+
+    #[tokio::test]
+    async fn surfaces_simulation_errors() {
+        let config = mock_rpc(json!({
+            "latestLedger": 1000,
+            "error": "HostError: Error(Contract, #2)",
+        }))
+        .await;
+
+        let error = query_contract(&config, &LeaderboardFunction::GetPlayerCount)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Error(Contract, #2)"));
+    }
+
+    #[tokio::test]
+    async fn rejects_state_changing_functions() {
+        let function = LeaderboardFunction::Join {
+            player: PLAYER.to_string(),
+        };
+        let error = simulate_read(&ContractConfig::default(), &function)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::InvalidInput(_)));
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_arguments_as_bad_requests() {
+        use axum::{http::StatusCode, response::IntoResponse};
+
+        let config = mock_rpc(simulated(ScVal::U32(0))).await;
+
+        // A bad checksum still starts with G and is 56 characters long
+        let bad_checksum = format!("{}A", &PLAYER[..55]);
+        for player in ["x".to_string(), bad_checksum] {
+            let function = LeaderboardFunction::GetWins { player };
+            let error = query_contract(&config, &function).await.unwrap_err();
+            assert!(matches!(error, AppError::InvalidInput(_)));
+            assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+
+        // Odd-length or non-hex hashes are rejected instead of truncated
+        for new_wasm_hash in ["abc", "zz"] {
+            let function = LeaderboardFunction::Upgrade {
+                new_wasm_hash: new_wasm_hash.to_string(),
+            };
+            assert!(matches!(
+                function_to_scval_params(&function),
+                Err(AppError::InvalidInput(_))
+            ));
+        }
+    }
+}
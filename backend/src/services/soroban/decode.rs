@@ -0,0 +1,309 @@
+// Decoding of contract return values into the shared contract DTOs
+//
+// #[contracttype] structs come back as ScVal maps keyed by field name, Option as
+// Void or the inner value, and Vec as ScVal vectors.
+
+use soroban_client::xdr::{AccountId, ContractId, Hash, Int128Parts, PublicKey, ScAddress, ScVal, UInt128Parts, Uint256};
+use serde_json::{json, Map, Value};
+use shared::dto::contract::{ContractAchievement, ContractBadge, ContractLeaderboardEntry, ContractPlayer, ContractPlayerPage};
+use stellar_strkey::{ed25519::PublicKey as Ed25519PublicKey, Contract as ContractStrkey};
+
+use crate::error::{AppError, Result};
+
+fn decode_error(expected: &str, value: &ScVal) -> AppError {
+    AppError::XdrDecoding(format!("Expected {} in contract result, got {:?}", expected, value))
+}
+
+/// Decode an address into its G... or C... strkey
+pub fn scval_to_address(value: &ScVal) -> Result<String> {
+    match value {
+        ScVal::Address(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))))) => {
+            Ok(Ed25519PublicKey(*key).to_string())
+        }
+        ScVal::Address(ScAddress::Contract(ContractId(Hash(hash)))) => {
+            Ok(ContractStrkey(*hash).to_string())
+        }
+        other => Err(decode_error("account or contract address", other)),
+    }
+}
+
+pub fn scval_to_bool(value: &ScVal) -> Result<bool> {
+    match value {
+        ScVal::Bool(flag) => Ok(*flag),
+        other => Err(decode_error("bool", other)),
+    }
+}
+
+pub fn scval_to_u32(value: &ScVal) -> Result<u32> {
+    match value {
+        ScVal::U32(number) => Ok(*number),
+        other => Err(decode_error("u32", other)),
+    }
+}
+
+pub fn scval_to_string(value: &ScVal) -> Result<String> {
+    match value {
+        ScVal::String(string) => Ok(string.to_utf8_string_lossy()),
+        other => Err(decode_error("string", other)),
+    }
+}
+
+pub fn scval_to_u64(value: &ScVal) -> Result<u64> {
+    match value {
+        ScVal::U64(number) => Ok(*number),
+        other => Err(decode_error("u64", other)),
+    }
+}
+
+/// Decode an Option, mapping Void to None
+pub fn scval_to_option<T>(value: &ScVal, decode: impl Fn(&ScVal) -> Result<T>) -> Result<Option<T>> {
+    match value {
+        ScVal::Void => Ok(None),
+        other => decode(other).map(Some),
+    }
+}
+
+/// Decode a Vec, element by element
+pub fn scval_to_vec<T>(value: &ScVal, decode: impl Fn(&ScVal) -> Result<T>) -> Result<Vec<T>> {
+    match value {
+        ScVal::Vec(Some(items)) => items.iter().map(decode).collect(),
+        other => Err(decode_error("vec", other)),
+    }
+}
+
+/// Look up a field of a #[contracttype] struct
+pub fn struct_field<'a>(value: &'a ScVal, name: &str) -> Result<&'a ScVal> {
+    let ScVal::Map(Some(entries)) = value else {
+        return Err(decode_error("struct", value));
+    };
+
+    entries
+        .iter()
+        .find(|entry| matches!(&entry.key, ScVal::Symbol(symbol) if symbol.as_vec() == name.as_bytes()))
+        .map(|entry| &entry.val)
+        .ok_or_else(|| AppError::XdrDecoding(format!("Missing field '{}' in contract result", name)))
+}
+
+/// Decode a Player record
+pub fn scval_to_player(value: &ScVal) -> Result<ContractPlayer> {
+    Ok(ContractPlayer {
+        address: scval_to_address(struct_field(value, "address")?)?,
+        wins: scval_to_u32(struct_field(value, "wins")?)?,
+        losses: scval_to_u32(struct_field(value, "losses")?)?,
+        draws: scval_to_u32(struct_field(value, "draws")?)?,
+        goals_for: scval_to_u32(struct_field(value, "goals_for")?)?,
+        goals_against: scval_to_u32(struct_field(value, "goals_against")?)?,
+        rating: scval_to_u32(struct_field(value, "rating")?)?,
+        joined_at: scval_to_u64(struct_field(value, "joined_at")?)?,
+        last_played: scval_to_u64(struct_field(value, "last_played")?)?,
+        display_name: scval_to_option(struct_field(value, "display_name")?, scval_to_string)?,
+        avatar_id: scval_to_option(struct_field(value, "avatar_id")?, scval_to_u32)?,
+        country: scval_to_option(struct_field(value, "country")?, scval_to_string)?,
+    })
+}
+
+/// Decode a LeaderboardEntry
+pub fn scval_to_leaderboard_entry(value: &ScVal) -> Result<ContractLeaderboardEntry> {
+    Ok(ContractLeaderboardEntry {
+        address: scval_to_address(struct_field(value, "address")?)?,
+        wins: scval_to_u32(struct_field(value, "wins")?)?,
+        rating: scval_to_u32(struct_field(value, "rating")?)?,
+        display_name: scval_to_option(struct_field(value, "display_name")?, scval_to_string)?,
+        avatar_id: scval_to_option(struct_field(value, "avatar_id")?, scval_to_u32)?,
+        country: scval_to_option(struct_field(value, "country")?, scval_to_string)?,
+    })
+}
+
+/// Decode the result of get_leaderboard or get_leaderboard_for_season
+pub fn scval_to_leaderboard(value: &ScVal) -> Result<Vec<ContractLeaderboardEntry>> {
+    scval_to_vec(value, scval_to_leaderboard_entry)
+}
+
+/// Decode the result of get_players_page
+pub fn scval_to_player_page(value: &ScVal) -> Result<ContractPlayerPage> {
+    Ok(ContractPlayerPage {
+        players: scval_to_vec(struct_field(value, "players")?, scval_to_player)?,
+        next_cursor: scval_to_option(struct_field(value, "next_cursor")?, scval_to_u32)?,
+        total: scval_to_u32(struct_field(value, "total")?)?,
+    })
+}
+
+/// Decode the result of get_wins_batch
+pub fn scval_to_wins_batch(value: &ScVal) -> Result<Vec<u32>> {
+    scval_to_vec(value, scval_to_u32)
+}
+
+/// Decode the result of get_players_batch
+pub fn scval_to_players_batch(value: &ScVal) -> Result<Vec<Option<ContractPlayer>>> {
+    scval_to_vec(value, |item| scval_to_option(item, scval_to_player))
+}
+
+/// Decode an achievement Badge, whose achievement is the u32 discriminant
+pub fn scval_to_badge(value: &ScVal) -> Result<ContractBadge> {
+    let id = scval_to_u32(struct_field(value, "achievement")?)?;
+    Ok(ContractBadge {
+        achievement: ContractAchievement::from_id(id)
+            .ok_or_else(|| AppError::XdrDecoding(format!("Unknown achievement {} in contract result", id)))?,
+        awarded_at: scval_to_u64(struct_field(value, "awarded_at")?)?,
+    })
+}
+
+/// Decode the result of get_achievements
+pub fn scval_to_badges(value: &ScVal) -> Result<Vec<ContractBadge>> {
+    scval_to_vec(value, scval_to_badge)
+}
+
+/// Decode any contract value into JSON, for return types without a typed decoder
+///
+/// Structs become objects keyed by field name and unit enum variants their name,
+/// so the result deserializes into the matching shared DTO. 128-bit integers are
+/// strings to survive JSON number precision, bytes are hex.
+pub fn scval_to_json(value: &ScVal) -> Value {
+    match value {
+        ScVal::Void => Value::Null,
+        ScVal::Bool(flag) => json!(flag),
+        ScVal::U32(number) => json!(number),
+        ScVal::I32(number) => json!(number),
+        ScVal::U64(number) => json!(number),
+        ScVal::I64(number) => json!(number),
+        ScVal::Timepoint(time) => json!(time.0),
+        ScVal::Duration(duration) => json!(duration.0),
+        ScVal::U128(UInt128Parts { hi, lo }) => json!((((*hi as u128) << 64) | *lo as u128).to_string()),
+        ScVal::I128(Int128Parts { hi, lo }) => json!((((*hi as i128) << 64) | *lo as i128).to_string()),
+        ScVal::Symbol(symbol) => json!(symbol.to_utf8_string_lossy()),
+        ScVal::String(string) => json!(string.to_utf8_string_lossy()),
+        ScVal::Bytes(bytes) => json!(bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()),
+        ScVal::Address(_) => scval_to_address(value).map(Value::String).unwrap_or(Value::Null),
+        // Unit enum variants are encoded as a one-symbol vec
+        ScVal::Vec(Some(items)) if matches!(items.as_slice(), [ScVal::Symbol(_)]) => scval_to_json(&items[0]),
+        ScVal::Vec(Some(items)) => Value::Array(items.iter().map(scval_to_json).collect()),
+        ScVal::Map(Some(entries)) if entries.iter().all(|entry| matches!(entry.key, ScVal::Symbol(_))) => {
+            let fields: Map<String, Value> = entries
+                .iter()
+                .map(|entry| (scval_to_json(&entry.key).as_str().unwrap_or_default().to_string(), scval_to_json(&entry.val)))
+                .collect();
+            Value::Object(fields)
+        }
+        ScVal::Map(Some(entries)) => Value::Array(
+            entries.iter().map(|entry| json!([scval_to_json(&entry.key), scval_to_json(&entry.val)])).collect(),
+        ),
+        other => json!(format!("{:?}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_client::xdr::{ScMap, ScMapEntry, ScString, ScSymbol, ScVec};
+
+    const PLAYER: &str = "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ";
+
+    fn address(strkey: &str) -> ScVal {
+        let key = Ed25519PublicKey::from_string(strkey).unwrap();
+        ScVal::Address(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0)))))
+    }
+
+    fn contract_struct(fields: Vec<(&str, ScVal)>) -> ScVal {
+        let entries: Vec<ScMapEntry> = fields
+            .into_iter()
+            .map(|(name, val)| ScMapEntry {
+                key: ScVal::Symbol(ScSymbol(name.try_into().unwrap())),
+                val,
+            })
+            .collect();
+        ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+    }
+
+    fn player() -> ScVal {
+        contract_struct(vec![
+            ("address", address(PLAYER)),
+            ("avatar_id", ScVal::U32(7)),
+            ("country", ScVal::Void),
+            ("display_name", ScVal::String(ScString("Striker".try_into().unwrap()))),
+            ("draws", ScVal::U32(1)),
+            ("goals_against", ScVal::U32(4)),
+            ("goals_for", ScVal::U32(9)),
+            ("joined_at", ScVal::U64(100)),
+            ("last_played", ScVal::U64(200)),
+            ("losses", ScVal::U32(2)),
+            ("rating", ScVal::U32(1216)),
+            ("wins", ScVal::U32(3)),
+        ])
+    }
+
+    #[test]
+    fn decodes_player_page() {
+        let page = contract_struct(vec![
+            ("next_cursor", ScVal::U32(1)),
+            ("players", ScVal::Vec(Some(ScVec(vec![player()].try_into().unwrap())))),
+            ("total", ScVal::U32(2)),
+        ]);
+
+        let decoded = scval_to_player_page(&page).unwrap();
+        assert_eq!(decoded.next_cursor, Some(1));
+        assert_eq!(decoded.total, 2);
+        assert_eq!(decoded.players.len(), 1);
+        assert_eq!(decoded.players[0].address, PLAYER);
+        assert_eq!(decoded.players[0].wins, 3);
+        assert_eq!(decoded.players[0].rating, 1216);
+        assert_eq!(decoded.players[0].display_name.as_deref(), Some("Striker"));
+        assert_eq!(decoded.players[0].avatar_id, Some(7));
+        assert_eq!(decoded.players[0].country, None);
+    }
+
+    #[test]
+    fn decodes_players_batch_with_missing_players() {
+        let batch = ScVal::Vec(Some(ScVec(vec![ScVal::Void, player()].try_into().unwrap())));
+
+        let decoded = scval_to_players_batch(&batch).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert!(decoded[0].is_none());
+        assert_eq!(decoded[1].as_ref().unwrap().goals_for, 9);
+    }
+
+    #[test]
+    fn decodes_leaderboard_and_json_values() {
+        let entry = contract_struct(vec![
+            ("address", address(PLAYER)),
+            ("avatar_id", ScVal::Void),
+            ("country", ScVal::Void),
+            ("display_name", ScVal::Void),
+            ("rating", ScVal::U32(1232)),
+            ("wins", ScVal::U32(5)),
+        ]);
+        let leaderboard = ScVal::Vec(Some(ScVec(vec![entry].try_into().unwrap())));
+
+        let decoded = scval_to_leaderboard(&leaderboard).unwrap();
+        assert_eq!(decoded[0].address, PLAYER);
+        assert_eq!(decoded[0].wins, 5);
+        assert_eq!(decoded[0].rating, 1232);
+        assert!(decoded[0].display_name.is_none());
+
+        let json = scval_to_json(&player());
+        let player: ContractPlayer = serde_json::from_value(json).unwrap();
+        assert_eq!(player.rating, 1216);
+
+        let status = ScVal::Vec(Some(ScVec(vec![ScVal::Symbol(ScSymbol("Open".try_into().unwrap()))].try_into().unwrap())));
+        assert_eq!(scval_to_json(&status), json!("Open"));
+        assert_eq!(scval_to_json(&ScVal::I128(Int128Parts { hi: 0, lo: 500 })), json!("500"));
+    }
+
+    #[test]
+    fn decodes_badges() {
+        let badge = contract_struct(vec![("achievement", ScVal::U32(3)), ("awarded_at", ScVal::U64(150))]);
+        let badges = ScVal::Vec(Some(ScVec(vec![badge].try_into().unwrap())));
+
+        let decoded = scval_to_badges(&badges).unwrap();
+        assert_eq!(decoded, vec![ContractBadge { achievement: ContractAchievement::CleanSheet, awarded_at: 150 }]);
+
+        let unknown = contract_struct(vec![("achievement", ScVal::U32(99)), ("awarded_at", ScVal::U64(150))]);
+        assert!(scval_to_badge(&unknown).is_err());
+    }
+
+    #[test]
+    fn rejects_unexpected_shapes() {
+        assert!(scval_to_wins_batch(&ScVal::U32(1)).is_err());
+        assert!(scval_to_bool(&ScVal::U32(1)).is_err());
+        assert!(scval_to_player(&contract_struct(vec![("wins", ScVal::U32(1))])).is_err());
+    }
+}
@@ -0,0 +1,452 @@
+use soroban_client::{
+    Server, Options, Pagination, EventFilter, Topic,
+    soroban_rpc::EventType,
+    xdr::{ScSymbol, ScVal},
+};
+use std::{future::Future, sync::Arc, time::Duration};
+use tracing::{info, debug, warn};
+
+use crate::database::{models::NewContractEvent, repositories::ContractIndexRepository, DbPool};
+use crate::error::{AppError, Result};
+use super::{client::ContractConfig, decode, manager::ScalableContractManager};
+
+/// Events requested per getEvents call
+pub const EVENT_PAGE_SIZE: u32 = 100;
+
+/// Rating the contract gives every player when they join
+pub const INITIAL_RATING: i32 = 1200;
+
+const PLAYER_JOINED: &str = "player_joined";
+const WIN_ADDED: &str = "win_added";
+const RATING_CHANGED: &str = "rating_changed";
+
+/// A contract event as returned by getEvents
+#[derive(Debug, Clone)]
+pub struct RawContractEvent {
+    pub id: String,
+    pub ledger: u64,
+    pub topics: Vec<ScVal>,
+    pub value: ScVal,
+}
+
+/// One page of getEvents results
+#[derive(Debug, Clone)]
+pub struct EventPage {
+    pub events: Vec<RawContractEvent>,
+    /// Cursor to continue from, positioned after the last event returned
+    pub cursor: Option<String>,
+    pub latest_ledger: u64,
+}
+
+/// Where an events query starts
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventStart {
+    Ledger(u32),
+    Cursor(String),
+}
+
+/// Source of contract events, Soroban RPC in production
+pub trait EventSource: Send + Sync + 'static {
+    fn get_events(
+        &self,
+        contract: &ContractConfig,
+        start: EventStart,
+        limit: u32,
+    ) -> impl Future<Output = Result<EventPage>> + Send;
+}
+
+/// getEvents against the contract's Soroban RPC, filtered to the indexed events
+pub struct RpcEventSource;
+
+impl EventSource for RpcEventSource {
+    async fn get_events(&self, contract: &ContractConfig, start: EventStart, limit: u32) -> Result<EventPage> {
+        let config = contract.clone();
+
+        // The soroban client isn't Send, so drive it on a blocking thread
+        tokio::task::spawn_blocking(move || {
+            tokio::runtime::Handle::current().block_on(async move {
+                let rpc = Server::new(&config.rpc_url, Options::default())
+                    .map_err(|e| AppError::StellarRpc(format!("Failed to connect to Soroban RPC: {:?}", e)))?;
+
+                let filter = EventFilter::new(EventType::Contract)
+                    .contract(&config.contract_id)
+                    .topic(vec![Topic::Val(symbol(PLAYER_JOINED))])
+                    .topic(vec![Topic::Val(symbol(WIN_ADDED)), Topic::Any])
+                    .topic(vec![Topic::Val(symbol(RATING_CHANGED)), Topic::Any]);
+
+                let pagination = match start {
+                    EventStart::Ledger(ledger) => Pagination::From(ledger),
+                    EventStart::Cursor(cursor) => Pagination::Cursor(cursor),
+                };
+
+                let response = rpc.get_events(pagination, vec![filter], limit).await
+                    .map_err(|e| AppError::StellarRpc(format!("Failed to get contract events: {:?}", e)))?;
+
+                Ok(EventPage {
+                    events: response.events.iter()
+                        .map(|event| RawContractEvent {
+                            id: event.id.clone(),
+                            ledger: event.ledger,
+                            topics: event.topic(),
+                            value: event.value(),
+                        })
+                        .collect(),
+                    cursor: response.cursor,
+                    latest_ledger: response.latest_ledger,
+                })
+            })
+        })
+        .await
+        .map_err(|e| AppError::TaskExecution(format!("Event fetch task failed: {}", e)))?
+    }
+}
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().expect("event names are valid symbols")))
+}
+
+/// Decode a player_joined, win_added or rating_changed event, or None for events the indexer ignores
+pub fn decode_event(event: &RawContractEvent) -> Result<Option<NewContractEvent>> {
+    let name = match event.topics.first() {
+        Some(ScVal::Symbol(name)) => name.to_utf8_string_lossy(),
+        _ => return Ok(None),
+    };
+
+    let player_topic = || {
+        event.topics.get(1)
+            .ok_or_else(|| AppError::XdrDecoding(format!("{} event has no player topic", name)))
+    };
+
+    let (player, wins, rating) = match name.as_str() {
+        PLAYER_JOINED => (decode::scval_to_address(&event.value)?, None, Some(INITIAL_RATING)),
+        WIN_ADDED => {
+            let wins = decode::scval_to_u32(&event.value)?;
+            (decode::scval_to_address(player_topic()?)?, Some(wins as i32), None)
+        }
+        RATING_CHANGED => {
+            let rating = decode::scval_to_u32(&event.value)?;
+            (decode::scval_to_address(player_topic()?)?, None, Some(rating as i32))
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(NewContractEvent {
+        event_id: event.id.clone(),
+        ledger: event.ledger as i64,
+        event_type: name,
+        player_wallet_address: player,
+        wins,
+        rating,
+    }))
+}
+
+/// Mirrors contract events into Postgres
+///
+/// Each poll pages through getEvents for the current default contract from its
+/// stored cursor, or from `start_ledger` on its first run, and commits every page
+/// together with the cursor after it so a restart resumes where the last poll stopped.
+pub struct EventIndexer<S> {
+    source: S,
+    pool: DbPool,
+    contracts: Arc<ScalableContractManager>,
+    start_ledger: u32,
+    interval: Duration,
+}
+
+impl<S: EventSource> EventIndexer<S> {
+    pub fn new(source: S, pool: DbPool, contracts: Arc<ScalableContractManager>, start_ledger: u32, interval: Duration) -> Self {
+        Self {
+            source,
+            pool,
+            contracts,
+            start_ledger,
+            interval,
+        }
+    }
+
+    /// Index every event available now, returning how many were new
+    pub async fn sync(&self) -> Result<u64> {
+        // Looked up every poll, so reloads and admin changes apply without a restart
+        let contract = self.contracts.default_contract_config().await?;
+        let mut start = match ContractIndexRepository::get_cursor(&self.pool, &contract.contract_id).await? {
+            Some(cursor) => EventStart::Cursor(cursor),
+            None => EventStart::Ledger(self.start_ledger),
+        };
+        let mut indexed = 0;
+
+        loop {
+            let page = self.source.get_events(&contract, start.clone(), EVENT_PAGE_SIZE).await?;
+            // A malformed event mustn't stall the indexer, so it's logged and stepped over
+            let mut events = Vec::new();
+            for event in &page.events {
+                match decode_event(event) {
+                    Ok(Some(decoded)) => events.push(decoded),
+                    Ok(None) => {}
+                    Err(error) => warn!("⚠️  Skipping undecodable contract event {}: {}", event.id, error),
+                }
+            }
+
+            // Without a page cursor, resume after the last event; event ids double as cursors
+            let Some(cursor) = page.cursor.or_else(|| page.events.last().map(|event| event.id.clone())) else {
+                break;
+            };
+            indexed += ContractIndexRepository::record_page(
+                &self.pool,
+                &contract.contract_id,
+                &events,
+                &cursor,
+                page.latest_ledger as i64,
+            ).await?;
+            debug!("Indexed {} contract events up to cursor {}", events.len(), cursor);
+
+            // A short page means we've caught up
+            if page.events.len() < EVENT_PAGE_SIZE as usize {
+                break;
+            }
+            start = EventStart::Cursor(cursor);
+        }
+
+        Ok(indexed)
+    }
+
+    /// Run the indexer loop in the background
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!("🔎 Contract event indexer started (every {:?})", self.interval);
+
+            let mut ticker = tokio::time::interval(self.interval);
+            loop {
+                ticker.tick().await;
+
+                match self.sync().await {
+                    Ok(0) => {}
+                    Ok(indexed) => info!("✅ Indexed {} new contract events", indexed),
+                    Err(error) => warn!("❌ Contract event indexing failed: {}", error),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::registry_config::RegistryConfig;
+    use soroban_client::xdr::{AccountId, PublicKey, ScAddress, Uint256};
+    use std::{collections::VecDeque, sync::{Arc, Mutex}};
+    use stellar_strkey::ed25519::PublicKey as Ed25519PublicKey;
+
+    const CONTRACT: &str = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC";
+    const OTHER_CONTRACT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+    const PLAYER: &str = "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ";
+
+    /// Registry holding CONTRACT and OTHER_CONTRACT, with `default` as the default contract
+    fn registry_config(default: &str) -> RegistryConfig {
+        RegistryConfig::parse(&format!(
+            r#"
+            default_contract = "{default}"
+
+            [networks.testnet]
+            network = "testnet"
+
+            [[contracts]]
+            contract_id = "{CONTRACT}"
+            name = "Leaderboard"
+            network = "testnet"
+
+            [[contracts]]
+            contract_id = "{OTHER_CONTRACT}"
+            name = "Season 2 leaderboard"
+            network = "testnet"
+            "#
+        ))
+        .unwrap()
+    }
+
+    async fn test_contracts(pool: DbPool) -> Arc<ScalableContractManager> {
+        Arc::new(ScalableContractManager::new(pool, &registry_config(CONTRACT)).await.unwrap())
+    }
+
+    /// Serves canned pages and records the requests made
+    #[derive(Clone)]
+    struct StubEventSource {
+        pages: Arc<Mutex<VecDeque<EventPage>>>,
+        requests: Arc<Mutex<Vec<EventStart>>>,
+    }
+
+    impl StubEventSource {
+        fn new(pages: Vec<EventPage>) -> Self {
+            Self {
+                pages: Arc::new(Mutex::new(pages.into())),
+                requests: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    impl EventSource for StubEventSource {
+        async fn get_events(&self, _contract: &ContractConfig, start: EventStart, _limit: u32) -> Result<EventPage> {
+            self.requests.lock().unwrap().push(start);
+            Ok(self.pages.lock().unwrap().pop_front().unwrap_or(EventPage {
+                events: Vec::new(),
+                cursor: None,
+                latest_ledger: 0,
+            }))
+        }
+    }
+
+    fn address(strkey: &str) -> ScVal {
+        let key = Ed25519PublicKey::from_string(strkey).unwrap();
+        ScVal::Address(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0)))))
+    }
+
+    fn joined(id: &str, ledger: u64) -> RawContractEvent {
+        RawContractEvent {
+            id: id.to_string(),
+            ledger,
+            topics: vec![symbol(PLAYER_JOINED)],
+            value: address(PLAYER),
+        }
+    }
+
+    fn win(id: &str, ledger: u64, wins: u32) -> RawContractEvent {
+        RawContractEvent {
+            id: id.to_string(),
+            ledger,
+            topics: vec![symbol(WIN_ADDED), address(PLAYER)],
+            value: ScVal::U32(wins),
+        }
+    }
+
+    fn rating(id: &str, ledger: u64, rating: u32) -> RawContractEvent {
+        RawContractEvent {
+            id: id.to_string(),
+            ledger,
+            topics: vec![symbol(RATING_CHANGED), address(PLAYER)],
+            value: ScVal::U32(rating),
+        }
+    }
+
+    fn page(events: Vec<RawContractEvent>, cursor: &str) -> EventPage {
+        EventPage {
+            events,
+            cursor: Some(cursor.to_string()),
+            latest_ledger: 500,
+        }
+    }
+
+    #[test]
+    fn decodes_indexed_events_and_skips_others() {
+        let decoded = decode_event(&win("10-1", 10, 3)).unwrap().unwrap();
+        assert_eq!(decoded.event_type, WIN_ADDED);
+        assert_eq!(decoded.player_wallet_address, PLAYER);
+        assert_eq!(decoded.wins, Some(3));
+
+        let other = RawContractEvent {
+            id: "11-1".to_string(),
+            ledger: 11,
+            topics: vec![symbol("season_started"), ScVal::U32(1)],
+            value: ScVal::U64(0),
+        };
+        assert!(decode_event(&other).unwrap().is_none());
+        assert!(decode_event(&RawContractEvent { value: ScVal::U32(1), ..joined("12-1", 12) }).is_err());
+    }
+
+    #[sqlx::test]
+    async fn sync_indexes_pages_and_resumes_from_cursor(pool: DbPool) {
+        let source = StubEventSource::new(vec![
+            page(vec![joined("100-1", 100), win("101-1", 101, 1)], "101-1"),
+            page(vec![win("102-1", 102, 2)], "102-1"),
+        ]);
+        let indexer = EventIndexer::new(source.clone(), pool.clone(), test_contracts(pool.clone()).await, 90, Duration::from_secs(1));
+
+        assert_eq!(indexer.sync().await.unwrap(), 2);
+        assert_eq!(indexer.sync().await.unwrap(), 1);
+        assert_eq!(
+            *source.requests.lock().unwrap(),
+            vec![EventStart::Ledger(90), EventStart::Cursor("101-1".to_string())]
+        );
+
+        let player = ContractIndexRepository::get_player(&pool, PLAYER).await.unwrap().unwrap();
+        assert_eq!(player.wins, 2);
+        assert_eq!(player.joined_ledger, Some(100));
+        assert_eq!(player.last_event_ledger, 102);
+        assert_eq!(
+            ContractIndexRepository::get_cursor(&pool, CONTRACT).await.unwrap().as_deref(),
+            Some("102-1")
+        );
+    }
+
+    #[sqlx::test]
+    async fn sync_skips_undecodable_events_and_falls_back_to_the_last_event_id(pool: DbPool) {
+        let malformed = RawContractEvent { value: ScVal::U32(1), ..joined("100-1", 100) };
+        let source = StubEventSource::new(vec![EventPage {
+            events: vec![malformed, joined("101-1", 101)],
+            cursor: None,
+            latest_ledger: 500,
+        }]);
+        let indexer = EventIndexer::new(source.clone(), pool.clone(), test_contracts(pool.clone()).await, 90, Duration::from_secs(1));
+
+        assert_eq!(indexer.sync().await.unwrap(), 1);
+        assert!(ContractIndexRepository::get_player(&pool, PLAYER).await.unwrap().is_some());
+        assert_eq!(
+            ContractIndexRepository::get_cursor(&pool, CONTRACT).await.unwrap().as_deref(),
+            Some("101-1")
+        );
+    }
+
+    #[sqlx::test]
+    async fn ratings_follow_the_latest_rating_changed_event(pool: DbPool) {
+        let source = StubEventSource::new(vec![
+            page(vec![joined("100-1", 100), rating("101-1", 101, 1216)], "101-1"),
+            page(vec![rating("102-1", 102, 1199)], "102-1"),
+        ]);
+        let indexer = EventIndexer::new(source, pool.clone(), test_contracts(pool.clone()).await, 90, Duration::from_secs(1));
+
+        assert_eq!(decode_event(&joined("99-1", 99)).unwrap().unwrap().rating, Some(INITIAL_RATING));
+
+        indexer.sync().await.unwrap();
+        let player = ContractIndexRepository::get_player(&pool, PLAYER).await.unwrap().unwrap();
+        assert_eq!(player.rating, Some(1216));
+
+        // Ratings can fall, unlike wins
+        indexer.sync().await.unwrap();
+        let player = ContractIndexRepository::get_player(&pool, PLAYER).await.unwrap().unwrap();
+        assert_eq!(player.rating, Some(1199));
+    }
+
+    #[sqlx::test]
+    async fn sync_follows_the_current_default_contract(pool: DbPool) {
+        let source = StubEventSource::new(vec![page(vec![joined("100-1", 100)], "100-1")]);
+        let contracts = test_contracts(pool.clone()).await;
+        let indexer = EventIndexer::new(source.clone(), pool.clone(), contracts.clone(), 90, Duration::from_secs(1));
+
+        // A disabled default contract isn't indexed
+        contracts.set_contract_enabled(CONTRACT, false).await.unwrap();
+        assert!(indexer.sync().await.is_err());
+        assert!(source.requests.lock().unwrap().is_empty());
+
+        contracts.reload(&registry_config(OTHER_CONTRACT)).await;
+        assert_eq!(indexer.sync().await.unwrap(), 1);
+        assert_eq!(
+            ContractIndexRepository::get_cursor(&pool, OTHER_CONTRACT).await.unwrap().as_deref(),
+            Some("100-1")
+        );
+        assert!(ContractIndexRepository::get_cursor(&pool, CONTRACT).await.unwrap().is_none());
+    }
+
+    #[sqlx::test]
+    async fn replayed_events_are_not_counted_twice(pool: DbPool) {
+        let source = StubEventSource::new(vec![
+            page(vec![joined("100-1", 100), win("101-1", 101, 1)], "101-1"),
+            page(vec![win("101-1", 101, 1)], "101-1"),
+        ]);
+        let indexer = EventIndexer::new(source.clone(), pool.clone(), test_contracts(pool.clone()).await, 90, Duration::from_secs(1));
+
+        assert_eq!(indexer.sync().await.unwrap(), 2);
+        assert_eq!(indexer.sync().await.unwrap(), 0);
+
+        let leaderboard = ContractIndexRepository::get_leaderboard(&pool, 10).await.unwrap();
+        assert_eq!(leaderboard.len(), 1);
+        assert_eq!(leaderboard[0].wins, 1);
+    }
+}
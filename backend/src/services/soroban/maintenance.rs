@@ -0,0 +1,154 @@
+use soroban_client::{
+    Server, Options,
+    keypair::{Keypair, KeypairBehavior},
+    transaction::{Account, TransactionBuilder, AccountBehavior, TransactionBuilderBehavior, TransactionBehavior},
+    contract::{Contracts, ContractBehavior},
+    soroban_rpc::SendTransactionStatus,
+    xdr::ScVal,
+};
+use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+use tracing::{info, debug, warn};
+
+use crate::error::{AppError, Result};
+use super::{client::ContractConfig, manager::ScalableContractManager};
+
+/// Entries covered by each bump_all call, matching the contract's MAX_BUMP_BATCH
+pub const BUMP_BATCH_SIZE: u32 = 50;
+
+/// Periodically extends the TTL of leaderboard storage so inactive players aren't archived
+///
+/// Each tick submits one bump_all batch signed by the contract admin, walking the
+/// player, tournament and wager indexes with the cursor the contract returns and
+/// wrapping back to 0 at the end. The default contract is looked up every tick, so
+/// reloads and admin changes apply without a restart.
+pub struct TtlMaintenance {
+    contracts: Arc<ScalableContractManager>,
+    admin_secret_key: String,
+    interval: Duration,
+}
+
+impl TtlMaintenance {
+    pub fn new(contracts: Arc<ScalableContractManager>, admin_secret_key: String, interval: Duration) -> Self {
+        Self {
+            contracts,
+            admin_secret_key,
+            interval,
+        }
+    }
+
+    /// Run the maintenance loop in the background
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!("⏳ TTL maintenance started (every {:?})", self.interval);
+
+            let mut ticker = tokio::time::interval(self.interval);
+            let mut contract_id = String::new();
+            let mut cursor = 0u32;
+
+            loop {
+                ticker.tick().await;
+
+                let config = match self.contracts.default_contract_config().await {
+                    Ok(config) => config,
+                    Err(error) => {
+                        warn!("⚠️  Skipping TTL bump: {}", error);
+                        continue;
+                    }
+                };
+
+                // A different default contract starts its own walk from the beginning
+                if config.contract_id != contract_id {
+                    contract_id = config.contract_id.clone();
+                    cursor = 0;
+                }
+
+                let secret = self.admin_secret_key.clone();
+                let start = cursor;
+
+                // The soroban client isn't Send, so drive it on a blocking thread
+                let result = tokio::task::spawn_blocking(move || {
+                    tokio::runtime::Handle::current().block_on(async move {
+                        bump_batch(&config, &secret, start, BUMP_BATCH_SIZE).await
+                    })
+                }).await;
+
+                match result {
+                    Ok(Ok(next)) => {
+                        info!("✅ Extended storage TTL from player {}, next batch starts at {}", start, next);
+                        cursor = next;
+                    }
+                    Ok(Err(error)) => warn!("❌ TTL bump from player {} failed: {}", start, error),
+                    Err(join_error) => warn!("❌ TTL bump task failed: {}", join_error),
+                }
+            }
+        })
+    }
+}
+
+/// Submit a single bump_all(start, count) call signed by the admin
+/// Returns the cursor for the next batch, as reported by the contract
+pub async fn bump_batch(
+    config: &ContractConfig,
+    admin_secret_key: &str,
+    start: u32,
+    count: u32,
+) -> Result<u32> {
+    config.validate()?;
+
+    let keypair = Keypair::from_secret(admin_secret_key)
+        .map_err(|e| AppError::Config(format!("Invalid admin secret key: {}", e)))?;
+    let admin_account = keypair.public_key();
+
+    let rpc = Server::new(&config.rpc_url, Options::default())
+        .map_err(|e| AppError::StellarRpc(format!("Failed to connect to Soroban RPC: {:?}", e)))?;
+
+    let account_response = rpc.get_account(&admin_account).await
+        .map_err(|e| AppError::Account(format!("Failed to get admin account info: {:?}", e)))?;
+
+    let account = Account::new(&admin_account, &account_response.sequence_number())
+        .map_err(|e| AppError::Account(format!("Failed to create account: {:?}", e)))?;
+
+    let contract = Contracts::new(&config.contract_id)
+        .map_err(|e| AppError::Transaction(format!("Failed to create contract: {:?}", e)))?;
+
+    let mut tx_builder = TransactionBuilder::new(
+        Rc::new(RefCell::new(account)),
+        &config.network_passphrase,
+        None
+    );
+    tx_builder.fee(1000000u32);
+    tx_builder.add_operation(contract.call(
+        "bump_all",
+        Some(vec![ScVal::U32(start), ScVal::U32(count)]),
+    ));
+    let tx = tx_builder.build();
+
+    // Simulate first to learn where the next batch starts
+    let simulation = rpc.simulate_transaction(&tx, None).await
+        .map_err(|e| AppError::Transaction(format!("Failed to simulate bump_all: {:?}", e)))?;
+    let next = match simulation.to_result() {
+        Some((ScVal::U32(next), _)) => next,
+        other => {
+            return Err(AppError::Transaction(format!("Unexpected bump_all result: {:?}", other)));
+        }
+    };
+    debug!("bump_all({}, {}) simulated, next cursor {}", start, count, next);
+
+    let mut prepared_tx = rpc.prepare_transaction(&tx).await
+        .map_err(|e| AppError::Transaction(format!("Failed to prepare transaction: {:?}", e)))?;
+    prepared_tx.sign(&[keypair]);
+
+    let response = rpc.send_transaction(prepared_tx).await
+        .map_err(|e| AppError::Transaction(format!("Failed to send transaction: {:?}", e)))?;
+
+    match response.status {
+        SendTransactionStatus::Pending | SendTransactionStatus::Duplicate => {
+            info!("📤 bump_all submitted: {}", response.hash);
+            Ok(next)
+        }
+        status => Err(AppError::Transaction(format!(
+            "bump_all transaction {} rejected with status {:?}",
+            response.hash, status
+        ))),
+    }
+}
@@ -1,36 +1,63 @@
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{info, warn, error};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    registry::{ContractRegistry, ContractMetadata, create_default_registry},
-    queue::{ContractQueue, ContractOperation, OperationPriority, QueueResult},
-    pool::PoolConfig,
-    circuit_breaker::CircuitBreakerConfig,
+    cache::{function_key, CacheLookup, CachePolicy},
+    client::ContractConfig,
+    registry::{ContractRegistry, ContractMetadata, ContractHandle},
+    registry_config::RegistryConfig,
+    queue::{ContractQueue, ContractOperation, OperationPriority, OracleCall, OracleKey, QueueResult},
+    tracker::TrackedTransaction,
+    circuit_breaker::CircuitBreakerError,
 };
+use crate::database::DbPool;
 use crate::error::{AppError, Result};
-use shared::dto::contract::LeaderboardFunction;
+use shared::dto::contract::{ContractValue, LeaderboardFunction};
+
+/// Workers draining the contract operation queue
+const QUEUE_WORKERS: usize = 4;
+
+/// Send attempts for oracle calls, which may wait on the oracle's previous transaction
+const ORACLE_MAX_RETRIES: u32 = 6;
+
+/// How long a read-only query result is served from cache
+const QUERY_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// How long past its TTL a shared read like the leaderboard is served while it refreshes
+const QUERY_STALE_FOR: Duration = Duration::from_secs(60);
 
 /// High-level contract manager that orchestrates all scalability components
 pub struct ScalableContractManager {
     registry: Arc<ContractRegistry>,
     queue: Arc<ContractQueue>,
     metrics: Arc<tokio::sync::RwLock<ContractMetrics>>,
+    default_contract_id: std::sync::RwLock<String>,
 }
 
 impl ScalableContractManager {
     /// Create a new scalable contract manager with all features enabled
-    pub async fn new() -> Result<Self> {
+    pub async fn new(pool: DbPool, config: &RegistryConfig) -> Result<Self> {
         info!("🚀 Initializing Scalable Contract Manager");
 
-        // Create contract registry with default contracts
-        let registry = create_default_registry()
+        // Create contract registry with the configured contracts
+        let registry = ContractRegistry::from_config(config)
             .await
             .map_err(|e| AppError::Config(format!("Failed to create registry: {}", e)))?;
 
-        // Create async queue for operations
-        let queue = Arc::new(ContractQueue::new());
+        let manager = Self::with_registry(Arc::new(registry), pool, config.default_contract_id());
+
+        info!("✅ Scalable Contract Manager initialized successfully");
+        Ok(manager)
+    }
+
+    /// Build a manager around an existing registry
+    pub fn with_registry(registry: Arc<ContractRegistry>, pool: DbPool, default_contract_id: String) -> Self {
+        // Durable queue for signed transactions, worked in the background
+        let queue = Arc::new(ContractQueue::new(pool));
+        queue.spawn_workers(QUEUE_WORKERS);
 
         // Initialize metrics
         let metrics = Arc::new(tokio::sync::RwLock::new(ContractMetrics::default()));
@@ -38,72 +65,101 @@ impl ScalableContractManager {
         // Start background tasks
         Self::start_background_tasks(queue.clone(), metrics.clone());
 
-        info!("✅ Scalable Contract Manager initialized successfully");
-
-        Ok(Self {
+        Self {
             registry,
             queue,
             metrics,
-        })
+            default_contract_id: std::sync::RwLock::new(default_contract_id),
+        }
+    }
+
+    /// Sign oracle calls, like recording match results, with the admin key
+    pub fn with_oracle(self, key: OracleKey) -> Self {
+        info!("🔑 Oracle calls are signed by {}", key.public_key());
+        self.queue.set_oracle(key);
+        self
+    }
+
+    /// Whether an oracle key is configured
+    pub fn has_oracle(&self) -> bool {
+        self.queue.oracle().is_some()
+    }
+
+    /// Contract the handlers talk to unless told otherwise
+    pub fn default_contract_id(&self) -> String {
+        self.default_contract_id.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Client configuration for the current default contract
+    /// Fails while that contract is disabled or no longer registered
+    pub async fn default_contract_config(&self) -> Result<ContractConfig> {
+        Ok(self.handle(&self.default_contract_id()).await?.contract_config())
+    }
+
+    /// Swap in a reloaded registry config
+    pub async fn reload(&self, config: &RegistryConfig) {
+        if let Err(error) = self.registry.reload(config).await {
+            warn!("❌ Contract registry reload failed: {}", error);
+            return;
+        }
+        *self.default_contract_id.write().unwrap_or_else(|e| e.into_inner()) = config.default_contract_id();
     }
 
     /// Generate XDR for contract function with all scalability features
+    /// Not cached: the envelope embeds the source account's sequence number
     pub async fn generate_xdr(
         &self,
         contract_id: &str,
         source_account: &str,
         function: &LeaderboardFunction,
     ) -> Result<String> {
-        // Get contract handle from registry
-        let handle = self
-            .registry
-            .get(contract_id)
-            .await
-            .ok_or_else(|| AppError::Config(format!("Contract not found: {}", contract_id)))?;
-
-        // Check cache first
-        let cache_key = format!("xdr:{}:{}:{}", contract_id, source_account, function.name());
-        if let Some(cached_xdr) = handle.cache.get(&cache_key).await {
-            info!("✅ XDR retrieved from cache");
-            self.record_cache_hit().await;
-            return String::from_utf8(cached_xdr)
-                .map_err(|e| AppError::XdrEncoding(format!("Invalid cached XDR: {}", e)));
-        }
+        let handle = self.handle(contract_id).await?;
+        let source_account = source_account.to_string();
+        let function = function.clone();
 
-        self.record_cache_miss().await;
+        let xdr = Self::call_contract(&handle, move |config| async move {
+            super::client::generate_leaderboard_xdr(&config, &source_account, &function).await
+        })
+        .await?;
 
-        // Get RPC connection from pool (validates connection is available)
-        let _connection = handle
-            .get_rpc_connection()
-            .await
-            .map_err(|e| AppError::StellarRpc(e))?;
-
-        // Execute with circuit breaker protection
-        let xdr_result = handle
-            .call_with_protection(async {
-                // Call the actual XDR generation
-                super::client::generate_leaderboard_xdr(
-                    &super::client::ContractConfig {
-                        contract_id: handle.metadata.contract_id.clone(),
-                        network_passphrase: handle.metadata.network_passphrase.clone(),
-                        rpc_url: handle.metadata.rpc_url.clone(),
-                    },
-                    source_account,
-                    function,
-                )
+        self.record_xdr_generated().await;
+        Ok(xdr)
+    }
+
+    /// Answer a read-only contract function by simulation
+    /// Results are cached briefly, keyed by the function and its arguments
+    pub async fn query(&self, contract_id: &str, function: &LeaderboardFunction) -> Result<ContractValue> {
+        let handle = self.handle(contract_id).await?;
+        let cache = handle.cache.clone();
+        let cache_key = function_key("query", function)?;
+
+        let query_function = function.clone();
+        let (value, lookup) = cache
+            .get_or_fetch(&cache_key, query_cache_policy(function), move || async move {
+                Self::call_contract(&handle, move |config| async move {
+                    super::client::query_contract(&config, &query_function).await
+                })
                 .await
             })
-            .await
-            .map_err(|e| AppError::StellarRpc(e))?;
+            .await?;
 
-        // Cache the result (1 minute TTL for XDR)
-        handle
-            .cache
-            .set(cache_key, xdr_result.clone().into_bytes(), Some(Duration::from_secs(60)))
-            .await;
+        match lookup {
+            CacheLookup::Fresh => self.record_cache_hit().await,
+            CacheLookup::Stale => self.record_cache_stale_hit().await,
+            CacheLookup::Miss => self.record_cache_miss().await,
+        }
+        Ok(value)
+    }
 
-        self.record_xdr_generated().await;
-        Ok(xdr_result)
+    /// Whether a player has joined the leaderboard, always read fresh
+    pub async fn has_joined(&self, contract_id: &str, player: &str) -> Result<bool> {
+        let handle = self.handle(contract_id).await?;
+        let player = player.to_string();
+
+        Self::call_contract(&handle, move |config| async move {
+            super::client::has_joined(&config, &player).await
+        })
+        .await
     }
 
     /// Submit signed transaction via async queue with retry logic
@@ -115,9 +171,12 @@ impl ScalableContractManager {
         signed_xdr: String,
         priority: Option<OperationPriority>,
     ) -> Result<String> {
+        let handle = self.handle(contract_id).await?;
+
         // Create operation
         let operation = ContractOperation::new(
             contract_id.to_string(),
+            handle.metadata.rpc_url.clone(),
             function.name().to_string(),
             source_account,
             Some(signed_xdr),
@@ -130,12 +189,51 @@ impl ScalableContractManager {
             .queue
             .submit(operation)
             .await
-            .map_err(|e| AppError::Transaction(e))?;
+            .map_err(AppError::Transaction)?;
 
         self.record_transaction_submitted().await;
         Ok(operation_id)
     }
 
+    /// Queue a contract call that the backend signs with the oracle key when it sends it
+    pub async fn submit_oracle_call(
+        &self,
+        contract_id: &str,
+        function: LeaderboardFunction,
+        priority: Option<OperationPriority>,
+    ) -> Result<String> {
+        let oracle_account = self.queue.oracle()
+            .map(|key| key.public_key().to_string())
+            .ok_or_else(|| AppError::Config("No oracle key configured".to_string()))?;
+        let handle = self.handle(contract_id).await?;
+
+        let operation = ContractOperation::oracle(
+            contract_id.to_string(),
+            handle.metadata.rpc_url.clone(),
+            oracle_account,
+            OracleCall {
+                network_passphrase: handle.metadata.network_passphrase.clone(),
+                function,
+            },
+        )
+        .with_priority(priority.unwrap_or(OperationPriority::Normal))
+        .with_max_retries(ORACLE_MAX_RETRIES);
+
+        let operation_id = self
+            .queue
+            .submit(operation)
+            .await
+            .map_err(AppError::Transaction)?;
+
+        self.record_transaction_submitted().await;
+        Ok(operation_id)
+    }
+
+    /// Latest status of a submitted transaction, by operation id
+    pub async fn get_transaction_status(&self, operation_id: &str) -> Result<Option<TrackedTransaction>> {
+        self.queue.status(operation_id).await.map_err(AppError::Internal)
+    }
+
     /// Get operation result from queue
     pub async fn get_operation_result(&self) -> Option<QueueResult> {
         self.queue.next_result().await
@@ -143,10 +241,27 @@ impl ScalableContractManager {
 
     /// Register a new contract dynamically
     pub async fn register_contract(&self, metadata: ContractMetadata) -> Result<()> {
+        if self.registry.contains(&metadata.contract_id).await {
+            return Err(AppError::InvalidInput(format!("Contract {} is already registered", metadata.contract_id)));
+        }
+
         self.registry
             .register(metadata)
             .await
-            .map_err(|e| AppError::Config(e))
+            .map_err(AppError::Config)
+    }
+
+    /// Enable or disable a registered contract
+    pub async fn set_contract_enabled(&self, contract_id: &str, enabled: bool) -> Result<ContractMetadata> {
+        self.registry
+            .set_enabled(contract_id, enabled)
+            .await
+            .map_err(AppError::NotFound)
+    }
+
+    /// Metadata of a registered contract
+    pub async fn metadata(&self, contract_id: &str) -> Result<ContractMetadata> {
+        Ok(self.handle(contract_id).await?.metadata)
     }
 
     /// List all registered contracts
@@ -161,11 +276,7 @@ impl ScalableContractManager {
 
     /// Get detailed contract information
     pub async fn get_contract_info(&self, contract_id: &str) -> Result<ContractInfo> {
-        let handle = self
-            .registry
-            .get(contract_id)
-            .await
-            .ok_or_else(|| AppError::Config(format!("Contract not found: {}", contract_id)))?;
+        let handle = self.handle(contract_id).await?;
 
         Ok(ContractInfo {
             metadata: handle.metadata.clone(),
@@ -190,12 +301,67 @@ impl ScalableContractManager {
         }
     }
 
+    async fn handle(&self, contract_id: &str) -> Result<ContractHandle> {
+        match self.registry.get(contract_id).await {
+            Some(handle) => Ok(handle),
+            None if self.registry.contains(contract_id).await => Err(AppError::ExternalService(
+                format!("Contract {} is disabled", contract_id)
+            )),
+            None => Err(AppError::Config(format!("Contract not found: {}", contract_id))),
+        }
+    }
+
+    /// Run a soroban client call against a contract through its pool and circuit breaker
+    ///
+    /// The client's futures aren't Send, so the call is driven on a blocking
+    /// thread. Only RPC and task failures count against the breaker; a call the
+    /// contract itself rejects still shows the RPC is reachable.
+    async fn call_contract<T, F, Fut>(handle: &ContractHandle, call: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(ContractConfig) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T>> + 'static,
+    {
+        let _connection = handle.get_rpc_connection().await.map_err(AppError::StellarRpc)?;
+        let config = handle.contract_config();
+
+        let outcome = handle
+            .circuit_breaker
+            .call(async move {
+                let result = tokio::task::spawn_blocking(move || {
+                    tokio::runtime::Handle::current().block_on(call(config))
+                })
+                .await
+                .map_err(|e| AppError::TaskExecution(format!("Contract call task failed: {}", e)))?;
+
+                match result {
+                    Err(error @ AppError::StellarRpc(_)) => Err(error),
+                    answered => Ok(answered),
+                }
+            })
+            .await;
+
+        match outcome {
+            Ok(answered) => answered,
+            Err(CircuitBreakerError::CircuitOpen) => Err(AppError::StellarRpc(format!(
+                "Contract {} is temporarily unavailable, try again shortly",
+                handle.metadata.contract_id
+            ))),
+            Err(CircuitBreakerError::RequestFailed(error)) => Err(error),
+        }
+    }
+
     // Internal metric recording methods
     async fn record_cache_hit(&self) {
         let mut metrics = self.metrics.write().await;
         metrics.cache_hits += 1;
     }
 
+    async fn record_cache_stale_hit(&self) {
+        let mut metrics = self.metrics.write().await;
+        metrics.cache_stale_hits += 1;
+    }
+
     async fn record_cache_miss(&self) {
         let mut metrics = self.metrics.write().await;
         metrics.cache_misses += 1;
@@ -252,6 +418,7 @@ pub struct ContractMetrics {
     pub failed_operations: u64,
     pub retried_operations: u64,
     pub cache_hits: u64,
+    pub cache_stale_hits: u64,
     pub cache_misses: u64,
     pub xdr_generated: u64,
     pub transactions_submitted: u64,
@@ -259,11 +426,12 @@ pub struct ContractMetrics {
 
 impl ContractMetrics {
     pub fn cache_hit_rate(&self) -> f64 {
-        let total = self.cache_hits + self.cache_misses;
+        let hits = self.cache_hits + self.cache_stale_hits;
+        let total = hits + self.cache_misses;
         if total == 0 {
             return 0.0;
         }
-        (self.cache_hits as f64 / total as f64) * 100.0
+        (hits as f64 / total as f64) * 100.0
     }
 
     pub fn success_rate(&self) -> f64 {
@@ -292,20 +460,118 @@ pub struct HealthStatus {
     pub cache_hit_rate: f64,
 }
 
-/// Configuration for the scalable contract manager
-#[derive(Debug, Clone)]
-pub struct ContractManagerConfig {
-    pub pool_config: PoolConfig,
-    pub circuit_breaker_config: CircuitBreakerConfig,
-    pub cache_ttl: Duration,
+/// Shared reads that many players poll may be served stale; per-player reads stay fresh
+fn query_cache_policy(function: &LeaderboardFunction) -> CachePolicy {
+    match function {
+        LeaderboardFunction::GetLeaderboard { .. }
+        | LeaderboardFunction::GetLeaderboardForSeason { .. }
+        | LeaderboardFunction::GetRatingLeaderboard { .. }
+        | LeaderboardFunction::GetAllPlayers
+        | LeaderboardFunction::GetPlayerCount
+        | LeaderboardFunction::GetCurrentSeason
+        | LeaderboardFunction::GetVersion => CachePolicy::stale_while_revalidate(QUERY_CACHE_TTL, QUERY_STALE_FOR),
+        _ => CachePolicy::fresh(QUERY_CACHE_TTL),
+    }
 }
 
-impl Default for ContractManagerConfig {
-    fn default() -> Self {
-        Self {
-            pool_config: PoolConfig::default(),
-            circuit_breaker_config: CircuitBreakerConfig::default(),
-            cache_ttl: Duration::from_secs(300),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{circuit_breaker::CircuitBreakerConfig, registry::NetworkType};
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+    use soroban_client::xdr::{Limits, ScVal, WriteXdr};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const CONTRACT: &str = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC";
+
+    /// Serve a JSON-RPC endpoint answering every call with `response`, counting the calls
+    async fn mock_rpc(response: Value) -> (String, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route("/", post(move |Json(request): Json<Value>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut response = response.clone();
+            response["id"] = request["id"].clone();
+            async move { Json(response) }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/", addr), calls)
+    }
+
+    async fn test_manager(pool: DbPool, rpc_url: String) -> ScalableContractManager {
+        let registry = Arc::new(ContractRegistry::new(
+            None,
+            Some(CircuitBreakerConfig {
+                failure_threshold: 2,
+                ..CircuitBreakerConfig::default()
+            }),
+        ));
+        registry.register(ContractMetadata {
+            contract_id: CONTRACT.to_string(),
+            name: "Test leaderboard".to_string(),
+            network: NetworkType::Standalone,
+            network_passphrase: NetworkType::Standalone.default_passphrase().to_string(),
+            rpc_url,
+            description: None,
+            version: None,
+            enabled: true,
+        }).await.unwrap();
+        ScalableContractManager::with_registry(registry, pool, CONTRACT.to_string())
+    }
+
+    fn simulated(value: ScVal) -> Value {
+        json!({ "jsonrpc": "2.0", "result": {
+            "latestLedger": 1000,
+            "minResourceFee": "100",
+            "results": [{ "auth": [], "xdr": value.to_xdr_base64(Limits::none()).unwrap() }],
+            "transactionData": null,
+        }})
+    }
+
+    #[sqlx::test]
+    async fn repeated_queries_are_served_from_cache(pool: DbPool) {
+        let (rpc_url, calls) = mock_rpc(simulated(ScVal::U32(7))).await;
+        let manager = test_manager(pool.clone(), rpc_url).await;
+
+        for _ in 0..2 {
+            let value = manager.query(CONTRACT, &LeaderboardFunction::GetPlayerCount).await.unwrap();
+            assert_eq!(value, ContractValue::U32(7));
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let metrics = manager.get_metrics().await;
+        assert_eq!((metrics.cache_hits, metrics.cache_misses), (1, 1));
+    }
+
+    #[sqlx::test]
+    async fn only_rpc_failures_open_the_circuit(pool: DbPool) {
+        let (rpc_url, _) = mock_rpc(json!({ "jsonrpc": "2.0", "result": {
+            "latestLedger": 1000,
+            "error": "HostError: Error(Contract, #2)",
+        }})).await;
+        let manager = test_manager(pool.clone(), rpc_url).await;
+        let player = "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ";
+
+        // The contract rejecting a call doesn't mean the RPC is down
+        for _ in 0..3 {
+            let error = manager.has_joined(CONTRACT, player).await.unwrap_err();
+            assert!(matches!(error, AppError::Transaction(_)));
+        }
+
+        let (rpc_url, calls) = mock_rpc(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32603, "message": "internal error" },
+        })).await;
+        let manager = test_manager(pool.clone(), rpc_url).await;
+
+        for _ in 0..3 {
+            let error = manager.has_joined(CONTRACT, player).await.unwrap_err();
+            assert!(matches!(error, AppError::StellarRpc(_)));
         }
+        // The third call was rejected by the open circuit without reaching the RPC
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 }
@@ -3,19 +3,27 @@
 pub mod cache;
 pub mod circuit_breaker;
 pub mod client;
+pub mod decode;
+pub mod indexer;
+pub mod maintenance;
 pub mod manager;
 pub mod pool;
 pub mod queue;
 pub mod registry;
+pub mod registry_config;
+pub mod tracker;
 
 // Re-export commonly used types for easier imports
 pub use cache::ContractCache;
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats};
-pub use client::{generate_leaderboard_xdr, submit_signed_transaction, ContractConfig};
+pub use client::{generate_leaderboard_xdr, ContractConfig};
+pub use indexer::{EventIndexer, EventSource, RpcEventSource};
+pub use maintenance::{bump_batch, TtlMaintenance, BUMP_BATCH_SIZE};
 pub use manager::{ScalableContractManager, ContractMetrics, ContractInfo, HealthStatus};
 pub use pool::{StellarRpcPool, PoolConfig, PoolStats, PooledRpcConnection};
-pub use queue::{ContractQueue, ContractOperation, OperationPriority, QueueResult};
+pub use queue::{ContractQueue, ContractOperation, OperationPriority, OracleCall, OracleKey, QueueResult};
 pub use registry::{
-    ContractRegistry, ContractMetadata, ContractHandle, NetworkType,
-    create_default_registry, RegistryStats,
+    ContractRegistry, ContractMetadata, ContractHandle, NetworkType, RegistryStats,
 };
+pub use registry_config::RegistryConfig;
+pub use tracker::{TrackedTransaction, TransactionRpc};
@@ -6,7 +6,7 @@ use std::time::{Duration, Instant};
 use crate::error::{AppError, Result};
 
 /// Connection pool configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PoolConfig {
     pub max_connections: usize,
     pub idle_timeout: Duration,
@@ -76,7 +76,12 @@ impl StellarRpcPool {
             conn
         } else {
             info!("📡 Creating new RPC connection to {}", self.rpc_url);
-            let server = Server::new(&self.rpc_url, Options::default())
+            // Standalone networks serve RPC over plain HTTP
+            let options = Options {
+                allow_http: self.rpc_url.starts_with("http://"),
+                ..Options::default()
+            };
+            let server = Server::new(&self.rpc_url, options)
                 .map_err(|e| AppError::StellarRpc(format!("Failed to create RPC server: {:?}", e)))?;
 
             PooledConnection {
@@ -118,7 +123,7 @@ impl PooledRpcConnection {
 
 impl Drop for PooledRpcConnection {
     fn drop(&mut self) {
-        let mut conn = PooledConnection {
+        let conn = PooledConnection {
             server: self.connection.server.clone(),
             last_used: Instant::now(),
         };
@@ -1,24 +1,79 @@
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, OnceLock,
+};
+use tokio::sync::{mpsc, Notify};
 use tokio::time::{sleep, Duration};
 use tracing::{error, info, warn};
 use uuid::Uuid;
+use shared::dto::contract::{ContractTransactionStatus, LeaderboardFunction};
+use soroban_client::keypair::{Keypair, KeypairBehavior};
+
+use super::client::{build_signed_xdr, ContractConfig};
+use super::tracker::{TrackedTransaction, TransactionRpc, CONFIRMATION_POLL_INTERVAL, CONFIRMATION_TIMEOUT};
+use crate::database::{
+    models::{ContractOperationRecord, NewContractOperation},
+    repositories::ContractOperationRepository,
+    DbPool,
+};
+use crate::error::AppError;
+
+/// Operations a worker claims at a time
+pub const CLAIM_BATCH_SIZE: i64 = 10;
+
+/// How long a claimed row stays hidden from other workers
+const CLAIM_LEASE: Duration = Duration::from_secs(30);
+
+/// How often idle workers look for operations that came due
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Contract operation to be queued
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractOperation {
     pub id: String,
     pub contract_id: String,
+    pub rpc_url: String,
     pub function_name: String,
     pub source_account: String,
     pub signed_xdr: Option<String>,
+    pub oracle_call: Option<OracleCall>,
     pub priority: OperationPriority,
     pub max_retries: u32,
-    pub retry_count: u32,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A contract call the queue signs with the oracle key when it sends it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleCall {
+    pub network_passphrase: String,
+    pub function: LeaderboardFunction,
+}
+
+/// The backend's signing key for oracle calls, the contract admin
+#[derive(Clone)]
+pub struct OracleKey {
+    secret_key: String,
+    public_key: String,
+}
+
+impl OracleKey {
+    pub fn from_secret(secret_key: &str) -> crate::error::Result<Self> {
+        let keypair = Keypair::from_secret(secret_key)
+            .map_err(|e| AppError::Config(format!("Invalid oracle secret key: {}", e)))?;
+
+        Ok(Self {
+            secret_key: secret_key.to_string(),
+            public_key: keypair.public_key(),
+        })
+    }
+
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum OperationPriority {
     Low = 0,
@@ -30,6 +85,7 @@ pub enum OperationPriority {
 impl ContractOperation {
     pub fn new(
         contract_id: String,
+        rpc_url: String,
         function_name: String,
         source_account: String,
         signed_xdr: Option<String>,
@@ -37,16 +93,24 @@ impl ContractOperation {
         Self {
             id: Uuid::new_v4().to_string(),
             contract_id,
+            rpc_url,
             function_name,
             source_account,
             signed_xdr,
+            oracle_call: None,
             priority: OperationPriority::Normal,
             max_retries: 3,
-            retry_count: 0,
             created_at: chrono::Utc::now(),
         }
     }
 
+    /// An unsigned call for the queue to sign with the oracle key
+    pub fn oracle(contract_id: String, rpc_url: String, oracle_account: String, call: OracleCall) -> Self {
+        let mut operation = Self::new(contract_id, rpc_url, call.function.name().to_string(), oracle_account, None);
+        operation.oracle_call = Some(call);
+        operation
+    }
+
     pub fn with_priority(mut self, priority: OperationPriority) -> Self {
         self.priority = priority;
         self
@@ -56,21 +120,23 @@ impl ContractOperation {
         self.max_retries = max_retries;
         self
     }
-
-    pub fn can_retry(&self) -> bool {
-        self.retry_count < self.max_retries
-    }
-
-    pub fn increment_retry(&mut self) {
-        self.retry_count += 1;
-    }
 }
 
-#[derive(Debug, Clone)]
-pub enum QueueMessage {
-    Submit(ContractOperation),
-    Status(String),
-    Shutdown,
+impl From<&ContractOperation> for NewContractOperation {
+    fn from(operation: &ContractOperation) -> Self {
+        Self {
+            id: operation.id.clone(),
+            contract_id: operation.contract_id.clone(),
+            rpc_url: operation.rpc_url.clone(),
+            function_name: operation.function_name.clone(),
+            source_account: operation.source_account.clone(),
+            signed_xdr: operation.signed_xdr.clone(),
+            oracle_call: operation.oracle_call.as_ref()
+                .and_then(|call| serde_json::to_string(call).ok()),
+            priority: operation.priority as i16,
+            max_retries: operation.max_retries as i32,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -80,94 +146,98 @@ pub enum QueueResult {
     Failed { operation_id: String, error: String },
 }
 
-/// Async queue for contract operations with retry logic
+/// Durable queue for contract operations, backed by the contract_operations table
+///
+/// Operations survive restarts: workers claim due rows with
+/// `FOR UPDATE SKIP LOCKED`, so several workers, or several backend instances,
+/// can share the table without double-sending. A row goes pending → submitted
+/// once sendTransaction accepts it, then confirmed or failed once
+/// getTransaction reports a result.
 pub struct ContractQueue {
-    tx: mpsc::UnboundedSender<QueueMessage>,
+    pool: DbPool,
+    wake: Arc<Notify>,
+    stopped: Arc<AtomicBool>,
+    result_tx: mpsc::UnboundedSender<QueueResult>,
     result_rx: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<QueueResult>>>,
+    oracle: OnceLock<OracleKey>,
+    /// Oracle calls are signed and sent one at a time so they don't race for a sequence number
+    oracle_send: tokio::sync::Mutex<()>,
 }
 
 impl ContractQueue {
-    pub fn new() -> Self {
-        let (tx, mut rx) = mpsc::unbounded_channel::<QueueMessage>();
+    /// Create a queue over the outbox table; call `spawn_workers` to start processing
+    pub fn new(pool: DbPool) -> Self {
         let (result_tx, result_rx) = mpsc::unbounded_channel::<QueueResult>();
 
-        // Clone tx for use in retry logic
-        let tx_clone = tx.clone();
+        Self {
+            pool,
+            wake: Arc::new(Notify::new()),
+            stopped: Arc::new(AtomicBool::new(false)),
+            result_tx,
+            result_rx: Arc::new(tokio::sync::Mutex::new(result_rx)),
+            oracle: OnceLock::new(),
+            oracle_send: tokio::sync::Mutex::new(()),
+        }
+    }
 
-        // Spawn worker task
-        tokio::spawn(async move {
-            info!("🚀 Contract queue worker started");
+    /// Sign oracle calls with `key`; only the first key set is used
+    pub fn set_oracle(&self, key: OracleKey) {
+        if self.oracle.set(key).is_err() {
+            warn!("⚠️  Oracle key already set, ignoring the new one");
+        }
+    }
 
-            while let Some(message) = rx.recv().await {
-                match message {
-                    QueueMessage::Submit(mut operation) => {
-                        info!("📥 Processing operation {} (priority: {:?})", operation.id, operation.priority);
+    /// The oracle key, if one is configured
+    pub fn oracle(&self) -> Option<&OracleKey> {
+        self.oracle.get()
+    }
 
-                        // Simulate contract submission (replace with actual logic)
-                        let result = Self::process_operation(&operation).await;
+    /// Start `count` workers draining due operations
+    pub fn spawn_workers(self: &Arc<Self>, count: usize) {
+        for worker in 0..count {
+            let queue = self.clone();
+            tokio::spawn(async move {
+                info!("🚀 Contract queue worker {} started", worker);
 
-                        match result {
-                            Ok(success_result) => {
-                                info!("✅ Operation {} completed successfully", operation.id);
-                                let _ = result_tx.send(QueueResult::Success {
-                                    operation_id: operation.id.clone(),
-                                    result: success_result,
-                                });
-                            }
-                            Err(error) if operation.can_retry() => {
-                                operation.increment_retry();
-                                warn!("⚠️  Operation {} failed (attempt {}/{}): {}",
-                                    operation.id, operation.retry_count, operation.max_retries, error);
-
-                                // Exponential backoff
-                                let delay = Duration::from_secs(2u64.pow(operation.retry_count));
-                                sleep(delay).await;
-
-                                // Retry
-                                let _ = result_tx.send(QueueResult::Retry {
-                                    operation_id: operation.id.clone(),
-                                    attempt: operation.retry_count,
-                                });
-
-                                // Re-queue for retry
-                                let _ = tx_clone.send(QueueMessage::Submit(operation));
-                            }
-                            Err(error) => {
-                                error!("❌ Operation {} failed permanently: {}", operation.id, error);
-                                let _ = result_tx.send(QueueResult::Failed {
-                                    operation_id: operation.id.clone(),
-                                    error,
-                                });
+                while !queue.stopped.load(Ordering::Relaxed) {
+                    match queue.process_due().await {
+                        Ok(0) => {
+                            // Idle until something is queued or a retry comes due
+                            tokio::select! {
+                                _ = queue.wake.notified() => {}
+                                _ = sleep(QUEUE_POLL_INTERVAL) => {}
                             }
                         }
-                    }
-                    QueueMessage::Status(operation_id) => {
-                        info!("📊 Status check for operation: {}", operation_id);
-                    }
-                    QueueMessage::Shutdown => {
-                        info!("🛑 Queue worker shutting down");
-                        break;
+                        Ok(_) => {}
+                        Err(error) => {
+                            error!("❌ Contract queue worker {} failed to claim operations: {}", worker, error);
+                            sleep(QUEUE_POLL_INTERVAL).await;
+                        }
                     }
                 }
-            }
-        });
 
-        Self {
-            tx,
-            result_rx: Arc::new(tokio::sync::Mutex::new(result_rx)),
+                info!("🛑 Contract queue worker {} shutting down", worker);
+            });
         }
     }
 
     /// Submit operation to queue
     pub async fn submit(&self, operation: ContractOperation) -> Result<String, String> {
-        let operation_id = operation.id.clone();
-
-        self.tx
-            .send(QueueMessage::Submit(operation))
+        ContractOperationRepository::enqueue(&self.pool, &NewContractOperation::from(&operation))
+            .await
             .map_err(|e| format!("Failed to queue operation: {}", e))?;
+        self.wake.notify_one();
+
+        info!("📤 Operation {} queued successfully", operation.id);
+        Ok(operation.id)
+    }
 
-        info!("📤 Operation {} queued successfully", operation_id);
-        Ok(operation_id)
+    /// Latest status of a submitted operation
+    pub async fn status(&self, operation_id: &str) -> Result<Option<TrackedTransaction>, String> {
+        ContractOperationRepository::find_by_id(&self.pool, operation_id)
+            .await
+            .map(|record| record.map(TrackedTransaction::from))
+            .map_err(|e| format!("Failed to read operation {}: {}", operation_id, e))
     }
 
     /// Get next result from queue
@@ -176,39 +246,391 @@ impl ContractQueue {
         rx.recv().await
     }
 
-    /// Shutdown the queue
+    /// Stop the workers once their current batch is done
     pub async fn shutdown(&self) -> Result<(), String> {
-        self.tx
-            .send(QueueMessage::Shutdown)
-            .map_err(|e| format!("Failed to shutdown queue: {}", e))
+        self.stopped.store(true, Ordering::Relaxed);
+        self.wake.notify_waiters();
+        Ok(())
+    }
+
+    /// Claim one batch of due operations and advance each, returning how many were claimed
+    pub async fn process_due(&self) -> Result<usize, sqlx::Error> {
+        let lease_until = Utc::now() + CLAIM_LEASE;
+        let claimed = ContractOperationRepository::claim_due(&self.pool, CLAIM_BATCH_SIZE, lease_until).await?;
+
+        for record in &claimed {
+            let outcome = match record.status.as_str() {
+                "submitted" => self.poll_operation(record).await,
+                _ => self.send_operation(record).await,
+            };
+            if let Err(error) = outcome {
+                // The claim lease brings the row back if this update never landed
+                error!("❌ Failed to record progress of operation {}: {}", record.id, error);
+            }
+        }
+
+        Ok(claimed.len())
+    }
+
+    /// Send the operation's signed transaction
+    async fn send_operation(&self, record: &ContractOperationRecord) -> Result<(), sqlx::Error> {
+        info!("📥 Processing operation {} (priority: {}, attempt {})",
+            record.id, record.priority, record.attempt_count);
+
+        let sent = match (record.signed_xdr.as_deref(), record.oracle_call.as_deref()) {
+            (Some(signed_xdr), _) => TransactionRpc::new(&record.rpc_url).send(signed_xdr).await,
+            (None, Some(call)) => {
+                let call = match serde_json::from_str::<OracleCall>(call) {
+                    Ok(call) => call,
+                    Err(error) => return self.finish(record, ContractTransactionStatus::Failed {
+                        hash: None,
+                        error: format!("Invalid oracle call: {}", error),
+                        contract_error: None,
+                    }).await,
+                };
+                let Some(key) = self.oracle.get().cloned() else {
+                    return self.finish(record, ContractTransactionStatus::Failed {
+                        hash: None,
+                        error: "No oracle key configured".to_string(),
+                        contract_error: None,
+                    }).await;
+                };
+                self.send_oracle_call(record, key, call).await
+            }
+            (None, None) => {
+                return self.finish(record, ContractTransactionStatus::Failed {
+                    hash: None,
+                    error: "No signed transaction to submit".to_string(),
+                    contract_error: None,
+                }).await;
+            }
+        };
+
+        match sent {
+            Ok(ContractTransactionStatus::Submitted { hash }) => {
+                info!("📤 Operation {} submitted as {}", record.id, hash);
+                ContractOperationRepository::mark_submitted(
+                    &self.pool,
+                    &record.id,
+                    &hash,
+                    Utc::now() + CONFIRMATION_POLL_INTERVAL,
+                ).await
+            }
+            Ok(status) => self.finish(record, status).await,
+            // attempt_count already includes this attempt
+            Err(error) if record.attempt_count <= record.max_retries => {
+                warn!("⚠️  Operation {} failed (attempt {}/{}): {}",
+                    record.id, record.attempt_count, record.max_retries + 1, error);
+
+                // Exponential backoff
+                let delay = Duration::from_secs(2u64.pow(record.attempt_count as u32));
+                ContractOperationRepository::reschedule(
+                    &self.pool,
+                    &record.id,
+                    Utc::now() + delay,
+                    Some(&error.to_string()),
+                ).await?;
+
+                let _ = self.result_tx.send(QueueResult::Retry {
+                    operation_id: record.id.clone(),
+                    attempt: record.attempt_count as u32,
+                });
+                Ok(())
+            }
+            Err(error) => {
+                self.finish(record, ContractTransactionStatus::Failed {
+                    hash: None,
+                    error: error.to_string(),
+                    contract_error: None,
+                }).await
+            }
+        }
     }
 
-    /// Process a single operation (placeholder - implement actual contract logic)
-    async fn process_operation(operation: &ContractOperation) -> Result<String, String> {
-        // TODO: Replace with actual Stellar contract submission logic
-        // For now, simulate processing time and success/failure
+    /// Sign an oracle call against the oracle account's current sequence number and send it
+    ///
+    /// A call rejected because the account's previous transaction hasn't landed yet,
+    /// or with a sequence number another instance already used, comes back as an Err
+    /// so it is retried and re-signed after the backoff.
+    async fn send_oracle_call(
+        &self,
+        record: &ContractOperationRecord,
+        key: OracleKey,
+        call: OracleCall,
+    ) -> crate::error::Result<ContractTransactionStatus> {
+        let _sending = self.oracle_send.lock().await;
+
+        let config = ContractConfig {
+            contract_id: record.contract_id.clone(),
+            network_passphrase: call.network_passphrase,
+            rpc_url: record.rpc_url.clone(),
+        };
+        let function = call.function;
 
-        sleep(Duration::from_millis(100)).await;
+        // The soroban client isn't Send, so drive it on a blocking thread
+        let signed_xdr = tokio::task::spawn_blocking(move || {
+            tokio::runtime::Handle::current().block_on(async move {
+                build_signed_xdr(&config, &key.secret_key, &function).await
+            })
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Oracle signing task failed: {}", e)))??;
 
-        // Simulate 90% success rate
-        if rand::random::<f32>() < 0.9 {
-            Ok(format!("Transaction submitted successfully for {}", operation.function_name))
-        } else {
-            Err(format!("Simulated error for {}", operation.function_name))
+        match TransactionRpc::new(&record.rpc_url).send(&signed_xdr).await? {
+            ContractTransactionStatus::Failed { error, .. } if error == "TxBadSeq" => {
+                Err(AppError::StellarRpc("Oracle account sequence moved on, re-signing".to_string()))
+            }
+            status => Ok(status),
         }
     }
-}
 
-impl Default for ContractQueue {
-    fn default() -> Self {
-        Self::new()
+    /// Check a submitted transaction once, giving up after CONFIRMATION_TIMEOUT
+    async fn poll_operation(&self, record: &ContractOperationRecord) -> Result<(), sqlx::Error> {
+        let hash = record.transaction_hash.clone().unwrap_or_default();
+
+        let last_error = match TransactionRpc::new(&record.rpc_url).poll(&hash).await {
+            Ok(Some(status)) => return self.finish(record, status).await,
+            Ok(None) => None,
+            Err(error) => Some(error.to_string()),
+        };
+
+        let submitted_at = record.submitted_at.unwrap_or(record.updated_at);
+        if Utc::now() > submitted_at + CONFIRMATION_TIMEOUT {
+            return self.finish(record, ContractTransactionStatus::Failed {
+                hash: Some(hash),
+                error: format!("Not confirmed within {}s", CONFIRMATION_TIMEOUT.as_secs()),
+                contract_error: None,
+            }).await;
+        }
+
+        ContractOperationRepository::reschedule(
+            &self.pool,
+            &record.id,
+            Utc::now() + CONFIRMATION_POLL_INTERVAL,
+            last_error.as_deref(),
+        ).await
+    }
+
+    /// Store a final status and report it on the results channel
+    async fn finish(&self, record: &ContractOperationRecord, status: ContractTransactionStatus) -> Result<(), sqlx::Error> {
+        let result = match &status {
+            ContractTransactionStatus::Confirmed { hash, ledger } => {
+                ContractOperationRepository::mark_confirmed(&self.pool, &record.id, hash, *ledger as i64).await?;
+                QueueResult::Success {
+                    operation_id: record.id.clone(),
+                    result: format!("{:?}", status),
+                }
+            }
+            ContractTransactionStatus::Failed { hash, error, contract_error } => {
+                error!("❌ Operation {} failed permanently: {}", record.id, error);
+                ContractOperationRepository::mark_failed(
+                    &self.pool,
+                    &record.id,
+                    hash.as_deref(),
+                    error,
+                    contract_error.map(|code| code as i32),
+                ).await?;
+                QueueResult::Failed {
+                    operation_id: record.id.clone(),
+                    error: error.clone(),
+                }
+            }
+            // Only final statuses are passed in
+            ContractTransactionStatus::Pending | ContractTransactionStatus::Submitted { .. } => return Ok(()),
+        };
+
+        let _ = self.result_tx.send(result);
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct QueueStats {
-    pub pending_operations: usize,
-    pub completed_operations: usize,
-    pub failed_operations: usize,
-    pub retry_operations: usize,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+    use std::sync::Mutex;
+
+    const CONTRACT: &str = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC";
+    const PLAYER: &str = "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ";
+
+    /// Serve a JSON-RPC endpoint answering each call with the next canned response
+    async fn mock_rpc(responses: Vec<Value>) -> String {
+        let responses = Arc::new(Mutex::new(responses.into_iter()));
+        let app = Router::new().route("/", post(move |Json(request): Json<Value>| {
+            let mut response = responses.lock().unwrap().next().expect("unexpected RPC call");
+            response["id"] = request["id"].clone();
+            async move { Json(response) }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/", addr)
+    }
+
+    fn result(result: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "result": result })
+    }
+
+    fn operation(rpc_url: &str, priority: OperationPriority) -> ContractOperation {
+        ContractOperation::new(
+            CONTRACT.to_string(),
+            rpc_url.to_string(),
+            "add_win".to_string(),
+            PLAYER.to_string(),
+            Some("AAAA".to_string()),
+        )
+        .with_priority(priority)
+    }
+
+    fn oracle_operation(rpc_url: &str, oracle_account: &str) -> ContractOperation {
+        ContractOperation::oracle(
+            CONTRACT.to_string(),
+            rpc_url.to_string(),
+            oracle_account.to_string(),
+            OracleCall {
+                network_passphrase: "Test SDF Network ; September 2015".to_string(),
+                function: LeaderboardFunction::RecordMatch {
+                    player: PLAYER.to_string(),
+                    opponent: None,
+                    session_id: "session-1".to_string(),
+                    score: 5,
+                    opponent_score: 2,
+                },
+            },
+        )
+    }
+
+    /// Make an operation due now instead of waiting out its backoff
+    async fn make_due(pool: &DbPool, id: &str) {
+        ContractOperationRepository::reschedule(pool, id, Utc::now(), None).await.unwrap();
+    }
+
+    async fn status(queue: &ContractQueue, id: &str) -> ContractTransactionStatus {
+        queue.status(id).await.unwrap().unwrap().status
+    }
+
+    #[sqlx::test]
+    async fn claims_by_priority_and_skips_locked_rows(pool: DbPool) {
+        let queue = ContractQueue::new(pool.clone());
+        let low = queue.submit(operation("http://unused/", OperationPriority::Low)).await.unwrap();
+        let critical = queue.submit(operation("http://unused/", OperationPriority::Critical)).await.unwrap();
+        let normal = queue.submit(operation("http://unused/", OperationPriority::Normal)).await.unwrap();
+
+        // Another worker holds the critical row
+        let mut other_worker = pool.begin().await.unwrap();
+        sqlx::query("SELECT id FROM contract_operations WHERE id = $1 FOR UPDATE")
+            .bind(&critical)
+            .execute(&mut *other_worker)
+            .await
+            .unwrap();
+
+        let lease_until = Utc::now() + CLAIM_LEASE;
+        let claimed = ContractOperationRepository::claim_due(&pool, 10, lease_until).await.unwrap();
+        let ids: Vec<_> = claimed.iter().map(|record| record.id.as_str()).collect();
+        assert_eq!(ids, vec![normal.as_str(), low.as_str()]);
+        assert!(claimed.iter().all(|record| record.attempt_count == 1));
+        other_worker.commit().await.unwrap();
+
+        // Claimed rows are leased; only the critical one is left
+        let claimed = ContractOperationRepository::claim_due(&pool, 10, lease_until).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, critical);
+        assert!(ContractOperationRepository::claim_due(&pool, 10, lease_until).await.unwrap().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn operation_is_sent_then_confirmed_by_a_later_worker(pool: DbPool) {
+        let rpc_url = mock_rpc(vec![
+            result(json!({ "status": "PENDING", "hash": "abc" })),
+            result(json!({ "status": "SUCCESS", "ledger": 1234 })),
+        ]).await;
+
+        let queue = ContractQueue::new(pool.clone());
+        let id = queue.submit(operation(&rpc_url, OperationPriority::Normal)).await.unwrap();
+        assert_eq!(status(&queue, &id).await, ContractTransactionStatus::Pending);
+
+        assert_eq!(queue.process_due().await.unwrap(), 1);
+        assert_eq!(status(&queue, &id).await, ContractTransactionStatus::Submitted { hash: "abc".to_string() });
+        // Not due for polling yet
+        assert_eq!(queue.process_due().await.unwrap(), 0);
+
+        // A fresh queue, as after a restart, picks the confirmation up from the table
+        let restarted = ContractQueue::new(pool.clone());
+        make_due(&pool, &id).await;
+        assert_eq!(restarted.process_due().await.unwrap(), 1);
+        assert_eq!(
+            status(&restarted, &id).await,
+            ContractTransactionStatus::Confirmed { hash: "abc".to_string(), ledger: 1234 }
+        );
+        assert!(matches!(restarted.next_result().await, Some(QueueResult::Success { .. })));
+    }
+
+    #[sqlx::test]
+    async fn rpc_failures_are_retried_until_attempts_run_out(pool: DbPool) {
+        let unavailable = json!({ "jsonrpc": "2.0", "error": { "code": -32603, "message": "unavailable" } });
+        let rpc_url = mock_rpc(vec![unavailable.clone(), unavailable]).await;
+
+        let queue = ContractQueue::new(pool.clone());
+        let id = queue
+            .submit(operation(&rpc_url, OperationPriority::High).with_max_retries(1))
+            .await
+            .unwrap();
+
+        assert_eq!(queue.process_due().await.unwrap(), 1);
+        let record = ContractOperationRepository::find_by_id(&pool, &id).await.unwrap().unwrap();
+        assert_eq!((record.status.as_str(), record.attempt_count), ("pending", 1));
+        assert!(record.last_error.unwrap().contains("unavailable"));
+        assert!(record.next_retry_at > Utc::now());
+        assert!(matches!(queue.next_result().await, Some(QueueResult::Retry { attempt: 1, .. })));
+
+        make_due(&pool, &id).await;
+        assert_eq!(queue.process_due().await.unwrap(), 1);
+        assert!(matches!(
+            status(&queue, &id).await,
+            ContractTransactionStatus::Failed { hash: None, contract_error: None, .. }
+        ));
+    }
+
+    #[sqlx::test]
+    async fn oracle_calls_fail_without_an_oracle_key(pool: DbPool) {
+        let queue = ContractQueue::new(pool.clone());
+        let id = queue.submit(oracle_operation("http://unused/", PLAYER)).await.unwrap();
+
+        let record = ContractOperationRepository::find_by_id(&pool, &id).await.unwrap().unwrap();
+        assert_eq!(record.function_name, "record_match");
+        assert!(record.signed_xdr.is_none());
+
+        assert_eq!(queue.process_due().await.unwrap(), 1);
+        assert_eq!(status(&queue, &id).await, ContractTransactionStatus::Failed {
+            hash: None,
+            error: "No oracle key configured".to_string(),
+            contract_error: None,
+        });
+    }
+
+    #[sqlx::test]
+    async fn oracle_calls_are_signed_again_on_each_attempt(pool: DbPool) {
+        // The oracle account lookup fails, so nothing gets signed or sent
+        let unavailable = json!({ "jsonrpc": "2.0", "error": { "code": -32603, "message": "unavailable" } });
+        let rpc_url = mock_rpc(vec![unavailable.clone(), unavailable]).await;
+
+        let secret = Keypair::random().unwrap().secret_key().unwrap();
+        let key = OracleKey::from_secret(&secret).unwrap();
+        let queue = ContractQueue::new(pool.clone());
+        queue.set_oracle(key.clone());
+        let id = queue.submit(oracle_operation(&rpc_url, key.public_key())).await.unwrap();
+
+        assert_eq!(queue.process_due().await.unwrap(), 1);
+        let record = ContractOperationRepository::find_by_id(&pool, &id).await.unwrap().unwrap();
+        assert_eq!((record.status.as_str(), record.attempt_count), ("pending", 1));
+        assert!(record.last_error.unwrap().contains("account"));
+
+        // The retry goes back to the account for a fresh sequence number
+        make_due(&pool, &id).await;
+        assert_eq!(queue.process_due().await.unwrap(), 1);
+        let record = ContractOperationRepository::find_by_id(&pool, &id).await.unwrap().unwrap();
+        assert_eq!((record.status.as_str(), record.attempt_count), ("pending", 2));
+        assert!(record.signed_xdr.is_none());
+    }
 }
@@ -6,7 +6,9 @@ use tracing::{info, warn};
 
 use super::pool::{StellarRpcPool, PoolConfig};
 use super::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
-use super::cache::ContractCache;
+use super::cache::{CacheConfig, ContractCache};
+use super::registry_config::RegistryConfig;
+use shared::dto::contract::ContractValue;
 
 /// Contract metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,9 +25,13 @@ pub struct ContractMetadata {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NetworkType {
+    #[serde(alias = "testnet")]
     Testnet,
+    #[serde(alias = "mainnet")]
     Mainnet,
+    #[serde(alias = "futurenet")]
     Futurenet,
+    #[serde(alias = "standalone")]
     Standalone,
 }
 
@@ -50,11 +56,45 @@ impl NetworkType {
 }
 
 /// Per-contract resources
+#[derive(Clone)]
 struct ContractResources {
     metadata: ContractMetadata,
+    pool_config: PoolConfig,
+    circuit_config: CircuitBreakerConfig,
     rpc_pool: Arc<StellarRpcPool>,
     circuit_breaker: Arc<CircuitBreaker>,
-    cache: Arc<ContractCache<Vec<u8>>>,
+    cache: Arc<ContractCache<ContractValue>>,
+}
+
+impl ContractResources {
+    fn new(metadata: ContractMetadata, pool_config: PoolConfig, circuit_config: CircuitBreakerConfig) -> Result<Self, String> {
+        // Create RPC pool
+        let rpc_pool = StellarRpcPool::new(metadata.rpc_url.clone(), pool_config.clone())
+            .map_err(|e| format!("Failed to create RPC pool: {}", e))?;
+
+        // Create circuit breaker
+        let circuit_breaker = CircuitBreaker::new(circuit_config.clone());
+
+        // Create bounded query cache
+        let cache = ContractCache::new(CacheConfig::default());
+
+        Ok(Self {
+            metadata,
+            pool_config,
+            circuit_config,
+            rpc_pool: Arc::new(rpc_pool),
+            circuit_breaker: Arc::new(circuit_breaker),
+            cache: Arc::new(cache),
+        })
+    }
+
+    /// Whether the existing pool and breaker can serve `metadata` as configured
+    fn same_connection(&self, metadata: &ContractMetadata, pool_config: &PoolConfig, circuit_config: &CircuitBreakerConfig) -> bool {
+        self.metadata.rpc_url == metadata.rpc_url
+            && self.metadata.network_passphrase == metadata.network_passphrase
+            && self.pool_config == *pool_config
+            && self.circuit_config == *circuit_config
+    }
 }
 
 /// Multi-contract registry for massive scale
@@ -78,47 +118,92 @@ impl ContractRegistry {
         }
     }
 
-    /// Register a new contract
+    /// Build a registry holding every configured contract
+    pub async fn from_config(config: &RegistryConfig) -> Result<Self, String> {
+        let registry = Self::new(None, None);
+        for contract in config.resolve() {
+            registry.register_with(contract.metadata, contract.pool_config, contract.circuit_config).await?;
+        }
+        Ok(registry)
+    }
+
+    /// Register a new contract with the registry's default settings
     pub async fn register(&self, metadata: ContractMetadata) -> Result<(), String> {
+        self.register_with(metadata, self.default_pool_config.clone(), self.default_circuit_config.clone()).await
+    }
+
+    /// Register a new contract; disabled contracts are kept but not handed out
+    pub async fn register_with(
+        &self,
+        metadata: ContractMetadata,
+        pool_config: PoolConfig,
+        circuit_config: CircuitBreakerConfig,
+    ) -> Result<(), String> {
+        info!("📝 Registering contract: {} ({})", metadata.name, metadata.contract_id);
         if !metadata.enabled {
-            warn!("⚠️  Contract {} is disabled, skipping registration", metadata.contract_id);
-            return Ok(());
+            warn!("⚠️  Contract {} is disabled", metadata.contract_id);
         }
 
-        info!("📝 Registering contract: {} ({})", metadata.name, metadata.contract_id);
+        let resources = ContractResources::new(metadata.clone(), pool_config, circuit_config)?;
 
-        // Create RPC pool
-        let rpc_pool = StellarRpcPool::new(
-            metadata.rpc_url.clone(),
-            self.default_pool_config.clone(),
-        )
-        .map_err(|e| format!("Failed to create RPC pool: {}", e))?;
+        let mut contracts = self.contracts.write().await;
+        contracts.insert(metadata.contract_id.clone(), resources);
 
-        // Create circuit breaker
-        let circuit_breaker = CircuitBreaker::new(self.default_circuit_config.clone());
+        info!("✅ Contract {} registered successfully", metadata.contract_id);
+        Ok(())
+    }
 
-        // Create cache (5 minute TTL)
-        let cache = ContractCache::new(std::time::Duration::from_secs(300));
+    /// Make the registry match `config`
+    ///
+    /// Contracts whose network and settings are unchanged keep their pool,
+    /// breaker and cache; changed ones get fresh resources, and contracts no
+    /// longer configured are dropped, including ones registered at runtime.
+    pub async fn reload(&self, config: &RegistryConfig) -> Result<(), String> {
+        let mut contracts = self.contracts.write().await;
 
-        let resources = ContractResources {
-            metadata: metadata.clone(),
-            rpc_pool: Arc::new(rpc_pool),
-            circuit_breaker: Arc::new(circuit_breaker),
-            cache: Arc::new(cache),
-        };
+        // Build the whole new map before touching the live one, so a failed
+        // reload leaves the running registry as it was
+        let mut reloaded = HashMap::new();
+        for contract in config.resolve() {
+            let id = contract.metadata.contract_id.clone();
+            let resources = match contracts.get(&id) {
+                Some(existing) if existing.same_connection(&contract.metadata, &contract.pool_config, &contract.circuit_config) => {
+                    ContractResources { metadata: contract.metadata, ..existing.clone() }
+                }
+                _ => ContractResources::new(contract.metadata, contract.pool_config, contract.circuit_config)?,
+            };
+            reloaded.insert(id, resources);
+        }
 
+        for removed in contracts.keys().filter(|id| !reloaded.contains_key(*id)) {
+            info!("🗑️  Contract {} is no longer configured", removed);
+        }
+        *contracts = reloaded;
+        Ok(())
+    }
+
+    /// Enable or disable a registered contract
+    pub async fn set_enabled(&self, contract_id: &str, enabled: bool) -> Result<ContractMetadata, String> {
         let mut contracts = self.contracts.write().await;
-        contracts.insert(metadata.contract_id.clone(), resources);
+        let resources = contracts
+            .get_mut(contract_id)
+            .ok_or_else(|| format!("Contract not found: {}", contract_id))?;
 
-        info!("✅ Contract {} registered successfully", metadata.contract_id);
-        Ok(())
+        resources.metadata.enabled = enabled;
+        info!("{} contract {}", if enabled { "✅ Enabled" } else { "⛔ Disabled" }, contract_id);
+        Ok(resources.metadata.clone())
+    }
+
+    /// Whether a contract is registered, enabled or not
+    pub async fn contains(&self, contract_id: &str) -> bool {
+        self.contracts.read().await.contains_key(contract_id)
     }
 
-    /// Get contract by ID
+    /// Get an enabled contract by ID
     pub async fn get(&self, contract_id: &str) -> Option<ContractHandle> {
         let contracts = self.contracts.read().await;
 
-        contracts.get(contract_id).map(|resources| ContractHandle {
+        contracts.get(contract_id).filter(|resources| resources.metadata.enabled).map(|resources| ContractHandle {
             metadata: resources.metadata.clone(),
             rpc_pool: resources.rpc_pool.clone(),
             circuit_breaker: resources.circuit_breaker.clone(),
@@ -170,10 +255,19 @@ pub struct ContractHandle {
     pub metadata: ContractMetadata,
     pub rpc_pool: Arc<StellarRpcPool>,
     pub circuit_breaker: Arc<CircuitBreaker>,
-    pub cache: Arc<ContractCache<Vec<u8>>>,
+    pub cache: Arc<ContractCache<ContractValue>>,
 }
 
 impl ContractHandle {
+    /// Client configuration for calls against this contract
+    pub fn contract_config(&self) -> super::client::ContractConfig {
+        super::client::ContractConfig {
+            contract_id: self.metadata.contract_id.clone(),
+            network_passphrase: self.metadata.network_passphrase.clone(),
+            rpc_url: self.metadata.rpc_url.clone(),
+        }
+    }
+
     /// Get a pooled RPC connection
     pub async fn get_rpc_connection(&self) -> Result<super::pool::PooledRpcConnection, String> {
         self.rpc_pool
@@ -202,29 +296,6 @@ pub struct RegistryStats {
     pub disabled_contracts: usize,
 }
 
-/// Create default contract registry with common contracts
-pub async fn create_default_registry() -> Result<Arc<ContractRegistry>, String> {
-    let registry = Arc::new(ContractRegistry::new(None, None));
-
-    // Register leaderboard contract
-    let leaderboard_metadata = ContractMetadata {
-        contract_id: std::env::var("CONTRACT_ID")
-            .unwrap_or_else(|_| "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC".to_string()),
-        name: "Stellar Heads Leaderboard".to_string(),
-        network: NetworkType::Testnet,
-        network_passphrase: NetworkType::Testnet.default_passphrase().to_string(),
-        rpc_url: std::env::var("SOROBAN_RPC_URL")
-            .unwrap_or_else(|_| NetworkType::Testnet.default_rpc_url().to_string()),
-        description: Some("Game leaderboard smart contract".to_string()),
-        version: Some("1.0.0".to_string()),
-        enabled: true,
-    };
-
-    registry.register(leaderboard_metadata).await?;
-
-    Ok(registry)
-}
-
 #[derive(Debug, Clone, Serialize)]
 pub struct ContractInfo {
     pub metadata: ContractMetadata,
@@ -232,3 +303,67 @@ pub struct ContractInfo {
     pub circuit_breaker_stats: super::circuit_breaker::CircuitBreakerStats,
     pub cache_stats: super::cache::CacheStats,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [networks.testnet]
+        network = "testnet"
+
+        [[contracts]]
+        contract_id = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC"
+        name = "Leaderboard"
+        network = "testnet"
+
+        [[contracts]]
+        contract_id = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        name = "Tournament"
+        network = "testnet"
+    "#;
+    const LEADERBOARD: &str = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC";
+    const TOURNAMENT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+
+    #[tokio::test]
+    async fn disabled_contracts_are_listed_but_not_handed_out() {
+        let registry = ContractRegistry::from_config(&RegistryConfig::parse(CONFIG).unwrap()).await.unwrap();
+
+        registry.set_enabled(TOURNAMENT, false).await.unwrap();
+        assert!(registry.get(TOURNAMENT).await.is_none());
+        assert!(registry.contains(TOURNAMENT).await);
+        assert_eq!(registry.stats().await.disabled_contracts, 1);
+
+        registry.set_enabled(TOURNAMENT, true).await.unwrap();
+        assert!(registry.get(TOURNAMENT).await.is_some());
+        assert!(registry.set_enabled("CUNKNOWN", true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn reload_keeps_unchanged_connections_and_drops_removed_contracts() {
+        let registry = ContractRegistry::from_config(&RegistryConfig::parse(CONFIG).unwrap()).await.unwrap();
+        let leaderboard_pool = registry.get(LEADERBOARD).await.unwrap().rpc_pool;
+
+        // Rename the leaderboard, drop the tournament
+        let reloaded = CONFIG
+            .replace("name = \"Leaderboard\"", "name = \"Season 2 leaderboard\"")
+            .split("[[contracts]]")
+            .take(2)
+            .collect::<Vec<_>>()
+            .join("[[contracts]]");
+        registry.reload(&RegistryConfig::parse(&reloaded).unwrap()).await.unwrap();
+
+        let leaderboard = registry.get(LEADERBOARD).await.unwrap();
+        assert_eq!(leaderboard.metadata.name, "Season 2 leaderboard");
+        assert!(Arc::ptr_eq(&leaderboard.rpc_pool, &leaderboard_pool));
+        assert!(!registry.contains(TOURNAMENT).await);
+
+        // A new RPC URL gets a new pool
+        let moved = reloaded.replace(
+            "[networks.testnet]",
+            "[networks.testnet]\n        rpc_url = \"http://localhost:8000/soroban/rpc\"",
+        );
+        registry.reload(&RegistryConfig::parse(&moved).unwrap()).await.unwrap();
+        assert!(!Arc::ptr_eq(&registry.get(LEADERBOARD).await.unwrap().rpc_pool, &leaderboard_pool));
+    }
+}
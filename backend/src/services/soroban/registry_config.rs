@@ -0,0 +1,354 @@
+// Contract registry configuration
+//
+// The registry is described by a TOML file: shared pool and circuit breaker
+// settings, named networks that may override them, and the contracts deployed
+// on each network. `${VAR}` references are replaced from the environment before
+// parsing, so RPC URLs and contract IDs can differ per deployment. Without a
+// file the registry falls back to the single contract named by CONTRACT_ID.
+
+use serde::Deserialize;
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+use super::circuit_breaker::CircuitBreakerConfig;
+use super::manager::ScalableContractManager;
+use super::pool::PoolConfig;
+use super::registry::{ContractMetadata, NetworkType};
+use crate::error::{AppError, Result};
+
+/// Contract the handlers use when CONTRACT_ID isn't set
+pub const DEFAULT_CONTRACT_ID: &str = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// Contract the handlers talk to; defaults to the first contract listed
+    pub default_contract: Option<String>,
+    #[serde(default)]
+    pub pool: PoolSettings,
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerSettings,
+    #[serde(default)]
+    pub networks: HashMap<String, NetworkConfig>,
+    #[serde(default)]
+    pub contracts: Vec<ContractEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub network: NetworkType,
+    /// Defaults to the network's public RPC
+    pub rpc_url: Option<String>,
+    /// Defaults to the network's passphrase
+    pub passphrase: Option<String>,
+    #[serde(default)]
+    pub pool: PoolSettings,
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerSettings,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractEntry {
+    pub contract_id: String,
+    pub name: String,
+    /// Key into `networks`
+    pub network: String,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Pool overrides; unset fields keep the value from the level above
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolSettings {
+    pub max_connections: Option<usize>,
+    pub idle_timeout_secs: Option<u64>,
+    pub connection_timeout_secs: Option<u64>,
+    pub max_retries: Option<u32>,
+}
+
+impl PoolSettings {
+    fn apply(&self, base: PoolConfig) -> PoolConfig {
+        PoolConfig {
+            max_connections: self.max_connections.unwrap_or(base.max_connections),
+            idle_timeout: self.idle_timeout_secs.map(Duration::from_secs).unwrap_or(base.idle_timeout),
+            connection_timeout: self.connection_timeout_secs.map(Duration::from_secs).unwrap_or(base.connection_timeout),
+            max_retries: self.max_retries.unwrap_or(base.max_retries),
+        }
+    }
+}
+
+/// Circuit breaker overrides; unset fields keep the value from the level above
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreakerSettings {
+    pub failure_threshold: Option<u32>,
+    pub timeout_secs: Option<u64>,
+    pub success_threshold: Option<u32>,
+}
+
+impl CircuitBreakerSettings {
+    fn apply(&self, base: CircuitBreakerConfig) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: self.failure_threshold.unwrap_or(base.failure_threshold),
+            timeout: self.timeout_secs.map(Duration::from_secs).unwrap_or(base.timeout),
+            success_threshold: self.success_threshold.unwrap_or(base.success_threshold),
+        }
+    }
+}
+
+/// A contract with its network and settings worked out
+#[derive(Debug, Clone)]
+pub struct ResolvedContract {
+    pub metadata: ContractMetadata,
+    pub pool_config: PoolConfig,
+    pub circuit_config: CircuitBreakerConfig,
+}
+
+impl RegistryConfig {
+    /// Read a config file, substituting `${VAR}` from the environment
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+        let config = Self::parse(&expand_env(&raw)?)
+            .map_err(|e| AppError::Config(format!("{}: {}", path.display(), e)))?;
+
+        info!("📄 Loaded {} contracts on {} networks from {}",
+            config.contracts.len(), config.networks.len(), path.display());
+        Ok(config)
+    }
+
+    pub fn parse(toml: &str) -> Result<Self> {
+        let config: Self = toml::from_str(toml)
+            .map_err(|e| AppError::Config(format!("Invalid contract registry config: {}", e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The single testnet contract from CONTRACT_ID and SOROBAN_RPC_URL
+    pub fn from_env() -> Self {
+        let mut networks = HashMap::new();
+        networks.insert("testnet".to_string(), NetworkConfig {
+            network: NetworkType::Testnet,
+            rpc_url: std::env::var("SOROBAN_RPC_URL").ok().filter(|url| !url.is_empty()),
+            passphrase: None,
+            pool: PoolSettings::default(),
+            circuit_breaker: CircuitBreakerSettings::default(),
+        });
+
+        Self {
+            default_contract: None,
+            pool: PoolSettings::default(),
+            circuit_breaker: CircuitBreakerSettings::default(),
+            networks,
+            contracts: vec![ContractEntry {
+                contract_id: std::env::var("CONTRACT_ID")
+                    .unwrap_or_else(|_| DEFAULT_CONTRACT_ID.to_string()),
+                name: "Stellar Heads Leaderboard".to_string(),
+                network: "testnet".to_string(),
+                description: Some("Game leaderboard smart contract".to_string()),
+                version: Some("1.0.0".to_string()),
+                enabled: true,
+            }],
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.contracts.is_empty() {
+            return Err(AppError::Config("At least one contract must be configured".to_string()));
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for contract in &self.contracts {
+            if !contract.contract_id.starts_with('C') || contract.contract_id.len() != 56 {
+                return Err(AppError::Config(format!("Invalid contract ID: {}", contract.contract_id)));
+            }
+            if !seen.insert(contract.contract_id.as_str()) {
+                return Err(AppError::Config(format!("Contract {} is listed twice", contract.contract_id)));
+            }
+            if !self.networks.contains_key(&contract.network) {
+                return Err(AppError::Config(format!(
+                    "Contract {} uses unknown network '{}'",
+                    contract.contract_id, contract.network
+                )));
+            }
+        }
+
+        if let Some(default) = &self.default_contract {
+            if !seen.contains(default.as_str()) {
+                return Err(AppError::Config(format!("Default contract {} is not configured", default)));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn default_contract_id(&self) -> String {
+        self.default_contract
+            .clone()
+            .unwrap_or_else(|| self.contracts[0].contract_id.clone())
+    }
+
+    /// Every contract with network defaults and setting overrides applied
+    pub fn resolve(&self) -> Vec<ResolvedContract> {
+        let pool_config = self.pool.apply(PoolConfig::default());
+        let circuit_config = self.circuit_breaker.apply(CircuitBreakerConfig::default());
+
+        self.contracts
+            .iter()
+            .map(|contract| {
+                let network = &self.networks[&contract.network];
+                ResolvedContract {
+                    metadata: ContractMetadata {
+                        contract_id: contract.contract_id.clone(),
+                        name: contract.name.clone(),
+                        network: network.network,
+                        network_passphrase: network.passphrase.clone()
+                            .unwrap_or_else(|| network.network.default_passphrase().to_string()),
+                        rpc_url: network.rpc_url.clone()
+                            .unwrap_or_else(|| network.network.default_rpc_url().to_string()),
+                        description: contract.description.clone(),
+                        version: contract.version.clone(),
+                        enabled: contract.enabled,
+                    },
+                    pool_config: network.pool.apply(pool_config.clone()),
+                    circuit_config: network.circuit_breaker.apply(circuit_config.clone()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Replace `${VAR}` with the variable's value; unset variables are an error
+fn expand_env(raw: &str) -> Result<String> {
+    let mut expanded = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let end = rest[start..].find('}')
+            .ok_or_else(|| AppError::Config("Unterminated ${ in contract registry config".to_string()))?;
+        let name = &rest[start + 2..start + end];
+        let value = std::env::var(name)
+            .map_err(|_| AppError::Config(format!("Contract registry config references unset variable {}", name)))?;
+        expanded.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Reload the registry from `path` whenever the process receives SIGHUP
+/// A file that fails to load is logged and the running registry kept
+#[cfg(unix)]
+pub fn spawn_reload_on_sighup(manager: Arc<ScalableContractManager>, path: String) -> tokio::task::JoinHandle<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(error) => {
+                warn!("❌ Can't listen for SIGHUP, contract registry reload disabled: {}", error);
+                return;
+            }
+        };
+        info!("🔁 Reloading contract registry from {} on SIGHUP", path);
+
+        while hangups.recv().await.is_some() {
+            match RegistryConfig::load(&path) {
+                Ok(config) => {
+                    manager.reload(&config).await;
+                    info!("✅ Contract registry reloaded from {}", path);
+                }
+                Err(error) => warn!("❌ Contract registry reload failed, keeping current contracts: {}", error),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEADERBOARD: &str = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC";
+    const MAINNET_LEADERBOARD: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+
+    const CONFIG: &str = r#"
+        default_contract = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+
+        [pool]
+        max_connections = 20
+
+        [circuit_breaker]
+        failure_threshold = 3
+
+        [networks.testnet]
+        network = "testnet"
+
+        [networks.mainnet]
+        network = "mainnet"
+        rpc_url = "https://rpc.example.org"
+        pool = { max_connections = 100 }
+
+        [[contracts]]
+        contract_id = "CC25DOXDMJ3OMDKE4ZETPY34734VQABAYAXSPKFXJ7I2STLCFV2VT7FC"
+        name = "Testnet leaderboard"
+        network = "testnet"
+
+        [[contracts]]
+        contract_id = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        name = "Mainnet leaderboard"
+        network = "mainnet"
+        enabled = false
+    "#;
+
+    #[test]
+    fn resolves_networks_and_layered_settings() {
+        let config = RegistryConfig::parse(CONFIG).unwrap();
+        assert_eq!(config.default_contract_id(), MAINNET_LEADERBOARD);
+
+        let resolved = config.resolve();
+        let testnet = &resolved[0];
+        assert_eq!(testnet.metadata.contract_id, LEADERBOARD);
+        assert_eq!(testnet.metadata.rpc_url, NetworkType::Testnet.default_rpc_url());
+        assert_eq!(testnet.metadata.network_passphrase, NetworkType::Testnet.default_passphrase());
+        assert_eq!(testnet.pool_config.max_connections, 20);
+        assert_eq!(testnet.circuit_config.failure_threshold, 3);
+
+        let mainnet = &resolved[1];
+        assert_eq!(mainnet.metadata.network, NetworkType::Mainnet);
+        assert_eq!(mainnet.metadata.rpc_url, "https://rpc.example.org");
+        assert!(!mainnet.metadata.enabled);
+        assert_eq!(mainnet.pool_config.max_connections, 100);
+        assert_eq!(mainnet.circuit_config.failure_threshold, 3);
+    }
+
+    #[test]
+    fn rejects_inconsistent_configs() {
+        let unknown_network = CONFIG.replace("network = \"mainnet\"\n        enabled", "network = \"futurenet\"\n        enabled");
+        assert!(RegistryConfig::parse(&unknown_network).is_err());
+
+        let duplicate = CONFIG.replace(MAINNET_LEADERBOARD, LEADERBOARD);
+        assert!(RegistryConfig::parse(&duplicate).is_err());
+
+        assert!(RegistryConfig::parse(&CONFIG.replace("max_connections = 20", "max_conections = 20")).is_err());
+    }
+
+    #[test]
+    fn substitutes_environment_variables() {
+        std::env::set_var("REGISTRY_CONFIG_TEST_RPC", "http://localhost:8000/soroban/rpc");
+        let expanded = expand_env("rpc_url = \"${REGISTRY_CONFIG_TEST_RPC}\"").unwrap();
+        assert_eq!(expanded, "rpc_url = \"http://localhost:8000/soroban/rpc\"");
+
+        assert!(expand_env("rpc_url = \"${REGISTRY_CONFIG_TEST_UNSET}\"").is_err());
+    }
+}
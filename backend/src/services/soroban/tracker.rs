@@ -0,0 +1,260 @@
+// Status tracking for submitted transactions
+//
+// Signed envelopes are sent to sendTransaction exactly as the wallet produced
+// them: round-tripping through the SDK's Transaction type drops the Soroban
+// footprint and invalidates the signature. The contract queue then polls
+// getTransaction until the network reports a final result.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use shared::dto::contract::{contract_error_name, ContractTransactionStatus};
+use soroban_client::xdr::{
+    DiagnosticEvent, ContractEventBody, Limits, OperationResult, OperationResultTr, ReadXdr,
+    ScError, ScVal, TransactionResult, TransactionResultResult,
+};
+use std::time::Duration;
+
+use crate::database::models::ContractOperationRecord;
+use crate::error::{AppError, Result};
+
+/// Delay between getTransaction polls
+pub const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long to wait for a submitted transaction to land in a ledger
+pub const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Latest known status of one queued operation
+#[derive(Debug, Clone, Serialize)]
+pub struct TrackedTransaction {
+    pub operation_id: String,
+    pub function_name: String,
+    pub status: ContractTransactionStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ContractOperationRecord> for TrackedTransaction {
+    fn from(record: ContractOperationRecord) -> Self {
+        let status = match (record.status.as_str(), record.transaction_hash) {
+            ("submitted", Some(hash)) => ContractTransactionStatus::Submitted { hash },
+            ("confirmed", Some(hash)) => ContractTransactionStatus::Confirmed {
+                hash,
+                ledger: record.ledger.unwrap_or_default() as u32,
+            },
+            ("failed", hash) => ContractTransactionStatus::Failed {
+                hash,
+                error: record.last_error.unwrap_or_else(|| "Transaction failed".to_string()),
+                contract_error: record.contract_error.map(|code| code as u32),
+            },
+            _ => ContractTransactionStatus::Pending,
+        };
+
+        Self {
+            operation_id: record.id,
+            function_name: record.function_name,
+            status,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+/// sendTransaction/getTransaction over plain JSON-RPC
+pub struct TransactionRpc {
+    client: reqwest::Client,
+    rpc_url: String,
+}
+
+impl TransactionRpc {
+    pub fn new(rpc_url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            rpc_url: rpc_url.to_string(),
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = self.client
+            .post(&self.rpc_url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await
+            .map_err(|e| AppError::StellarRpc(format!("{} request failed: {}", method, e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::StellarRpc(format!("Invalid {} response: {}", method, e)))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(AppError::StellarRpc(format!("{} returned an error: {}", method, error)));
+        }
+        response.get("result").cloned()
+            .ok_or_else(|| AppError::StellarRpc(format!("{} returned no result", method)))
+    }
+
+    /// Send a signed envelope
+    /// Returns Submitted, or Failed if the network rejected it outright;
+    /// an Err means the send itself should be retried
+    pub async fn send(&self, signed_xdr: &str) -> Result<ContractTransactionStatus> {
+        let result = self.call("sendTransaction", json!({ "transaction": signed_xdr })).await?;
+        let hash = string_field(&result, "hash").unwrap_or_default();
+
+        match string_field(&result, "status").as_deref() {
+            Some("PENDING") | Some("DUPLICATE") => Ok(ContractTransactionStatus::Submitted { hash }),
+            Some("ERROR") => Ok(failure(
+                Some(hash),
+                string_field(&result, "errorResultXdr").as_deref(),
+                &diagnostic_events(&result),
+            )),
+            other => Err(AppError::StellarRpc(format!("sendTransaction status {:?}, try again later", other))),
+        }
+    }
+
+    /// Check a submitted transaction once; None while it isn't in a ledger yet
+    pub async fn poll(&self, hash: &str) -> Result<Option<ContractTransactionStatus>> {
+        let result = self.call("getTransaction", json!({ "hash": hash })).await?;
+
+        match string_field(&result, "status").as_deref() {
+            Some("SUCCESS") => Ok(Some(ContractTransactionStatus::Confirmed {
+                hash: hash.to_string(),
+                ledger: result.get("ledger").and_then(Value::as_u64).unwrap_or_default() as u32,
+            })),
+            Some("FAILED") => Ok(Some(failure(
+                Some(hash.to_string()),
+                string_field(&result, "resultXdr").as_deref(),
+                &diagnostic_events(&result),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+fn string_field(value: &Value, name: &str) -> Option<String> {
+    value.get(name).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Diagnostic events from either the legacy top-level field or the newer `events` object
+fn diagnostic_events(result: &Value) -> Vec<DiagnosticEvent> {
+    result.get("diagnosticEventsXdr")
+        .or_else(|| result.pointer("/events/diagnosticEventsXdr"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|event| DiagnosticEvent::from_xdr_base64(event, Limits::none()).ok())
+        .collect()
+}
+
+/// Build a Failed status, naming the contract error when the contract raised one
+fn failure(hash: Option<String>, result_xdr: Option<&str>, events: &[DiagnosticEvent]) -> ContractTransactionStatus {
+    let contract_error = events.iter().find_map(|event| {
+        let ContractEventBody::V0(body) = &event.event.body;
+        body.topics.iter().chain([&body.data]).find_map(|value| match value {
+            ScVal::Error(ScError::Contract(code)) => Some(*code),
+            _ => None,
+        })
+    });
+
+    let error = match contract_error {
+        Some(code) => format!(
+            "Contract error #{} ({})",
+            code,
+            contract_error_name(code).unwrap_or("unknown")
+        ),
+        None => result_xdr
+            .and_then(|xdr| TransactionResult::from_xdr_base64(xdr, Limits::none()).ok())
+            .map(|result| describe_result(&result))
+            .unwrap_or_else(|| "Transaction failed".to_string()),
+    };
+
+    ContractTransactionStatus::Failed { hash, error, contract_error }
+}
+
+fn describe_result(result: &TransactionResult) -> String {
+    match &result.result {
+        TransactionResultResult::TxFailed(operations) => operations
+            .iter()
+            .map(|operation| match operation {
+                OperationResult::OpInner(OperationResultTr::InvokeHostFunction(invoke)) => {
+                    format!("InvokeHostFunction {}", invoke.name())
+                }
+                other => other.name().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.name().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Json, Router};
+    use soroban_client::xdr::{
+        ContractEvent, ContractEventType, ContractEventV0, ExtensionPoint, ScSymbol, WriteXdr,
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// Serve a JSON-RPC endpoint answering each call with the next canned result
+    async fn mock_rpc(results: Vec<Value>) -> String {
+        let results = Arc::new(Mutex::new(results.into_iter()));
+        let app = Router::new().route("/", post(move |Json(request): Json<Value>| {
+            let result = results.lock().unwrap().next().expect("unexpected RPC call");
+            async move { Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })) }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/", addr)
+    }
+
+    fn contract_error_event(code: u32) -> String {
+        DiagnosticEvent {
+            in_successful_contract_call: false,
+            event: ContractEvent {
+                ext: ExtensionPoint::V0,
+                contract_id: None,
+                type_: ContractEventType::Diagnostic,
+                body: ContractEventBody::V0(ContractEventV0 {
+                    topics: vec![
+                        ScVal::Symbol(ScSymbol("error".try_into().unwrap())),
+                        ScVal::Error(ScError::Contract(code)),
+                    ].try_into().unwrap(),
+                    data: ScVal::Void,
+                }),
+            },
+        }
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sends_and_polls_until_final() {
+        let rpc = TransactionRpc::new(&mock_rpc(vec![
+            json!({ "status": "PENDING", "hash": "abc" }),
+            json!({ "status": "NOT_FOUND" }),
+            json!({ "status": "SUCCESS", "ledger": 1234 }),
+        ]).await);
+
+        let submitted = rpc.send("AAAA").await.unwrap();
+        assert_eq!(submitted, ContractTransactionStatus::Submitted { hash: "abc".to_string() });
+
+        assert_eq!(rpc.poll("abc").await.unwrap(), None);
+        assert_eq!(
+            rpc.poll("abc").await.unwrap(),
+            Some(ContractTransactionStatus::Confirmed { hash: "abc".to_string(), ledger: 1234 })
+        );
+    }
+
+    #[tokio::test]
+    async fn decodes_contract_error_from_failed_transaction() {
+        let rpc = TransactionRpc::new(&mock_rpc(vec![
+            json!({ "status": "FAILED", "diagnosticEventsXdr": [contract_error_event(2)] }),
+        ]).await);
+
+        let status = rpc.poll("abc").await.unwrap().unwrap();
+        assert_eq!(status, ContractTransactionStatus::Failed {
+            hash: Some("abc".to_string()),
+            error: "Contract error #2 (NotJoined)".to_string(),
+            contract_error: Some(2),
+        });
+    }
+}
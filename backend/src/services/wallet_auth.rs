@@ -0,0 +1,437 @@
+// Wallet-signature authentication, modelled on SEP-10
+//
+// The server hands out a challenge transaction: sequence number 0 so it can
+// never be submitted, a short time window, and a manage_data operation sourced
+// from the player's account carrying a random nonce. The server signs it, the
+// wallet countersigns it, and a challenge carrying both valid signatures is
+// exchanged for a short-lived JWT naming the account.
+//
+// Only the account's master key is accepted; signers added to the account on
+// chain aren't consulted.
+//
+// The same secret also derives the per-match keys the game signs its result
+// transcripts with, so they never need to be stored.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use soroban_client::xdr::{
+    DataValue, DecoratedSignature, Limits, ManageDataOp, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, ReadXdr, SequenceNumber, SignatureHint, String64, TimeBounds,
+    TimePoint, Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256,
+    WriteXdr,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use stellar_strkey::ed25519::{PrivateKey as Ed25519PrivateKey, PublicKey as Ed25519PublicKey};
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use shared::dto::auth::{AuthChallengeResponse, AuthTokenResponse};
+
+/// How long a wallet has to sign a challenge
+const CHALLENGE_WINDOW: Duration = Duration::from_secs(300);
+
+/// Random bytes in a challenge nonce; base64 makes them the 64 bytes SEP-10 expects
+const NONCE_BYTES: usize = 48;
+
+/// Claims carried by a session token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletClaims {
+    /// The authenticated account (G...)
+    pub sub: String,
+    pub iss: String,
+    pub iat: u64,
+    pub exp: u64,
+    pub jti: String,
+}
+
+/// Issues and verifies wallet challenges, and the session tokens they're exchanged for
+pub struct WalletAuth {
+    signing_key: SigningKey,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    network_passphrase: String,
+    home_domain: String,
+    session_ttl: Duration,
+    /// Root of the per-match transcript keys, kept apart from the JWT key itself
+    transcript_secret: [u8; 32],
+    /// Nonces already exchanged for a token, with the time their challenge expires
+    used_nonces: Mutex<HashMap<String, u64>>,
+}
+
+impl WalletAuth {
+    pub fn new(
+        signing_key: SigningKey,
+        jwt_secret: &[u8],
+        network_passphrase: String,
+        home_domain: String,
+        session_ttl: Duration,
+    ) -> Self {
+        Self {
+            signing_key,
+            encoding_key: EncodingKey::from_secret(jwt_secret),
+            decoding_key: DecodingKey::from_secret(jwt_secret),
+            network_passphrase,
+            home_domain,
+            session_ttl,
+            transcript_secret: hmac_sha256(jwt_secret, &[b"match transcripts"]),
+            used_nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build from AUTH_* settings, generating whatever keys aren't configured
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let signing_key = match &config.auth_signing_key {
+            Some(secret) => {
+                let seed = Ed25519PrivateKey::from_string(secret)
+                    .map_err(|_| AppError::Config("AUTH_SIGNING_KEY must be a Stellar secret key (S...)".to_string()))?;
+                SigningKey::from_bytes(&seed.0)
+            }
+            None => {
+                info!("AUTH_SIGNING_KEY not set, signing challenges with a key generated for this run");
+                SigningKey::from_bytes(&random_bytes())
+            }
+        };
+
+        let jwt_secret = match &config.auth_jwt_secret {
+            Some(secret) => secret.as_bytes().to_vec(),
+            None => {
+                warn!("⚠️  AUTH_JWT_SECRET not set, wallet sessions won't survive a restart");
+                random_bytes::<32>().to_vec()
+            }
+        };
+
+        Ok(Self::new(
+            signing_key,
+            &jwt_secret,
+            config.auth_network_passphrase.clone(),
+            config.auth_home_domain.clone(),
+            Duration::from_secs(config.auth_session_ttl_secs),
+        ))
+    }
+
+    /// The server's account, which sources and signs every challenge
+    pub fn server_account(&self) -> String {
+        Ed25519PublicKey(self.signing_key.verifying_key().to_bytes()).to_string()
+    }
+
+    /// A fresh challenge transaction for `account`, signed by the server
+    pub fn challenge(&self, account: &str) -> Result<AuthChallengeResponse> {
+        let client_key = parse_account(account)?;
+        let now = unix_now();
+
+        let nonce = BASE64.encode(random_bytes::<NONCE_BYTES>());
+        let operation = Operation {
+            source_account: Some(MuxedAccount::Ed25519(Uint256(client_key))),
+            body: OperationBody::ManageData(ManageDataOp {
+                data_name: String64(self.auth_data_name().try_into().map_err(xdr_error)?),
+                data_value: Some(DataValue(nonce.into_bytes().try_into().map_err(xdr_error)?)),
+            }),
+        };
+
+        let transaction = Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(self.signing_key.verifying_key().to_bytes())),
+            fee: 100,
+            seq_num: SequenceNumber(0),
+            cond: Preconditions::Time(TimeBounds {
+                min_time: TimePoint(now),
+                max_time: TimePoint(now + CHALLENGE_WINDOW.as_secs()),
+            }),
+            memo: Memo::None,
+            operations: vec![operation].try_into().map_err(xdr_error)?,
+            ext: TransactionExt::V0,
+        };
+
+        let hash = self.transaction_hash(&transaction)?;
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: transaction,
+            signatures: vec![decorated_signature(&self.signing_key, &hash)?].try_into().map_err(xdr_error)?,
+        });
+
+        Ok(AuthChallengeResponse {
+            transaction: envelope.to_xdr_base64(Limits::none()).map_err(xdr_error)?,
+            network_passphrase: self.network_passphrase.clone(),
+        })
+    }
+
+    /// Check a challenge countersigned by the wallet and issue a session token for its account
+    pub fn verify_challenge(&self, signed_xdr: &str) -> Result<AuthTokenResponse> {
+        let envelope = TransactionEnvelope::from_xdr_base64(signed_xdr, Limits::none())
+            .map_err(|e| AppError::XdrDecoding(format!("Failed to decode challenge: {:?}", e)))?;
+        let TransactionEnvelope::Tx(envelope) = envelope else {
+            return Err(invalid("challenge must be a v1 transaction envelope"));
+        };
+        let transaction = &envelope.tx;
+
+        let server_key = self.signing_key.verifying_key();
+        if transaction.source_account != MuxedAccount::Ed25519(Uint256(server_key.to_bytes())) {
+            return Err(invalid("challenge wasn't issued by this server"));
+        }
+        if transaction.seq_num.0 != 0 {
+            return Err(invalid("challenge sequence number must be 0"));
+        }
+
+        let Preconditions::Time(bounds) = &transaction.cond else {
+            return Err(invalid("challenge has no time bounds"));
+        };
+        let now = unix_now();
+        if now < bounds.min_time.0 || now > bounds.max_time.0 {
+            return Err(invalid("challenge has expired"));
+        }
+
+        let Some(first) = transaction.operations.first() else {
+            return Err(invalid("challenge has no operations"));
+        };
+        let (Some(MuxedAccount::Ed25519(Uint256(client_key))), OperationBody::ManageData(data)) =
+            (&first.source_account, &first.body)
+        else {
+            return Err(invalid("challenge must start with a manage_data operation from the account"));
+        };
+        if data.data_name.0.as_slice() != self.auth_data_name().as_bytes() {
+            return Err(invalid("challenge is for another home domain"));
+        }
+        let nonce = match &data.data_value {
+            Some(value) if value.0.len() == 64 => String::from_utf8_lossy(value.0.as_slice()).into_owned(),
+            _ => return Err(invalid("challenge nonce must be 64 bytes")),
+        };
+
+        // Further operations may only come from the server
+        let server_source = Some(MuxedAccount::Ed25519(Uint256(server_key.to_bytes())));
+        if transaction.operations.iter().skip(1).any(|op| op.source_account != server_source) {
+            return Err(invalid("challenge has operations from other accounts"));
+        }
+
+        let client_key = VerifyingKey::from_bytes(client_key)
+            .map_err(|_| invalid("challenge account isn't a valid ed25519 key"))?;
+        let hash = self.transaction_hash(transaction)?;
+        let signatures = envelope.signatures.as_slice();
+        if signatures.len() != 2 {
+            return Err(invalid("challenge must carry exactly the server's and the account's signatures"));
+        }
+        if !signed_by(signatures, &server_key, &hash) {
+            return Err(invalid("challenge is missing the server's signature"));
+        }
+        if !signed_by(signatures, &client_key, &hash) {
+            return Err(AppError::Unauthorized("Challenge isn't signed by the account".to_string()));
+        }
+
+        // Each challenge can be exchanged once
+        {
+            let mut used = self.used_nonces.lock().unwrap_or_else(|e| e.into_inner());
+            used.retain(|_, expires| *expires >= now);
+            if used.insert(nonce, bounds.max_time.0).is_some() {
+                return Err(AppError::Unauthorized("Challenge has already been used".to_string()));
+            }
+        }
+
+        let account = Ed25519PublicKey(client_key.to_bytes()).to_string();
+        self.issue_token(account)
+    }
+
+    /// Claims of a valid, unexpired session token
+    pub fn authenticate(&self, token: &str) -> Result<WalletClaims> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[&self.home_domain]);
+        validation.leeway = 0;
+
+        jsonwebtoken::decode::<WalletClaims>(token, &self.decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid session token: {}", e)))
+    }
+
+    fn issue_token(&self, account: String) -> Result<AuthTokenResponse> {
+        let issued_at = unix_now();
+        let claims = WalletClaims {
+            sub: account.clone(),
+            iss: self.home_domain.clone(),
+            iat: issued_at,
+            exp: issued_at + self.session_ttl.as_secs(),
+            jti: uuid::Uuid::new_v4().to_string(),
+        };
+
+        let token = jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+            .map_err(|e| AppError::Internal(format!("Failed to issue session token: {}", e)))?;
+        let expires_at = chrono::DateTime::from_timestamp(claims.exp as i64, 0)
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_default();
+
+        info!("🔐 Issued wallet session for {}", account);
+        Ok(AuthTokenResponse {
+            token,
+            wallet_address: account,
+            expires_at,
+        })
+    }
+
+    /// Key the game signs one match's transcript with, bound to the player and session
+    pub fn session_key(&self, account: &str, session_id: &str) -> [u8; 32] {
+        hmac_sha256(&self.transcript_secret, &[account.as_bytes(), &[0], session_id.as_bytes()])
+    }
+
+    /// manage_data key naming the domain the challenge is for
+    fn auth_data_name(&self) -> String {
+        format!("{} auth", self.home_domain)
+    }
+
+    fn transaction_hash(&self, transaction: &Transaction) -> Result<[u8; 32]> {
+        let network_id: [u8; 32] = Sha256::digest(self.network_passphrase.as_bytes()).into();
+        transaction.hash(network_id).map_err(xdr_error)
+    }
+}
+
+/// Sign a transaction hash, tagged with the signer's hint
+pub fn decorated_signature(key: &SigningKey, hash: &[u8; 32]) -> Result<DecoratedSignature> {
+    let public_key = key.verifying_key().to_bytes();
+    Ok(DecoratedSignature {
+        hint: SignatureHint(public_key[28..].try_into().expect("4 byte hint")),
+        signature: soroban_client::xdr::Signature(key.sign(hash).to_bytes().to_vec().try_into().map_err(xdr_error)?),
+    })
+}
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes any key length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn signed_by(signatures: &[DecoratedSignature], key: &VerifyingKey, hash: &[u8; 32]) -> bool {
+    let hint = &key.as_bytes()[28..];
+    signatures
+        .iter()
+        .filter(|signature| signature.hint.0 == hint)
+        .filter_map(|signature| Signature::from_slice(signature.signature.0.as_slice()).ok())
+        .any(|signature| key.verify(hash, &signature).is_ok())
+}
+
+fn parse_account(account: &str) -> Result<[u8; 32]> {
+    Ed25519PublicKey::from_string(account)
+        .map(|key| key.0)
+        .map_err(|_| AppError::InvalidInput(format!("Invalid Stellar account: {}", account)))
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn xdr_error(error: soroban_client::xdr::Error) -> AppError {
+    AppError::XdrEncoding(format!("{:?}", error))
+}
+
+fn invalid(reason: &str) -> AppError {
+    AppError::InvalidInput(format!("Invalid challenge: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn wallet_auth() -> WalletAuth {
+        WalletAuth::new(
+            SigningKey::from_bytes(&random_bytes()),
+            b"test secret",
+            PASSPHRASE.to_string(),
+            "stellarheads.test".to_string(),
+            Duration::from_secs(3600),
+        )
+    }
+
+    fn account(key: &SigningKey) -> String {
+        Ed25519PublicKey(key.verifying_key().to_bytes()).to_string()
+    }
+
+    /// Countersign a challenge the way a wallet would
+    fn sign(auth: &WalletAuth, challenge: &str, key: &SigningKey) -> String {
+        let TransactionEnvelope::Tx(mut envelope) =
+            TransactionEnvelope::from_xdr_base64(challenge, Limits::none()).unwrap()
+        else {
+            panic!("challenge should be a v1 envelope");
+        };
+        let hash = auth.transaction_hash(&envelope.tx).unwrap();
+        let mut signatures = envelope.signatures.to_vec();
+        signatures.push(decorated_signature(key, &hash).unwrap());
+        envelope.signatures = signatures.try_into().unwrap();
+        TransactionEnvelope::Tx(envelope).to_xdr_base64(Limits::none()).unwrap()
+    }
+
+    #[test]
+    fn signed_challenge_is_exchanged_for_a_session_once() {
+        let auth = wallet_auth();
+        let player = SigningKey::from_bytes(&random_bytes());
+
+        let challenge = auth.challenge(&account(&player)).unwrap();
+        assert_eq!(challenge.network_passphrase, PASSPHRASE);
+        let signed = sign(&auth, &challenge.transaction, &player);
+
+        let session = auth.verify_challenge(&signed).unwrap();
+        assert_eq!(session.wallet_address, account(&player));
+        assert_eq!(auth.authenticate(&session.token).unwrap().sub, account(&player));
+
+        // Replaying the same signed challenge doesn't mint another session
+        assert!(matches!(auth.verify_challenge(&signed), Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn challenge_signed_by_another_key_is_rejected() {
+        let auth = wallet_auth();
+        let player = SigningKey::from_bytes(&random_bytes());
+        let impostor = SigningKey::from_bytes(&random_bytes());
+
+        let challenge = auth.challenge(&account(&player)).unwrap();
+        let signed = sign(&auth, &challenge.transaction, &impostor);
+        assert!(matches!(auth.verify_challenge(&signed), Err(AppError::Unauthorized(_))));
+
+        // Unsigned by the wallet at all
+        assert!(auth.verify_challenge(&challenge.transaction).is_err());
+    }
+
+    #[test]
+    fn challenges_and_tokens_from_another_server_are_rejected() {
+        let auth = wallet_auth();
+        let other = wallet_auth();
+        let player = SigningKey::from_bytes(&random_bytes());
+
+        let challenge = other.challenge(&account(&player)).unwrap();
+        let signed = sign(&other, &challenge.transaction, &player);
+        assert!(matches!(auth.verify_challenge(&signed), Err(AppError::InvalidInput(_))));
+
+        let forged = WalletAuth::new(
+            SigningKey::from_bytes(&random_bytes()),
+            b"another secret",
+            PASSPHRASE.to_string(),
+            "stellarheads.test".to_string(),
+            Duration::from_secs(3600),
+        );
+        let challenge = forged.challenge(&account(&player)).unwrap();
+        let token = forged.verify_challenge(&sign(&forged, &challenge.transaction, &player)).unwrap().token;
+        assert!(auth.authenticate(&token).is_err());
+    }
+
+    #[test]
+    fn session_keys_are_bound_to_the_player_and_session() {
+        let auth = wallet_auth();
+        let player = account(&SigningKey::from_bytes(&random_bytes()));
+        let other = account(&SigningKey::from_bytes(&random_bytes()));
+
+        // Stable across requests, so the backend can re-derive it when the result arrives
+        assert_eq!(auth.session_key(&player, "session-1"), auth.session_key(&player, "session-1"));
+        assert_ne!(auth.session_key(&player, "session-1"), auth.session_key(&player, "session-2"));
+        assert_ne!(auth.session_key(&player, "session-1"), auth.session_key(&other, "session-1"));
+    }
+}
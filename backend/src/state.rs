@@ -0,0 +1,60 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::database::DbPool;
+use crate::metrics::Metrics;
+use crate::services::{ScalableContractManager, WalletAuth};
+
+/// Shared state handed to every handler
+/// Handlers extract only the part they need, e.g. `State<DbPool>` or `State<Arc<ScalableContractManager>>`
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: DbPool,
+    pub contracts: Arc<ScalableContractManager>,
+    pub metrics: Arc<Metrics>,
+    /// Wallet challenges and the session tokens required on write routes
+    pub wallet_auth: Arc<WalletAuth>,
+    /// Bearer token for /api/admin routes; admin routes are refused without one
+    pub admin_api_token: Option<Arc<str>>,
+}
+
+impl AppState {
+    pub fn new(
+        pool: DbPool,
+        contracts: Arc<ScalableContractManager>,
+        wallet_auth: Arc<WalletAuth>,
+        admin_api_token: Option<String>,
+    ) -> Self {
+        Self {
+            pool,
+            contracts,
+            metrics: Arc::new(Metrics::new()),
+            wallet_auth,
+            admin_api_token: admin_api_token.map(Arc::from),
+        }
+    }
+}
+
+impl FromRef<AppState> for DbPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ScalableContractManager> {
+    fn from_ref(state: &AppState) -> Self {
+        state.contracts.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Metrics> {
+    fn from_ref(state: &AppState) -> Self {
+        state.metrics.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<WalletAuth> {
+    fn from_ref(state: &AppState) -> Self {
+        state.wallet_auth.clone()
+    }
+}
@@ -0,0 +1,108 @@
+// Single-elimination bracket layout
+//
+// Brackets are stored flat, round by round. A bracket with `size` slots (a power
+// of two) has size/2 first-round matches, size/4 in round two and so on down to
+// the final, size - 1 matches in total. Round numbers start at 1.
+
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::BracketMatch;
+
+/// Number of slots in the first round, so that every player fits
+fn bracket_size(player_count: u32) -> u32 {
+    player_count.next_power_of_two()
+}
+
+/// Index of a round's first match in the flat bracket
+fn round_offset(size: u32, round: u32) -> u32 {
+    // Earlier rounds hold size/2 + size/4 + ... matches
+    size - (size >> (round - 1))
+}
+
+/// Seed a bracket from players in registration order
+/// Seed 1 meets the last seed, seed 2 the second last and so on; when the player
+/// count isn't a power of two the top seeds get a bye into round two
+pub fn seed(env: &Env, players: &Vec<Address>) -> Vec<BracketMatch> {
+    let size = bracket_size(players.len());
+    let rounds = size.trailing_zeros();
+
+    let mut bracket = Vec::new(env);
+    for round in 1..=rounds {
+        for slot in 0..(size >> round) {
+            let (player_a, player_b) = if round == 1 {
+                (players.get(slot), players.get(size - 1 - slot))
+            } else {
+                (None, None)
+            };
+            bracket.push_back(BracketMatch {
+                round,
+                slot,
+                player_a,
+                player_b,
+                winner: None,
+            });
+        }
+    }
+
+    for slot in 0..(size / 2) {
+        let first_round = bracket.get_unchecked(slot);
+        if let (Some(player), None) = (first_round.player_a, first_round.player_b) {
+            record_winner(&mut bracket, slot, &player);
+        }
+    }
+
+    bracket
+}
+
+/// Find the undecided match in `round` between `winner` and `loser`
+pub fn find_match(
+    bracket: &Vec<BracketMatch>,
+    round: u32,
+    winner: &Address,
+    loser: &Address,
+) -> Option<u32> {
+    let size = bracket.len() + 1;
+    if round == 0 || round > size.trailing_zeros() {
+        return None;
+    }
+
+    let start = round_offset(size, round);
+    let end = start + (size >> round);
+    (start..end).find(|&index| {
+        let entry = bracket.get_unchecked(index);
+        let (Some(a), Some(b)) = (entry.player_a, entry.player_b) else {
+            return false;
+        };
+        entry.winner.is_none()
+            && ((a == *winner && b == *loser) || (a == *loser && b == *winner))
+    })
+}
+
+/// Record the winner of a match and move them into their next-round match
+/// Returns the winner if this was the final
+pub fn record_winner(
+    bracket: &mut Vec<BracketMatch>,
+    index: u32,
+    winner: &Address,
+) -> Option<Address> {
+    let size = bracket.len() + 1;
+
+    let mut decided = bracket.get_unchecked(index);
+    decided.winner = Some(winner.clone());
+    bracket.set(index, decided.clone());
+
+    if decided.round == size.trailing_zeros() {
+        return Some(winner.clone());
+    }
+
+    let next_index = round_offset(size, decided.round + 1) + decided.slot / 2;
+    let mut next = bracket.get_unchecked(next_index);
+    if decided.slot.is_multiple_of(2) {
+        next.player_a = Some(winner.clone());
+    } else {
+        next.player_b = Some(winner.clone());
+    }
+    bracket.set(next_index, next);
+
+    None
+}
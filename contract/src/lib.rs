@@ -1,12 +1,48 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, contracterror,
-    Address, Env, Map, Vec, log, Symbol
+    contract, contractimpl, contracttype, contracterror, token,
+    Address, BytesN, Env, Map, String, Vec, log, Symbol
 };
 
+mod bracket;
+mod rating;
+mod storage;
 mod test;
 
+use rating::{Outcome, AI_RATING, INITIAL_RATING};
+
+/// Maximum number of entries kept in the sorted top-N leaderboard index
+pub const MAX_LEADERBOARD_SIZE: u32 = 100;
+
+/// Storage layout version written by this build of the contract
+/// Version 1 is the original layout with every player in a single DataKey::Players map
+pub const CONTRACT_VERSION: u32 = 2;
+
+/// Maximum number of players returned by one page or batch query
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of players whose entries bump_all extends in one call
+pub const MAX_BUMP_BATCH: u32 = 50;
+
+/// Maximum number of legacy players moved by one migrate call
+pub const MAX_MIGRATE_BATCH: u32 = 50;
+
+/// Maximum number of players that can register for a tournament
+pub const MAX_TOURNAMENT_PLAYERS: u32 = 64;
+
+/// Maximum length of a profile display name, in bytes
+pub const MAX_DISPLAY_NAME_LEN: u32 = 32;
+
+/// Highest avatar id the game ships artwork for
+pub const MAX_AVATAR_ID: u32 = 63;
+
+/// Goals in a single match needed for the hat-trick achievement
+pub const HAT_TRICK_GOALS: u32 = 3;
+
+/// Seconds after creation before an unsettled wager can be refunded (7 days)
+pub const WAGER_TIMEOUT_SECS: u64 = 7 * 24 * 60 * 60;
+
 /// Contract errors
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -18,23 +54,149 @@ pub enum Error {
     NotJoined = 2,
     /// Invalid input parameter
     InvalidInput = 3,
+    /// Contract has no admin
+    NotInitialized = 4,
+    // 5 was AlreadyInitialized; the admin is now set by the constructor
+    /// Match session has already been recorded
+    SessionAlreadyRecorded = 6,
+    /// Season id must be greater than the current season's
+    InvalidSeason = 7,
+    /// Storage is already at the current layout version
+    AlreadyMigrated = 8,
+    /// Tournament does not exist
+    TournamentNotFound = 9,
+    /// Tournament registration has closed
+    RegistrationClosed = 10,
+    /// Tournament has reached max_players
+    TournamentFull = 11,
+    /// Player is already registered for the tournament
+    AlreadyRegistered = 12,
+    /// Tournament bracket hasn't been drawn yet
+    TournamentNotStarted = 13,
+    /// No undecided match between these players in that round
+    InvalidMatch = 14,
+    /// Wager does not exist
+    WagerNotFound = 15,
+    /// Wager is not in a state that allows this action
+    InvalidWagerState = 16,
+    /// Wager timed out and can only be refunded
+    WagerExpired = 17,
+    /// Wager hasn't timed out yet
+    WagerNotExpired = 18,
 }
 
 // Data keys for contract storage
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    Players,      // Map of all players who have joined
+    Player(Address),     // Individual player records
     PlayerWins(Address), // Individual player win counts
+    PlayerCount,  // Number of players who have joined
+    PlayerAt(u32), // Player address by join order, for enumeration
+    TopRated,     // Sorted top-N rating index
+    Admin,        // Backend oracle allowed to record match results
+    Session(String), // Recorded matches by session id
+    CurrentSeason, // Season currently (or most recently) in progress
+    SeasonWins(u32, Address), // Per-season player win counts
+    SeasonTopPlayers(u32),    // Sorted top-N index for a season still in progress
+    SeasonArchive(u32),       // Final top-N of a closed season
+    Version,      // Storage layout version, see CONTRACT_VERSION
+    Players,      // Version 1 map of all players, only read by migrate()
+    TournamentCount, // Number of tournaments created, also the latest id
+    Tournament(u32), // Tournament details and registrations by id
+    Bracket(u32),    // Drawn bracket of a tournament in progress or finished
+    WagerCount,      // Number of wagers created, also the latest id
+    Wager(u32),      // Head-to-head wager by id
+    Achievements(Address), // Badges awarded to a player, in the order earned
+    LostLastMatch(Address), // Set while a player's most recent match was a loss
+    Profile(Address),       // Optional display metadata set by the player
 }
 
 // Player information stored on the leaderboard
 #[derive(Clone)]
 #[contracttype]
+pub struct PlayerRecord {
+    pub address: Address,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub goals_for: u32,
+    pub goals_against: u32,
+    pub rating: u32,      // Elo rating, updated by record_match
+    pub joined_at: u64,   // timestamp when they joined
+    pub last_played: u64, // timestamp of their last recorded match, 0 if none
+}
+
+// Display metadata a player can attach to their address
+#[derive(Clone)]
+#[contracttype]
+pub struct Profile {
+    pub display_name: String, // 1 to MAX_DISPLAY_NAME_LEN bytes
+    pub avatar_id: u32,       // 0 to MAX_AVATAR_ID
+    pub country: String,      // ISO 3166-1 alpha-2 code, or empty if not shared
+}
+
+// Player information as returned by the contract: the stored record plus profile
+// Profile fields are flattened since contract types can't nest an Option of a struct
+#[derive(Clone)]
+#[contracttype]
 pub struct Player {
     pub address: Address,
     pub wins: u32,
-    pub joined_at: u64, // timestamp when they joined
+    pub losses: u32,
+    pub draws: u32,
+    pub goals_for: u32,
+    pub goals_against: u32,
+    pub rating: u32,
+    pub joined_at: u64,
+    pub last_played: u64,
+    pub display_name: Option<String>, // None until the player sets a profile
+    pub avatar_id: Option<u32>,
+    pub country: Option<String>,
+}
+
+// Player record as stored by version 1 of the contract
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyPlayer {
+    pub address: Address,
+    pub wins: u32,
+    pub joined_at: u64,
+}
+
+// Aggregated player statistics, mirroring the backend PlayerStats DTO
+#[derive(Clone)]
+#[contracttype]
+pub struct PlayerStats {
+    pub address: Address,
+    pub total_games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub goals_for: u32,
+    pub goals_against: u32,
+    pub rating: u32,
+    pub last_played: u64,
+}
+
+// Full result of a recorded match, stored per session and emitted as an event
+#[derive(Clone)]
+#[contracttype]
+pub struct MatchRecord {
+    pub session_id: String,
+    pub player: Address,
+    pub opponent: Option<Address>, // None for matches against the AI
+    pub score: u32,
+    pub opponent_score: u32,
+    pub played_at: u64,
+}
+
+// Entry of a stored top-N wins index
+#[derive(Clone)]
+#[contracttype]
+pub struct WinsEntry {
+    pub address: Address,
+    pub wins: u32,
 }
 
 // Leaderboard entry for sorted results
@@ -43,6 +205,115 @@ pub struct Player {
 pub struct LeaderboardEntry {
     pub address: Address,
     pub wins: u32,
+    pub rating: u32,
+    pub display_name: Option<String>, // Profile fields, None without a profile
+    pub avatar_id: Option<u32>,
+    pub country: Option<String>,
+}
+
+// Rating leaderboard entry for sorted results
+#[derive(Clone)]
+#[contracttype]
+pub struct RatingEntry {
+    pub address: Address,
+    pub rating: u32,
+}
+
+// One page of players in join order
+#[derive(Clone)]
+#[contracttype]
+pub struct PlayerPage {
+    pub players: Vec<Player>,
+    pub next_cursor: Option<u32>, // Cursor for the following page, None on the last page
+    pub total: u32,               // Player count at the time of the query
+}
+
+// A leaderboard season; wins recorded before ends_at count towards it
+#[derive(Clone)]
+#[contracttype]
+pub struct Season {
+    pub id: u32,
+    pub started_at: u64,
+    pub ends_at: u64,
+}
+
+// Lifecycle of a tournament
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TournamentStatus {
+    Registration, // Accepting players until the entry deadline or max_players
+    InProgress,   // Bracket drawn, results being reported
+    Finished,     // Final decided
+}
+
+// A single-elimination tournament run by its own admin
+#[derive(Clone)]
+#[contracttype]
+pub struct Tournament {
+    pub id: u32,
+    pub name: String,
+    pub admin: Address, // Organizer allowed to report results
+    pub max_players: u32,
+    pub entry_deadline: u64,
+    pub players: Vec<Address>, // Registration order, which is also the seeding
+    pub status: TournamentStatus,
+    pub winner: Option<Address>,
+}
+
+// One match in a tournament bracket
+#[derive(Clone)]
+#[contracttype]
+pub struct BracketMatch {
+    pub round: u32, // 1 for the first round
+    pub slot: u32,  // Position within the round
+    pub player_a: Option<Address>, // None until decided by the previous round
+    pub player_b: Option<Address>, // None until decided, or for a bye
+    pub winner: Option<Address>,
+}
+
+// Achievement ids awarded by record_match (and add_win for the win milestones)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+#[repr(u32)]
+pub enum Achievement {
+    FirstWin = 1,   // Win a match
+    TenWins = 2,    // Reach 10 wins
+    CleanSheet = 3, // Win without conceding
+    Comeback = 4,   // Win the match straight after a loss
+    HatTrick = 5,   // Score HAT_TRICK_GOALS or more in one match
+}
+
+// An achievement as awarded to a player
+#[derive(Clone)]
+#[contracttype]
+pub struct Badge {
+    pub achievement: Achievement,
+    pub awarded_at: u64,
+}
+
+// Lifecycle of a wager
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum WagerStatus {
+    Open,     // Challenger's stake escrowed, waiting for the opponent
+    Accepted, // Both stakes escrowed, waiting for the oracle
+    Settled,  // Pot paid out to the winner, or returned on a draw
+    Refunded, // Timed out and stakes returned
+}
+
+// A head-to-head match stake escrowed by the contract
+#[derive(Clone)]
+#[contracttype]
+pub struct Wager {
+    pub id: u32,
+    pub player_one: Address, // Challenger, stakes on creation
+    pub player_two: Address, // Opponent, stakes on acceptance
+    pub token: Address,      // Stellar asset contract both stakes are paid in
+    pub amount: i128,        // Stake per player
+    pub status: WagerStatus,
+    pub created_at: u64,
+    pub expires_at: u64,     // Refundable from this time if not settled
+    pub winner: Option<Address>, // None until settled, and for a draw
 }
 
 #[contract]
@@ -57,37 +328,38 @@ impl StellarHeadsLeaderboard {
         // Require player to authorize this transaction
         player.require_auth();
 
-        let mut players: Map<Address, Player> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(&env));
-
         // Check if player already joined
-        if players.contains_key(player.clone()) {
+        if Self::has_joined(env.clone(), player.clone()) {
             log!(&env, "Player already joined: {}", player);
             return Err(Error::AlreadyJoined);
         }
 
         // Create new player entry
-        let new_player = Player {
+        let new_player = PlayerRecord {
             address: player.clone(),
             wins: 0,
+            losses: 0,
+            draws: 0,
+            goals_for: 0,
+            goals_against: 0,
+            rating: INITIAL_RATING,
             joined_at: env.ledger().timestamp(),
+            last_played: 0,
         };
 
-        // Add player to the map
-        players.set(player.clone(), new_player);
+        // Store the player under its own key
+        storage::set(&env, &DataKey::Player(player.clone()), &new_player);
 
         // Initialize win count to 0
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlayerWins(player.clone()), &0u32);
+        storage::set(&env, &DataKey::PlayerWins(player.clone()), &0u32);
 
-        // Save updated players map
-        env.storage()
-            .persistent()
-            .set(&DataKey::Players, &players);
+        // Append to the enumeration index
+        let count = Self::get_player_count(env.clone());
+        storage::set(&env, &DataKey::PlayerAt(count), &player);
+        storage::instance_set(&env, &DataKey::PlayerCount, &(count + 1));
+
+        // New players rank below everyone already at the initial rating
+        Self::update_rating_index(&env, &player, INITIAL_RATING);
 
         // Emit event for player join
         env.events().publish(
@@ -101,71 +373,257 @@ impl StellarHeadsLeaderboard {
 
     /// Check if a player has joined the leaderboard
     pub fn has_joined(env: Env, player: Address) -> bool {
-        let players: Map<Address, Player> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(&env));
+        storage::has(&env, &DataKey::Player(player))
+    }
+
+    /// Set the admin (backend oracle) address
+    /// Runs as part of deployment, so nobody can claim the role before the deployer does
+    pub fn __constructor(env: Env, admin: Address) {
+        storage::instance_set(&env, &DataKey::Admin, &admin);
+
+        // Fresh deployments start on the current layout; code put over v1 data still needs migrate()
+        if !storage::has(&env, &DataKey::Players) {
+            storage::instance_set(&env, &DataKey::Version, &CONTRACT_VERSION);
+        }
 
-        players.contains_key(player)
+        log!(&env, "Leaderboard deployed with admin: {}", admin);
     }
 
-    /// Add a win for a player (requires authorization)
+    /// Hand the admin role to a new address (requires current admin authorization)
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
+
+        storage::instance_set(&env, &DataKey::Admin, &new_admin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), admin),
+            new_admin.clone()
+        );
+
+        log!(&env, "Admin changed to: {}", new_admin);
+        Ok(())
+    }
+
+    /// Replace the contract code with previously uploaded WASM (requires admin authorization)
+    /// Storage is kept; call migrate() afterwards if the new code bumps CONTRACT_VERSION
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "upgraded"),),
+            new_wasm_hash
+        );
+
+        Ok(())
+    }
+
+    /// Get the storage layout version currently in use
+    pub fn get_version(env: Env) -> u32 {
+        Self::stored_version(&env)
+    }
+
+    /// Bring storage up to CONTRACT_VERSION in batches (requires admin authorization)
+    /// Moves legacy players start..start + count; call again with the returned start until it is 0
+    /// The version is only bumped once every legacy player has been moved
+    /// Returns Err(Error::AlreadyMigrated) if there is nothing to do
+    pub fn migrate(env: Env, start: u32, count: u32) -> Result<u32, Error> {
+        Self::require_admin(&env)?;
+
+        if count == 0 || count > MAX_MIGRATE_BATCH {
+            return Err(Error::InvalidInput);
+        }
+
+        let version = Self::stored_version(&env);
+        if version >= CONTRACT_VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+
+        if version < 2 {
+            let next = Self::migrate_players_map(&env, start, count);
+            if next != 0 {
+                return Ok(next);
+            }
+        }
+
+        storage::instance_set(&env, &DataKey::Version, &CONTRACT_VERSION);
+
+        env.events().publish(
+            (Symbol::new(&env, "migrated"), version),
+            CONTRACT_VERSION
+        );
+
+        log!(&env, "Storage migrated from version {} to {}", version, CONTRACT_VERSION);
+        Ok(0)
+    }
+
+    /// Extend the TTL of a batch of player, tournament and wager entries plus the shared indexes (requires admin authorization)
+    /// Covers players start..start + count in join order and the tournaments and wagers with those ids (counting from 1),
+    /// so nothing still in use is archived
+    /// Session replay guards and closed season archives are left to expire: they aren't enumerable, and once
+    /// archived they can still be restored, while an archived session still blocks a replay of that match
+    /// Returns the start index of the next batch, or 0 once every entry has been covered
+    pub fn bump_all(env: Env, start: u32, count: u32) -> Result<u32, Error> {
+        Self::require_admin(&env)?;
+
+        if count == 0 || count > MAX_BUMP_BATCH {
+            return Err(Error::InvalidInput);
+        }
+
+        storage::extend_instance(&env);
+        storage::extend(&env, &DataKey::TopRated);
+
+        let season = Self::get_current_season(env.clone());
+        if let Some(season) = &season {
+            storage::extend(&env, &DataKey::SeasonTopPlayers(season.id));
+        }
+
+        let player_count = Self::get_player_count(env.clone());
+        let tournament_count: u32 = storage::instance_get(&env, &DataKey::TournamentCount).unwrap_or(0);
+        let wager_count: u32 = storage::instance_get(&env, &DataKey::WagerCount).unwrap_or(0);
+        let total = player_count.max(tournament_count).max(wager_count);
+
+        let end = start.saturating_add(count).min(total);
+        for index in start..end {
+            if let Some(address) = storage::get::<Address>(&env, &DataKey::PlayerAt(index)) {
+                storage::extend(&env, &DataKey::Player(address.clone()));
+                storage::extend(&env, &DataKey::PlayerWins(address.clone()));
+                storage::extend(&env, &DataKey::Profile(address.clone()));
+                storage::extend(&env, &DataKey::Achievements(address.clone()));
+                storage::extend(&env, &DataKey::LostLastMatch(address.clone()));
+                if let Some(season) = &season {
+                    storage::extend(&env, &DataKey::SeasonWins(season.id, address));
+                }
+            }
+
+            storage::extend(&env, &DataKey::Tournament(index + 1));
+            storage::extend(&env, &DataKey::Bracket(index + 1));
+            storage::extend(&env, &DataKey::Wager(index + 1));
+        }
+
+        log!(&env, "Extended TTL for entries {} to {}", start, end);
+        Ok(if end >= total { 0 } else { end })
+    }
+
+    /// Get the admin (backend oracle) address
+    pub fn get_admin(env: Env) -> Option<Address> {
+        storage::instance_get(&env, &DataKey::Admin)
+    }
+
+    /// Add a win for a player (requires oracle authorization)
+    /// Carries no opponent information, so the player's rating is left unchanged
     /// Returns Ok(new_win_count) or Err(Error::NotJoined) if player hasn't joined
     pub fn add_win(env: Env, player: Address) -> Result<u32, Error> {
-        // Require player to authorize this transaction
-        player.require_auth();
+        // Wins are attested by the backend oracle, not self-reported
+        Self::require_admin(&env)?;
 
         // Check if player has joined
         if !Self::has_joined(env.clone(), player.clone()) {
             return Err(Error::NotJoined);
         }
 
-        // Get current win count
-        let current_wins: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlayerWins(player.clone()))
-            .unwrap_or(0);
+        Ok(Self::increment_wins(&env, &player))
+    }
 
-        let new_wins = current_wins + 1;
+    /// Record the result of a finished match (requires oracle authorization)
+    /// `opponent` is None for matches played against the AI
+    /// Updates wins, losses, draws, goals and Elo ratings for both sides and emits a match_recorded event
+    /// Matches against the AI are rated against a fixed AI_RATING
+    /// Returns Ok(player_win_count) or Err(Error::SessionAlreadyRecorded) on replay
+    pub fn record_match(
+        env: Env,
+        player: Address,
+        opponent: Option<Address>,
+        session_id: String,
+        score: u32,
+        opponent_score: u32,
+    ) -> Result<u32, Error> {
+        Self::require_admin(&env)?;
 
-        // Update win count
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlayerWins(player.clone()), &new_wins);
-
-        // Update player in the players map
-        let mut players: Map<Address, Player> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(&env));
-
-        if let Some(mut player_data) = players.get(player.clone()) {
-            player_data.wins = new_wins;
-            players.set(player.clone(), player_data);
-            env.storage()
-                .persistent()
-                .set(&DataKey::Players, &players);
+        if session_id.is_empty() {
+            return Err(Error::InvalidInput);
         }
 
-        // Emit event for win addition
+        // Reject replayed session ids
+        let session_key = DataKey::Session(session_id.clone());
+        if storage::has(&env, &session_key) {
+            log!(&env, "Session already recorded: {}", session_id);
+            return Err(Error::SessionAlreadyRecorded);
+        }
+
+        if !Self::has_joined(env.clone(), player.clone()) {
+            return Err(Error::NotJoined);
+        }
+
+        if let Some(opponent) = &opponent {
+            if *opponent == player {
+                return Err(Error::InvalidInput);
+            }
+            if !Self::has_joined(env.clone(), opponent.clone()) {
+                return Err(Error::NotJoined);
+            }
+        }
+
+        let record = MatchRecord {
+            session_id: session_id.clone(),
+            player: player.clone(),
+            opponent: opponent.clone(),
+            score,
+            opponent_score,
+            played_at: env.ledger().timestamp(),
+        };
+        storage::set(&env, &session_key, &record);
+
+        // Ratings are computed from both pre-match ratings before either is updated
+        let player_rating = Self::get_rating(env.clone(), player.clone());
+        let opponent_rating = match &opponent {
+            Some(opponent) => Self::get_rating(env.clone(), opponent.clone()),
+            None => AI_RATING,
+        };
+        let outcome = if score > opponent_score {
+            Outcome::Win
+        } else if score < opponent_score {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        };
+
+        let player_wins = Self::apply_result(
+            &env,
+            &player,
+            score,
+            opponent_score,
+            rating::updated_rating(player_rating, opponent_rating, outcome),
+        );
+        if let Some(opponent) = &opponent {
+            let opponent_outcome = match outcome {
+                Outcome::Win => Outcome::Loss,
+                Outcome::Loss => Outcome::Win,
+                Outcome::Draw => Outcome::Draw,
+            };
+            Self::apply_result(
+                &env,
+                opponent,
+                opponent_score,
+                score,
+                rating::updated_rating(opponent_rating, player_rating, opponent_outcome),
+            );
+        }
+
+        // Emit the full result so off-chain indexers can rebuild match history
         env.events().publish(
-            (Symbol::new(&env, "win_added"), player.clone()),
-            new_wins
+            (Symbol::new(&env, "match_recorded"), player.clone()),
+            record
         );
 
-        log!(&env, "Win added for player: {} (total: {})", player, new_wins);
-        Ok(new_wins)
+        log!(&env, "Match recorded: {} ({} - {})", session_id, score, opponent_score);
+        Ok(player_wins)
     }
 
     /// Get wins for a specific player
     pub fn get_wins(env: Env, player: Address) -> u32 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlayerWins(player))
-            .unwrap_or(0)
+        storage::get(&env, &DataKey::PlayerWins(player)).unwrap_or(0)
     }
 
     /// Get wins for the calling player (same as get_wins but more explicit)
@@ -173,80 +631,927 @@ impl StellarHeadsLeaderboard {
         Self::get_wins(env, player)
     }
 
-    /// Get all players who have joined the leaderboard
+    /// Get all players who have joined the leaderboard, in join order
+    /// Grows with the player base; use get_players_page for anything user-facing
     pub fn get_all_players(env: Env) -> Vec<Address> {
-        let players: Map<Address, Player> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(&env));
+        let count = Self::get_player_count(env.clone());
 
         let mut result = Vec::new(&env);
-        for (address, _) in players.iter() {
-            result.push_back(address);
+        for index in 0..count {
+            if let Some(address) = storage::get(&env, &DataKey::PlayerAt(index)) {
+                result.push_back(address);
+            }
         }
         result
     }
 
-    /// Get leaderboard sorted by wins (top players first)
-    pub fn get_leaderboard(env: Env, limit: u32) -> Vec<LeaderboardEntry> {
-        let players: Map<Address, Player> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(&env));
-
-        // Convert to vector for sorting
-        let mut entries = Vec::new(&env);
-        for (address, player_data) in players.iter() {
-            entries.push_back(LeaderboardEntry {
-                address,
-                wins: player_data.wins,
-            });
-        }
-
-        // Sort by wins (descending) - manual bubble sort since we can't use std::sort
-        let len = entries.len();
-        for i in 0..len {
-            for j in 0..(len - i - 1) {
-                if entries.get(j).unwrap().wins < entries.get(j + 1).unwrap().wins {
-                    let temp = entries.get(j).unwrap();
-                    entries.set(j, entries.get(j + 1).unwrap());
-                    entries.set(j + 1, temp);
+    /// Get up to `limit` players in join order, starting at `cursor` (0 for the first page)
+    /// Join order never changes, so pages stay stable as new players join
+    pub fn get_players_page(env: Env, cursor: u32, limit: u32) -> Result<PlayerPage, Error> {
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(Error::InvalidInput);
+        }
+
+        let total = Self::get_player_count(env.clone());
+        let end = cursor.saturating_add(limit).min(total);
+
+        let mut players = Vec::new(&env);
+        for index in cursor..end {
+            if let Some(address) = storage::get(&env, &DataKey::PlayerAt(index)) {
+                if let Some(player) = Self::get_player(env.clone(), address) {
+                    players.push_back(player);
                 }
             }
         }
 
-        // Apply limit
-        let mut result = Vec::new(&env);
-        let actual_limit = if limit == 0 || limit > len { len } else { limit };
+        Ok(PlayerPage {
+            players,
+            next_cursor: if end < total { Some(end) } else { None },
+            total,
+        })
+    }
 
-        for i in 0..actual_limit {
-            result.push_back(entries.get(i).unwrap());
+    /// Get wins for several players at once, in the order given
+    /// Players who haven't joined have 0 wins
+    pub fn get_wins_batch(env: Env, players: Vec<Address>) -> Result<Vec<u32>, Error> {
+        if players.len() > MAX_PAGE_SIZE {
+            return Err(Error::InvalidInput);
         }
 
-        result
+        let mut wins = Vec::new(&env);
+        for player in players.iter() {
+            wins.push_back(Self::get_wins(env.clone(), player));
+        }
+        Ok(wins)
+    }
+
+    /// Get several player records at once, in the order given
+    /// Players who haven't joined come back as None
+    pub fn get_players_batch(env: Env, players: Vec<Address>) -> Result<Vec<Option<Player>>, Error> {
+        if players.len() > MAX_PAGE_SIZE {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut records = Vec::new(&env);
+        for player in players.iter() {
+            records.push_back(Self::get_player(env.clone(), player));
+        }
+        Ok(records)
+    }
+
+    /// Get leaderboard sorted by rating (highest first)
+    /// Served from the top-N rating index, so at most MAX_LEADERBOARD_SIZE entries are returned
+    pub fn get_leaderboard(env: Env, limit: u32) -> Vec<LeaderboardEntry> {
+        let mut leaderboard = Vec::new(&env);
+        for entry in Self::get_rating_leaderboard(env.clone(), limit).iter() {
+            let wins = Self::get_wins(env.clone(), entry.address.clone());
+            leaderboard.push_back(Self::leaderboard_entry(&env, entry.address, wins, entry.rating));
+        }
+        leaderboard
     }
 
     /// Get total number of players
     pub fn get_player_count(env: Env) -> u32 {
-        let players: Map<Address, Player> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(&env));
-
-        players.len()
+        storage::instance_get(&env, &DataKey::PlayerCount).unwrap_or(0)
     }
 
-    /// Get player info by address
+    /// Get player info by address, including their profile if they've set one
     pub fn get_player(env: Env, player: Address) -> Option<Player> {
-        let players: Map<Address, Player> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(&env));
+        let record: PlayerRecord = storage::get(&env, &DataKey::Player(player.clone()))?;
+        let profile = Self::get_profile(env, player);
+
+        Some(Player {
+            address: record.address,
+            wins: record.wins,
+            losses: record.losses,
+            draws: record.draws,
+            goals_for: record.goals_for,
+            goals_against: record.goals_against,
+            rating: record.rating,
+            joined_at: record.joined_at,
+            last_played: record.last_played,
+            display_name: profile.as_ref().map(|profile| profile.display_name.clone()),
+            avatar_id: profile.as_ref().map(|profile| profile.avatar_id),
+            country: profile.map(|profile| profile.country),
+        })
+    }
+
+    /// Set or replace the caller's profile
+    /// Returns Err(Error::InvalidInput) if a field is out of range
+    pub fn set_profile(
+        env: Env,
+        player: Address,
+        display_name: String,
+        avatar_id: u32,
+        country: String,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        if !Self::has_joined(env.clone(), player.clone()) {
+            return Err(Error::NotJoined);
+        }
+        if display_name.is_empty()
+            || display_name.len() > MAX_DISPLAY_NAME_LEN
+            || avatar_id > MAX_AVATAR_ID
+            || !Self::is_country_code(&country)
+        {
+            return Err(Error::InvalidInput);
+        }
+
+        let profile = Profile {
+            display_name,
+            avatar_id,
+            country,
+        };
+        storage::set(&env, &DataKey::Profile(player.clone()), &profile);
+
+        env.events().publish(
+            (Symbol::new(&env, "profile_updated"), player),
+            profile
+        );
+
+        Ok(())
+    }
+
+    /// Get a player's profile, if they've set one
+    pub fn get_profile(env: Env, player: Address) -> Option<Profile> {
+        storage::get(&env, &DataKey::Profile(player))
+    }
+
+    /// Get aggregated match statistics for a player
+    /// Returns None if the player hasn't joined
+    pub fn get_player_stats(env: Env, player: Address) -> Option<PlayerStats> {
+        let data = Self::get_player(env, player)?;
+
+        Some(PlayerStats {
+            address: data.address,
+            total_games: data.wins + data.losses + data.draws,
+            wins: data.wins,
+            losses: data.losses,
+            draws: data.draws,
+            goals_for: data.goals_for,
+            goals_against: data.goals_against,
+            rating: data.rating,
+            last_played: data.last_played,
+        })
+    }
+
+    /// Get a player's Elo rating, 0 if they haven't joined
+    pub fn get_rating(env: Env, player: Address) -> u32 {
+        Self::get_player(env, player)
+            .map(|data| data.rating)
+            .unwrap_or(0)
+    }
+
+    /// Get leaderboard sorted by rating (highest first)
+    /// Served from the top-N rating index, so at most MAX_LEADERBOARD_SIZE entries are returned
+    pub fn get_rating_leaderboard(env: Env, limit: u32) -> Vec<RatingEntry> {
+        let rated = Self::load_rating_index(&env);
+        if limit == 0 || limit >= rated.len() {
+            return rated;
+        }
+
+        rated.slice(0..limit)
+    }
+
+    /// Start a new season (requires oracle authorization)
+    /// Closes the current season first, archiving its final top-N
+    /// Returns Err(Error::InvalidSeason) unless `id` is greater than the current season's
+    pub fn start_season(env: Env, id: u32, ends_at: u64) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        let now = env.ledger().timestamp();
+        if ends_at <= now {
+            return Err(Error::InvalidInput);
+        }
+
+        if let Some(current) = Self::get_current_season(env.clone()) {
+            if id <= current.id {
+                return Err(Error::InvalidSeason);
+            }
+            Self::archive_season(&env, current.id);
+        }
+
+        let season = Season {
+            id,
+            started_at: now,
+            ends_at,
+        };
+        storage::instance_set(&env, &DataKey::CurrentSeason, &season);
+
+        env.events().publish(
+            (Symbol::new(&env, "season_started"), id),
+            ends_at
+        );
+
+        log!(&env, "Season {} started, ends at {}", id, ends_at);
+        Ok(())
+    }
+
+    /// Get the current (or most recently started) season
+    pub fn get_current_season(env: Env) -> Option<Season> {
+        storage::instance_get(&env, &DataKey::CurrentSeason)
+    }
+
+    /// Get a player's wins within a season
+    pub fn get_season_wins(env: Env, season: u32, player: Address) -> u32 {
+        storage::get(&env, &DataKey::SeasonWins(season, player)).unwrap_or(0)
+    }
+
+    /// Get a season's leaderboard sorted by season wins (top players first)
+    /// Closed seasons are served from their archived final standings
+    pub fn get_leaderboard_for_season(env: Env, season: u32, limit: u32) -> Vec<LeaderboardEntry> {
+        let top = match storage::get(&env, &DataKey::SeasonArchive(season)) {
+            Some(archived) => archived,
+            None => Self::load_top_index(&env, &DataKey::SeasonTopPlayers(season)),
+        };
+        Self::to_leaderboard(&env, top, limit)
+    }
+
+    /// Get a recorded match by its session id
+    pub fn get_match(env: Env, session_id: String) -> Option<MatchRecord> {
+        storage::get(&env, &DataKey::Session(session_id))
+    }
+
+    /// Create a single-elimination tournament organized by `admin`
+    /// Players can register until entry_deadline or until max_players have signed up
+    /// Returns the new tournament id
+    pub fn create_tournament(
+        env: Env,
+        admin: Address,
+        name: String,
+        max_players: u32,
+        entry_deadline: u64,
+    ) -> Result<u32, Error> {
+        admin.require_auth();
+
+        if !(2..=MAX_TOURNAMENT_PLAYERS).contains(&max_players)
+            || entry_deadline <= env.ledger().timestamp()
+        {
+            return Err(Error::InvalidInput);
+        }
+
+        let id: u32 = storage::instance_get(&env, &DataKey::TournamentCount).unwrap_or(0) + 1;
+        let tournament = Tournament {
+            id,
+            name: name.clone(),
+            admin,
+            max_players,
+            entry_deadline,
+            players: Vec::new(&env),
+            status: TournamentStatus::Registration,
+            winner: None,
+        };
+        storage::set(&env, &DataKey::Tournament(id), &tournament);
+        storage::instance_set(&env, &DataKey::TournamentCount, &id);
+
+        env.events().publish(
+            (Symbol::new(&env, "tournament_created"), id),
+            name
+        );
+
+        log!(&env, "Tournament {} created", id);
+        Ok(id)
+    }
+
+    /// Register a joined player for a tournament
+    /// The bracket is drawn as soon as the tournament fills up
+    pub fn register(env: Env, player: Address, tournament: u32) -> Result<(), Error> {
+        player.require_auth();
+
+        let mut details = Self::load_tournament(&env, tournament)?;
+        if details.status != TournamentStatus::Registration
+            || env.ledger().timestamp() >= details.entry_deadline
+        {
+            return Err(Error::RegistrationClosed);
+        }
+        if !Self::has_joined(env.clone(), player.clone()) {
+            return Err(Error::NotJoined);
+        }
+        if details.players.contains(&player) {
+            return Err(Error::AlreadyRegistered);
+        }
+        if details.players.len() >= details.max_players {
+            return Err(Error::TournamentFull);
+        }
+
+        details.players.push_back(player.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "tournament_registered"), tournament, player),
+            details.players.len()
+        );
+
+        if details.players.len() == details.max_players {
+            Self::start_tournament(&env, &mut details)?;
+        }
+
+        storage::set(&env, &DataKey::Tournament(tournament), &details);
+        Ok(())
+    }
+
+    /// Report the result of a bracket match (requires the tournament admin's authorization)
+    /// The winner advances automatically; once registration has closed the first report
+    /// also draws the bracket
+    pub fn report_result(
+        env: Env,
+        tournament: u32,
+        round: u32,
+        winner: Address,
+        loser: Address,
+    ) -> Result<(), Error> {
+        let mut details = Self::load_tournament(&env, tournament)?;
+        details.admin.require_auth();
+
+        if details.status == TournamentStatus::Registration {
+            if env.ledger().timestamp() < details.entry_deadline {
+                return Err(Error::TournamentNotStarted);
+            }
+            Self::start_tournament(&env, &mut details)?;
+        }
+
+        let bracket_key = DataKey::Bracket(tournament);
+        let mut bracket: Vec<BracketMatch> =
+            storage::get(&env, &bracket_key).ok_or(Error::TournamentNotStarted)?;
+        let index = bracket::find_match(&bracket, round, &winner, &loser)
+            .ok_or(Error::InvalidMatch)?;
+        let champion = bracket::record_winner(&mut bracket, index, &winner);
+        storage::set(&env, &bracket_key, &bracket);
+
+        env.events().publish(
+            (Symbol::new(&env, "tournament_result"), tournament, round),
+            (winner, loser)
+        );
+
+        if let Some(champion) = champion {
+            details.status = TournamentStatus::Finished;
+            details.winner = Some(champion.clone());
+
+            env.events().publish(
+                (Symbol::new(&env, "tournament_won"), tournament),
+                champion
+            );
+        }
+
+        storage::set(&env, &DataKey::Tournament(tournament), &details);
+        Ok(())
+    }
+
+    /// Get a tournament's bracket, round by round
+    /// While registration is open this previews the draw from the players registered so far
+    pub fn get_bracket(env: Env, tournament: u32) -> Result<Vec<BracketMatch>, Error> {
+        let details = Self::load_tournament(&env, tournament)?;
+
+        if let Some(drawn) = storage::get(&env, &DataKey::Bracket(tournament)) {
+            return Ok(drawn);
+        }
+
+        if details.players.len() < 2 {
+            return Ok(Vec::new(&env));
+        }
+        Ok(bracket::seed(&env, &details.players))
+    }
+
+    /// Get a tournament's details and registrations
+    pub fn get_tournament(env: Env, tournament: u32) -> Option<Tournament> {
+        storage::get(&env, &DataKey::Tournament(tournament))
+    }
+
+    /// Challenge another player to a match staked with `amount` of `token`
+    /// The challenger's stake is escrowed in the contract until the wager is settled or refunded
+    /// Returns the new wager id
+    pub fn create_wager(
+        env: Env,
+        player_one: Address,
+        player_two: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<u32, Error> {
+        player_one.require_auth();
+
+        if amount <= 0 || player_one == player_two {
+            return Err(Error::InvalidInput);
+        }
+        if !Self::has_joined(env.clone(), player_one.clone())
+            || !Self::has_joined(env.clone(), player_two.clone())
+        {
+            return Err(Error::NotJoined);
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &player_one,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let id: u32 = storage::instance_get(&env, &DataKey::WagerCount).unwrap_or(0) + 1;
+        let now = env.ledger().timestamp();
+        let wager = Wager {
+            id,
+            player_one: player_one.clone(),
+            player_two: player_two.clone(),
+            token,
+            amount,
+            status: WagerStatus::Open,
+            created_at: now,
+            expires_at: now + WAGER_TIMEOUT_SECS,
+            winner: None,
+        };
+        storage::set(&env, &DataKey::Wager(id), &wager);
+        storage::instance_set(&env, &DataKey::WagerCount, &id);
+
+        env.events().publish(
+            (Symbol::new(&env, "wager_created"), id, player_one, player_two),
+            amount
+        );
+
+        Ok(id)
+    }
+
+    /// Accept a wager as the challenged player, escrowing the matching stake
+    pub fn accept_wager(env: Env, id: u32) -> Result<(), Error> {
+        let mut wager = Self::load_wager(&env, id)?;
+        wager.player_two.require_auth();
+
+        if wager.status != WagerStatus::Open {
+            return Err(Error::InvalidWagerState);
+        }
+        if env.ledger().timestamp() >= wager.expires_at {
+            return Err(Error::WagerExpired);
+        }
+
+        token::Client::new(&env, &wager.token).transfer(
+            &wager.player_two,
+            &env.current_contract_address(),
+            &wager.amount,
+        );
+
+        wager.status = WagerStatus::Accepted;
+        storage::set(&env, &DataKey::Wager(id), &wager);
+
+        env.events().publish((Symbol::new(&env, "wager_accepted"), id), wager.amount);
+        Ok(())
+    }
+
+    /// Settle an accepted wager before it times out (requires oracle authorization)
+    /// The winner takes both stakes; None records a draw and returns each stake
+    pub fn settle_wager(env: Env, id: u32, winner: Option<Address>) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        let mut wager = Self::load_wager(&env, id)?;
+        if wager.status != WagerStatus::Accepted {
+            return Err(Error::InvalidWagerState);
+        }
+        if env.ledger().timestamp() >= wager.expires_at {
+            return Err(Error::WagerExpired);
+        }
+
+        let token = token::Client::new(&env, &wager.token);
+        let contract = env.current_contract_address();
+        match &winner {
+            Some(address) if *address == wager.player_one || *address == wager.player_two => {
+                token.transfer(&contract, address, &(wager.amount * 2));
+            }
+            Some(_) => return Err(Error::InvalidInput),
+            None => {
+                token.transfer(&contract, &wager.player_one, &wager.amount);
+                token.transfer(&contract, &wager.player_two, &wager.amount);
+            }
+        }
+
+        wager.status = WagerStatus::Settled;
+        wager.winner = winner.clone();
+        storage::set(&env, &DataKey::Wager(id), &wager);
+
+        env.events().publish((Symbol::new(&env, "wager_settled"), id), winner);
+        Ok(())
+    }
+
+    /// Return escrowed stakes once a wager has timed out without being settled
+    /// Anyone can trigger the refund; funds only ever go back to the players who staked them
+    pub fn refund_wager(env: Env, id: u32) -> Result<(), Error> {
+        let mut wager = Self::load_wager(&env, id)?;
+        if env.ledger().timestamp() < wager.expires_at {
+            return Err(Error::WagerNotExpired);
+        }
+
+        let token = token::Client::new(&env, &wager.token);
+        let contract = env.current_contract_address();
+        match wager.status {
+            WagerStatus::Open => {
+                token.transfer(&contract, &wager.player_one, &wager.amount);
+            }
+            WagerStatus::Accepted => {
+                token.transfer(&contract, &wager.player_one, &wager.amount);
+                token.transfer(&contract, &wager.player_two, &wager.amount);
+            }
+            WagerStatus::Settled | WagerStatus::Refunded => return Err(Error::InvalidWagerState),
+        }
+
+        wager.status = WagerStatus::Refunded;
+        storage::set(&env, &DataKey::Wager(id), &wager);
+
+        env.events().publish((Symbol::new(&env, "wager_refunded"), id), wager.amount);
+        Ok(())
+    }
+
+    /// Get a wager by id
+    pub fn get_wager(env: Env, id: u32) -> Option<Wager> {
+        storage::get(&env, &DataKey::Wager(id))
+    }
+
+    /// Get the badges a player has earned, in the order they were awarded
+    pub fn get_achievements(env: Env, player: Address) -> Vec<Badge> {
+        storage::get(&env, &DataKey::Achievements(player)).unwrap_or(Vec::new(&env))
+    }
+}
+
+impl StellarHeadsLeaderboard {
+    /// Require the stored admin to authorize the current invocation
+    /// Returns the admin address
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = storage::instance_get(env, &DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Load a tournament or fail with TournamentNotFound
+    fn load_tournament(env: &Env, tournament: u32) -> Result<Tournament, Error> {
+        storage::get(env, &DataKey::Tournament(tournament)).ok_or(Error::TournamentNotFound)
+    }
+
+    /// Load a wager or fail with WagerNotFound
+    fn load_wager(env: &Env, id: u32) -> Result<Wager, Error> {
+        storage::get(env, &DataKey::Wager(id)).ok_or(Error::WagerNotFound)
+    }
+
+    /// Close registration and draw the bracket
+    /// The caller is responsible for saving the updated tournament
+    fn start_tournament(env: &Env, details: &mut Tournament) -> Result<(), Error> {
+        if details.players.len() < 2 {
+            return Err(Error::TournamentNotStarted);
+        }
+
+        let drawn = bracket::seed(env, &details.players);
+        storage::set(env, &DataKey::Bracket(details.id), &drawn);
+        details.status = TournamentStatus::InProgress;
+
+        env.events().publish(
+            (Symbol::new(env, "tournament_started"), details.id),
+            details.players.len()
+        );
+
+        Ok(())
+    }
+
+    /// Storage layout version, inferring version 1 from the legacy players map
+    fn stored_version(env: &Env) -> u32 {
+        if let Some(version) = storage::instance_get(env, &DataKey::Version) {
+            return version;
+        }
+
+        if storage::has(env, &DataKey::Players) {
+            1
+        } else {
+            CONTRACT_VERSION
+        }
+    }
+
+    /// Move legacy players start..start + count out of the version 1 DataKey::Players map
+    /// Players are appended to the enumeration index in address order
+    /// Removes the map and returns 0 once the last player has been moved, otherwise the next start
+    fn migrate_players_map(env: &Env, start: u32, count: u32) -> u32 {
+        let legacy: Map<Address, LegacyPlayer> =
+            storage::get(env, &DataKey::Players).unwrap_or(Map::new(env));
+
+        let end = start.saturating_add(count).min(legacy.len());
+        for (address, legacy_player) in legacy.iter().skip(start as usize).take(end.saturating_sub(start) as usize) {
+            // Skip anyone who already joined again under the new layout, or was moved by an earlier batch
+            if Self::has_joined(env.clone(), address.clone()) {
+                continue;
+            }
+
+            let player = PlayerRecord {
+                address: address.clone(),
+                wins: legacy_player.wins,
+                losses: 0,
+                draws: 0,
+                goals_for: 0,
+                goals_against: 0,
+                rating: INITIAL_RATING,
+                joined_at: legacy_player.joined_at,
+                last_played: 0,
+            };
+            storage::set(env, &DataKey::Player(address.clone()), &player);
+            storage::set(env, &DataKey::PlayerWins(address.clone()), &legacy_player.wins);
+
+            let count = Self::get_player_count(env.clone());
+            storage::set(env, &DataKey::PlayerAt(count), &address);
+            storage::instance_set(env, &DataKey::PlayerCount, &(count + 1));
+
+            Self::update_rating_index(env, &address, INITIAL_RATING);
+        }
+
+        log!(env, "Migrated legacy players {} to {}", start, end);
+        if end < legacy.len() {
+            return end;
+        }
+
+        storage::remove(env, &DataKey::Players);
+        0
+    }
+
+    /// Apply one side of a match result to a joined player's record
+    /// Returns the player's win count after the match
+    fn apply_result(
+        env: &Env,
+        player: &Address,
+        goals_for: u32,
+        goals_against: u32,
+        new_rating: u32,
+    ) -> u32 {
+        // Wins go through increment_wins so the win counter and season index stay in sync
+        let wins = if goals_for > goals_against {
+            Self::increment_wins(env, player)
+        } else {
+            Self::get_wins(env.clone(), player.clone())
+        };
+
+        let player_key = DataKey::Player(player.clone());
+        if let Some(mut player_data) = storage::get::<PlayerRecord>(env, &player_key) {
+            if goals_for < goals_against {
+                player_data.losses += 1;
+            } else if goals_for == goals_against {
+                player_data.draws += 1;
+            }
+            player_data.goals_for += goals_for;
+            player_data.goals_against += goals_against;
+            player_data.rating = new_rating;
+            player_data.last_played = env.ledger().timestamp();
+
+            storage::set(env, &player_key, &player_data);
+        }
+
+        Self::update_rating_index(env, player, new_rating);
+        env.events().publish(
+            (Symbol::new(env, "rating_changed"), player.clone()),
+            new_rating
+        );
+        Self::award_match_achievements(env, player, goals_for, goals_against);
+
+        wins
+    }
+
+    /// Award the achievements earned by one side of a recorded match
+    /// The contract only sees final scores, so a comeback is a win straight after a loss
+    fn award_match_achievements(env: &Env, player: &Address, goals_for: u32, goals_against: u32) {
+        if goals_for >= HAT_TRICK_GOALS {
+            Self::award(env, player, Achievement::HatTrick);
+        }
+
+        let lost_last_key = DataKey::LostLastMatch(player.clone());
+        if goals_for > goals_against {
+            if goals_against == 0 {
+                Self::award(env, player, Achievement::CleanSheet);
+            }
+            if storage::has(env, &lost_last_key) {
+                Self::award(env, player, Achievement::Comeback);
+            }
+        }
+
+        if goals_for < goals_against {
+            storage::set(env, &lost_last_key, &true);
+        } else {
+            storage::remove(env, &lost_last_key);
+        }
+    }
+
+    /// Award an achievement unless the player already has it
+    fn award(env: &Env, player: &Address, achievement: Achievement) {
+        let key = DataKey::Achievements(player.clone());
+        let mut badges: Vec<Badge> = storage::get(env, &key).unwrap_or(Vec::new(env));
+        if badges.iter().any(|badge| badge.achievement == achievement) {
+            return;
+        }
+
+        badges.push_back(Badge {
+            achievement,
+            awarded_at: env.ledger().timestamp(),
+        });
+        storage::set(env, &key, &badges);
+
+        env.events().publish(
+            (Symbol::new(env, "achievement_awarded"), player.clone()),
+            achievement
+        );
+    }
+
+    /// Increment a joined player's win count and return the new total
+    fn increment_wins(env: &Env, player: &Address) -> u32 {
+        // Get current win count
+        let current_wins: u32 =
+            storage::get(env, &DataKey::PlayerWins(player.clone())).unwrap_or(0);
+
+        let new_wins = current_wins + 1;
+
+        // Update win count
+        storage::set(env, &DataKey::PlayerWins(player.clone()), &new_wins);
+
+        // Update the player record
+        let player_key = DataKey::Player(player.clone());
+        if let Some(mut player_data) = storage::get::<PlayerRecord>(env, &player_key) {
+            player_data.wins = new_wins;
+            storage::set(env, &player_key, &player_data);
+        }
+
+        Self::award(env, player, Achievement::FirstWin);
+        if new_wins >= 10 {
+            Self::award(env, player, Achievement::TenWins);
+        }
+
+        // Count the win towards the season if one is in progress
+        if let Some(season) = Self::active_season(env) {
+            let season_key = DataKey::SeasonWins(season.id, player.clone());
+            let season_wins: u32 = storage::get(env, &season_key).unwrap_or(0) + 1;
+            storage::set(env, &season_key, &season_wins);
+
+            Self::update_top_index(
+                env,
+                &DataKey::SeasonTopPlayers(season.id),
+                player,
+                season_wins,
+            );
+        }
+
+        // Emit event for win addition
+        env.events().publish(
+            (Symbol::new(env, "win_added"), player.clone()),
+            new_wins
+        );
+
+        log!(env, "Win added for player: {} (total: {})", player, new_wins);
+        new_wins
+    }
+
+    /// Get the current season if its end time hasn't passed yet
+    fn active_season(env: &Env) -> Option<Season> {
+        let season: Season = storage::instance_get(env, &DataKey::CurrentSeason)?;
+        if env.ledger().timestamp() < season.ends_at {
+            Some(season)
+        } else {
+            None
+        }
+    }
+
+    /// Freeze a season's final standings into its archive
+    fn archive_season(env: &Env, season: u32) {
+        let live_key = DataKey::SeasonTopPlayers(season);
+        let top = Self::load_top_index(env, &live_key);
+
+        storage::set(env, &DataKey::SeasonArchive(season), &top);
+        storage::remove(env, &live_key);
+
+        env.events().publish(
+            (Symbol::new(env, "season_closed"), season),
+            top.len()
+        );
+    }
+
+    /// Truncate a sorted index to `limit` entries, 0 meaning no limit
+    fn apply_limit(top: Vec<WinsEntry>, limit: u32) -> Vec<WinsEntry> {
+        if limit == 0 || limit >= top.len() {
+            return top;
+        }
+
+        top.slice(0..limit)
+    }
+
+    /// Truncate a sorted wins index to `limit` entries and attach each player's rating and profile
+    fn to_leaderboard(env: &Env, top: Vec<WinsEntry>, limit: u32) -> Vec<LeaderboardEntry> {
+        let mut leaderboard = Vec::new(env);
+        for entry in Self::apply_limit(top, limit).iter() {
+            let rating = storage::get::<PlayerRecord>(env, &DataKey::Player(entry.address.clone()))
+                .map(|record| record.rating)
+                .unwrap_or(0);
+            leaderboard.push_back(Self::leaderboard_entry(env, entry.address, entry.wins, rating));
+        }
+        leaderboard
+    }
+
+    /// Leaderboard row for a player, with their profile attached
+    fn leaderboard_entry(env: &Env, address: Address, wins: u32, rating: u32) -> LeaderboardEntry {
+        let profile = Self::get_profile(env.clone(), address.clone());
+        LeaderboardEntry {
+            address,
+            wins,
+            rating,
+            display_name: profile.as_ref().map(|profile| profile.display_name.clone()),
+            avatar_id: profile.as_ref().map(|profile| profile.avatar_id),
+            country: profile.map(|profile| profile.country),
+        }
+    }
+
+    /// Whether a country is empty or an ISO 3166-1 alpha-2 style code (two uppercase letters)
+    fn is_country_code(country: &String) -> bool {
+        match country.len() {
+            0 => true,
+            2 => {
+                let mut code = [0u8; 2];
+                country.copy_into_slice(&mut code);
+                code.iter().all(u8::is_ascii_uppercase)
+            }
+            _ => false,
+        }
+    }
+
+    /// Load a sorted top-N leaderboard index
+    fn load_top_index(env: &Env, key: &DataKey) -> Vec<WinsEntry> {
+        storage::get(env, key).unwrap_or(Vec::new(env))
+    }
+
+    /// Move a player to their new position in a top-N index
+    /// Wins only ever increase, so a single pass over the bounded index is enough
+    fn update_top_index(env: &Env, key: &DataKey, player: &Address, wins: u32) {
+        let mut top = Self::load_top_index(env, key);
+
+        if let Some(index) = top.iter().position(|entry| entry.address == *player) {
+            top.remove(index as u32);
+        }
+
+        // Players with equal wins keep their existing order, so insert after them
+        let position = top
+            .iter()
+            .position(|entry| entry.wins < wins)
+            .map(|index| index as u32)
+            .unwrap_or(top.len());
+
+        if position >= MAX_LEADERBOARD_SIZE {
+            return;
+        }
+
+        top.insert(
+            position,
+            WinsEntry {
+                address: player.clone(),
+                wins,
+            },
+        );
+
+        if top.len() > MAX_LEADERBOARD_SIZE {
+            top.pop_back();
+        }
+
+        storage::set(env, key, &top);
+    }
+
+    /// Load the sorted top-N rating index
+    fn load_rating_index(env: &Env) -> Vec<RatingEntry> {
+        storage::get(env, &DataKey::TopRated).unwrap_or(Vec::new(env))
+    }
+
+    /// Move a player to their new position in the top-N rating index
+    /// Unlike wins, ratings can drop, so a player who falls to the bottom of a full
+    /// index is evicted: players outside the index may now rank above them. They
+    /// re-enter the next time a match lifts them back into the top-N.
+    fn update_rating_index(env: &Env, player: &Address, rating: u32) {
+        let mut rated = Self::load_rating_index(env);
+        let was_full = rated.len() >= MAX_LEADERBOARD_SIZE;
+
+        let existing = rated.iter().position(|entry| entry.address == *player);
+        let mut dropped = false;
+        if let Some(index) = existing {
+            dropped = rating < rated.get(index as u32).unwrap().rating;
+            rated.remove(index as u32);
+        }
+
+        // Players with equal ratings keep their existing order, so insert after them
+        let position = rated
+            .iter()
+            .position(|entry| entry.rating < rating)
+            .map(|index| index as u32)
+            .unwrap_or(rated.len());
+
+        if existing.is_none() && position >= MAX_LEADERBOARD_SIZE {
+            return;
+        }
+
+        let evicted = was_full && dropped && position == rated.len();
+        if position < MAX_LEADERBOARD_SIZE && !evicted {
+            rated.insert(
+                position,
+                RatingEntry {
+                    address: player.clone(),
+                    rating,
+                },
+            );
+
+            if rated.len() > MAX_LEADERBOARD_SIZE {
+                rated.pop_back();
+            }
+        }
 
-        players.get(player)
+        storage::set(env, &DataKey::TopRated, &rated);
     }
 }
\ No newline at end of file
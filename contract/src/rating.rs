@@ -0,0 +1,104 @@
+// Integer Elo rating math
+//
+// Soroban contracts can't use floating point, so expected scores are computed
+// in fixed point with FIXED_ONE as 1.0.
+
+/// Rating every player starts with
+pub const INITIAL_RATING: u32 = 1200;
+
+/// Fixed rating used for the AI opponent
+pub const AI_RATING: u32 = 1200;
+
+/// Maximum rating change from a single match
+pub const K_FACTOR: i128 = 32;
+
+/// Rating differences beyond this are clamped, as in standard Elo tables
+pub const MAX_RATING_DIFF: u32 = 800;
+
+/// Fixed-point representation of 1.0
+pub const FIXED_ONE: i128 = 1_000_000_000;
+
+/// ln(10) in fixed point
+const LN_10: i128 = 2_302_585_093;
+
+/// 10^(q/4) in fixed point for q in 0..=8, covering every 100 rating points
+const QUARTER_POWERS_OF_10: [i128; 9] = [
+    1_000_000_000,
+    1_778_279_410,
+    3_162_277_660,
+    5_623_413_252,
+    10_000_000_000,
+    17_782_794_100,
+    31_622_776_602,
+    56_234_132_519,
+    100_000_000_000,
+];
+
+/// Match outcome from the rated player's point of view
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    /// Actual score in fixed point: 1 for a win, 0.5 for a draw, 0 for a loss
+    fn score(self) -> i128 {
+        match self {
+            Outcome::Win => FIXED_ONE,
+            Outcome::Draw => FIXED_ONE / 2,
+            Outcome::Loss => 0,
+        }
+    }
+}
+
+/// 10^(diff/400) in fixed point, for diff in 0..=MAX_RATING_DIFF
+fn pow10_rating_diff(diff: u32) -> i128 {
+    let quarter = (diff / 100) as usize;
+    let rest = (diff % 100) as i128;
+
+    // e^x for x = rest * ln(10) / 400 < 0.58, where the series converges fast
+    let x = rest * LN_10 / 400;
+    let mut term = FIXED_ONE;
+    let mut sum = FIXED_ONE;
+    for n in 1..=12 {
+        term = term * x / (n * FIXED_ONE);
+        sum += term;
+    }
+
+    QUARTER_POWERS_OF_10[quarter] * sum / FIXED_ONE
+}
+
+/// Expected score of a player rated `rating` against `opponent_rating`, in fixed point
+pub fn expected_score(rating: u32, opponent_rating: u32) -> i128 {
+    let diff = rating.abs_diff(opponent_rating).min(MAX_RATING_DIFF);
+    let power = pow10_rating_diff(diff);
+
+    // E = 1 / (1 + 10^((Rb - Ra) / 400))
+    if opponent_rating >= rating {
+        FIXED_ONE * FIXED_ONE / (FIXED_ONE + power)
+    } else {
+        power * FIXED_ONE / (FIXED_ONE + power)
+    }
+}
+
+/// Rating change for a player after a match, rounded to the nearest point
+pub fn rating_delta(rating: u32, opponent_rating: u32, outcome: Outcome) -> i32 {
+    let change = K_FACTOR * (outcome.score() - expected_score(rating, opponent_rating));
+
+    // Round half away from zero
+    let half = FIXED_ONE / 2;
+    let rounded = if change >= 0 {
+        (change + half) / FIXED_ONE
+    } else {
+        (change - half) / FIXED_ONE
+    };
+    rounded as i32
+}
+
+/// New rating for a player after a match, never dropping below zero
+pub fn updated_rating(rating: u32, opponent_rating: u32, outcome: Outcome) -> u32 {
+    let delta = rating_delta(rating, opponent_rating, outcome);
+    rating.saturating_add_signed(delta)
+}
@@ -0,0 +1,79 @@
+// Storage access with TTL extension
+//
+// Every read or write of a live entry pushes its TTL back out, so active players
+// never get archived. Inactive players, tournaments and wagers are kept alive by
+// the bump_all maintenance entrypoint.
+
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+
+use crate::DataKey;
+
+/// Ledgers closed per day at ~5 seconds per ledger
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Contract instance (admin, counters, current season) is extended to 30 days
+pub const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Persistent entries (players, indexes, matches) are extended to 90 days
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 90 * DAY_IN_LEDGERS;
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - 7 * DAY_IN_LEDGERS;
+
+/// Extend the contract instance TTL
+pub fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Extend a persistent entry's TTL if it exists
+pub fn extend(env: &Env, key: &DataKey) -> bool {
+    let exists = env.storage().persistent().has(key);
+    if exists {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    exists
+}
+
+/// Read a persistent entry, extending its TTL
+pub fn get<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    value
+}
+
+/// Write a persistent entry, extending its TTL
+pub fn set<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+/// Check whether a persistent entry exists, extending its TTL if it does
+pub fn has(env: &Env, key: &DataKey) -> bool {
+    extend(env, key)
+}
+
+/// Remove a persistent entry
+pub fn remove(env: &Env, key: &DataKey) {
+    env.storage().persistent().remove(key);
+}
+
+/// Read an instance entry, extending the instance TTL
+pub fn instance_get<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    extend_instance(env);
+    env.storage().instance().get(key)
+}
+
+/// Write an instance entry, extending the instance TTL
+pub fn instance_set<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().instance().set(key, value);
+    extend_instance(env);
+}
@@ -1,12 +1,19 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, EnvTestConfig, Events, Ledger, MockAuth, MockAuthInvoke,
+    },
+    vec, Address, Env, IntoVal, String, Symbol,
+};
 
 #[test]
 fn test_join_and_has_joined() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, StellarHeadsLeaderboard);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
     let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
 
     let player = Address::generate(&env);
@@ -18,8 +25,7 @@ fn test_join_and_has_joined() {
     env.mock_all_auths();
 
     // Join the leaderboard
-    let result = client.join(&player);
-    assert!(result.is_ok());
+    client.join(&player);
 
     // Player should now be joined
     assert!(client.has_joined(&player));
@@ -32,7 +38,8 @@ fn test_join_and_has_joined() {
 #[test]
 fn test_add_win_and_get_wins() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, StellarHeadsLeaderboard);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
     let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
 
     let player = Address::generate(&env);
@@ -49,7 +56,7 @@ fn test_add_win_and_get_wins() {
 
     // Add a win
     let new_wins = client.add_win(&player);
-    assert_eq!(new_wins, Ok(1));
+    assert_eq!(new_wins, 1);
 
     // Check wins
     assert_eq!(client.get_wins(&player), 1);
@@ -57,14 +64,15 @@ fn test_add_win_and_get_wins() {
 
     // Add another win
     let new_wins = client.add_win(&player);
-    assert_eq!(new_wins, Ok(2));
+    assert_eq!(new_wins, 2);
     assert_eq!(client.get_wins(&player), 2);
 }
 
 #[test]
 fn test_leaderboard() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, StellarHeadsLeaderboard);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
     let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
 
     let player1 = Address::generate(&env);
@@ -79,13 +87,13 @@ fn test_leaderboard() {
     client.join(&player2);
     client.join(&player3);
 
-    // Add different number of wins
-    client.add_win(&player1); // 1 win
-    client.add_win(&player2); // 1 win
-    client.add_win(&player2); // 2 wins total
-    client.add_win(&player3); // 1 win
-    client.add_win(&player3); // 2 wins
-    client.add_win(&player3); // 3 wins total
+    // Unrated wins pile up without moving player1's rating
+    client.add_win(&player1);
+    client.add_win(&player1);
+    client.add_win(&player1);
+
+    // A single rated win lifts player2 above them
+    client.record_match(&player2, &Some(player3.clone()), &String::from_str(&env, "session-1"), &2, &0);
 
     // Check player count
     assert_eq!(client.get_player_count(), 3);
@@ -94,10 +102,13 @@ fn test_leaderboard() {
     let leaderboard = client.get_leaderboard(&10);
     assert_eq!(leaderboard.len(), 3);
 
-    // Should be sorted by wins (descending)
-    assert_eq!(leaderboard.get(0).unwrap().wins, 3); // player3
-    assert_eq!(leaderboard.get(1).unwrap().wins, 2); // player2
-    assert_eq!(leaderboard.get(2).unwrap().wins, 1); // player1
+    // Should be sorted by rating (descending), not wins
+    let first = leaderboard.get(0).unwrap();
+    assert_eq!((first.address, first.rating, first.wins), (player2, 1216, 1));
+    let second = leaderboard.get(1).unwrap();
+    assert_eq!((second.address, second.rating, second.wins), (player1, rating::INITIAL_RATING, 3));
+    let third = leaderboard.get(2).unwrap();
+    assert_eq!((third.address, third.rating, third.wins), (player3, 1184, 0));
 
     // Test with limit
     let limited_leaderboard = client.get_leaderboard(&2);
@@ -107,7 +118,8 @@ fn test_leaderboard() {
 #[test]
 fn test_get_all_players() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, StellarHeadsLeaderboard);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
     let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
 
     let player1 = Address::generate(&env);
@@ -132,7 +144,8 @@ fn test_get_all_players() {
 #[test]
 fn test_add_win_without_joining() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, StellarHeadsLeaderboard);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
     let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
 
     let player = Address::generate(&env);
@@ -143,4 +156,1602 @@ fn test_add_win_without_joining() {
     // Try to add win without joining first - should return error
     let result = client.try_add_win(&player);
     assert!(result.is_err());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_constructor_sets_admin() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    // The admin is set as part of deployment, so there's no call left to front-run
+    assert_eq!(client.get_admin(), Some(admin));
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+}
+
+#[test]
+fn test_record_match_updates_wins() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+    let opponent = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.join(&player);
+    client.join(&opponent);
+
+    // Win against the AI
+    let wins = client.record_match(&player, &None, &String::from_str(&env, "session-1"), &3, &1);
+    assert_eq!(wins, 1);
+
+    // Loss against another player credits the opponent
+    let wins = client.record_match(
+        &player,
+        &Some(opponent.clone()),
+        &String::from_str(&env, "session-2"),
+        &0,
+        &2,
+    );
+    assert_eq!(wins, 1);
+    assert_eq!(client.get_wins(&opponent), 1);
+
+    // Draws don't change win counts
+    client.record_match(&player, &None, &String::from_str(&env, "session-3"), &2, &2);
+    assert_eq!(client.get_wins(&player), 1);
+}
+
+#[test]
+fn test_record_match_requires_admin() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+    let session_id = String::from_str(&env, "session-1");
+
+    env.mock_all_auths();
+    client.join(&player);
+
+    // Only the player signs - the oracle's auth is missing
+    env.mock_auths(&[MockAuth {
+        address: &player,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "record_match",
+            args: (player.clone(), None::<Address>, session_id.clone(), 3u32, 0u32).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    let result = client.try_record_match(&player, &None, &session_id, &3, &0);
+    assert!(result.is_err());
+
+    // Self-reported wins are rejected as well
+    env.mock_auths(&[MockAuth {
+        address: &player,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "add_win",
+            args: (player.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    let result = client.try_add_win(&player);
+    assert!(result.is_err());
+
+    assert_eq!(client.get_wins(&player), 0);
+}
+
+#[test]
+fn test_record_match_rejects_duplicate_session() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+    let session_id = String::from_str(&env, "session-1");
+
+    env.mock_all_auths();
+    client.join(&player);
+
+    client.record_match(&player, &None, &session_id, &3, &0);
+
+    // Replaying the same session must not add another win
+    let result = client.try_record_match(&player, &None, &session_id, &3, &0);
+    assert_eq!(result, Err(Ok(Error::SessionAlreadyRecorded)));
+    assert_eq!(client.get_wins(&player), 1);
+}
+
+#[test]
+fn test_record_match_updates_player_stats() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+    let opponent = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.join(&player);
+    client.join(&opponent);
+
+    // Player isn't in any matches yet
+    let stats = client.get_player_stats(&player).unwrap();
+    assert_eq!(stats.total_games, 0);
+    assert_eq!(stats.last_played, 0);
+
+    env.ledger().set_timestamp(1_000);
+    client.record_match(&player, &Some(opponent.clone()), &String::from_str(&env, "session-1"), &3, &1);
+    client.record_match(&player, &Some(opponent.clone()), &String::from_str(&env, "session-2"), &0, &2);
+    env.ledger().set_timestamp(2_000);
+    client.record_match(&player, &None, &String::from_str(&env, "session-3"), &2, &2);
+
+    let stats = client.get_player_stats(&player).unwrap();
+    assert_eq!(stats.total_games, 3);
+    assert_eq!(stats.wins, 1);
+    assert_eq!(stats.losses, 1);
+    assert_eq!(stats.draws, 1);
+    assert_eq!(stats.goals_for, 5);
+    assert_eq!(stats.goals_against, 5);
+    assert_eq!(stats.last_played, 2_000);
+
+    // The opponent gets the mirrored result, but not the AI match
+    let stats = client.get_player_stats(&opponent).unwrap();
+    assert_eq!(stats.total_games, 2);
+    assert_eq!(stats.wins, 1);
+    assert_eq!(stats.losses, 1);
+    assert_eq!(stats.draws, 0);
+    assert_eq!(stats.goals_for, 3);
+    assert_eq!(stats.goals_against, 3);
+    assert_eq!(stats.last_played, 1_000);
+
+    // The full result is kept per session
+    let record = client.get_match(&String::from_str(&env, "session-2")).unwrap();
+    assert_eq!(record.player, player);
+    assert_eq!(record.opponent, Some(opponent.clone()));
+    assert_eq!(record.score, 0);
+    assert_eq!(record.opponent_score, 2);
+    assert_eq!(record.played_at, 1_000);
+
+    assert!(client.get_player_stats(&Address::generate(&env)).is_none());
+}
+
+#[test]
+fn test_record_match_emits_event() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+    let session_id = String::from_str(&env, "session-1");
+
+    env.mock_all_auths();
+    client.join(&player);
+
+    client.record_match(&player, &None, &session_id, &1, &1);
+
+    let (event_contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(event_contract, contract_id);
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "match_recorded"), player.clone()).into_val(&env)
+    );
+
+    let record: MatchRecord = data.into_val(&env);
+    assert_eq!(record.session_id, session_id);
+    assert_eq!(record.player, player);
+    assert_eq!(record.opponent, None);
+    assert_eq!(record.score, 1);
+    assert_eq!(record.opponent_score, 1);
+}
+
+/// Distinct session id for the `n`th match of a test
+fn session_id(env: &Env, n: u32) -> String {
+    let digits = [1000, 100, 10, 1].map(|place| b'0' + (n / place % 10) as u8);
+    String::from_bytes(env, &digits)
+}
+
+#[test]
+fn test_leaderboard_index_is_bounded_and_sorted() {
+    // Skip the snapshot, it would hold every simulated player
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    // Give players up to six wins against the AI, so ratings differ
+    let total = MAX_LEADERBOARD_SIZE + 20;
+    let mut players = soroban_sdk::Vec::new(&env);
+    let mut matches = 0;
+    for i in 0..total {
+        let player = Address::generate(&env);
+        client.join(&player);
+        for _ in 0..(i % 7) {
+            client.record_match(&player, &None, &session_id(&env, matches), &1, &0);
+            matches += 1;
+        }
+        players.push_back(player);
+    }
+
+    assert_eq!(client.get_player_count(), total);
+    assert_eq!(client.get_all_players(), players);
+
+    let leaderboard = client.get_leaderboard(&0);
+    assert_eq!(leaderboard.len(), MAX_LEADERBOARD_SIZE);
+    for i in 1..leaderboard.len() {
+        assert!(leaderboard.get(i - 1).unwrap().rating >= leaderboard.get(i).unwrap().rating);
+    }
+    assert_eq!(leaderboard.get(0).unwrap().wins, 6);
+
+    // A late climber enters the index once they pass the lowest entry
+    let climber = players.get(0).unwrap();
+    for _ in 0..10 {
+        client.record_match(&climber, &None, &session_id(&env, matches), &1, &0);
+        matches += 1;
+    }
+    let leaderboard = client.get_leaderboard(&1);
+    assert_eq!(leaderboard.get(0).unwrap().address, climber);
+    assert_eq!(leaderboard.get(0).unwrap().wins, 10);
+}
+
+#[test]
+fn test_new_players_rank_above_players_who_lost() {
+    // Skip the snapshot, it would hold a full index of players
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    // A loss to the AI drops a player below the initial rating
+    let loser = Address::generate(&env);
+    client.join(&loser);
+    client.record_match(&loser, &None, &session_id(&env, 0), &0, &1);
+    assert_eq!(client.get_rating(&loser), 1184);
+
+    for i in 1..MAX_LEADERBOARD_SIZE - 1 {
+        let player = Address::generate(&env);
+        client.join(&player);
+        client.record_match(&player, &None, &session_id(&env, i), &0, &1);
+    }
+
+    // A newcomer joins above everyone who lost, filling the index
+    let newcomer = Address::generate(&env);
+    client.join(&newcomer);
+    let leaderboard = client.get_leaderboard(&0);
+    assert_eq!(leaderboard.len(), MAX_LEADERBOARD_SIZE);
+    assert_eq!(leaderboard.get(0).unwrap().address, newcomer);
+    assert_eq!(leaderboard.get(0).unwrap().rating, rating::INITIAL_RATING);
+    assert_eq!(leaderboard.get(1).unwrap().address, loser);
+    assert_eq!(leaderboard.get(1).unwrap().rating, 1184);
+
+    // A full index still admits a newcomer rated above its lowest entry
+    let late = Address::generate(&env);
+    client.join(&late);
+    let leaderboard = client.get_leaderboard(&0);
+    assert_eq!(leaderboard.len(), MAX_LEADERBOARD_SIZE);
+    assert_eq!(leaderboard.get(0).unwrap().address, newcomer);
+    assert_eq!(leaderboard.get(1).unwrap().address, late);
+    for i in 1..leaderboard.len() {
+        assert!(leaderboard.get(i - 1).unwrap().rating >= leaderboard.get(i).unwrap().rating);
+    }
+}
+
+#[test]
+fn test_budget_with_thousands_of_players() {
+    // Skip the snapshot, it would hold every simulated player
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let mut last = Address::generate(&env);
+    for i in 0..3_000u32 {
+        last = Address::generate(&env);
+        client.join(&last);
+        if i % 10 == 0 {
+            client.add_win(&last);
+        }
+    }
+    assert_eq!(client.get_player_count(), 3_000);
+
+    // The budget is reset before every top-level invocation, so these
+    // measure a single call against the full player base
+    client.add_win(&last);
+    let add_win_cpu = env.cost_estimate().budget().cpu_instruction_cost();
+
+    client.get_leaderboard(&10);
+    let leaderboard_cpu = env.cost_estimate().budget().cpu_instruction_cost();
+
+    client.join(&Address::generate(&env));
+    let join_cpu = env.cost_estimate().budget().cpu_instruction_cost();
+
+    // The test host keeps the whole ledger in one storage map, so these figures
+    // still grow with the player count; on the network only the footprint is
+    // loaded. Either way they stay under the 100M instruction limit.
+    let limit = 100_000_000;
+    assert!(add_win_cpu < limit, "add_win used {} instructions", add_win_cpu);
+    assert!(leaderboard_cpu < limit, "get_leaderboard used {} instructions", leaderboard_cpu);
+    assert!(join_cpu < limit, "join used {} instructions", join_cpu);
+}
+
+#[test]
+fn test_seasons_track_wins_separately() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.join(&player1);
+    client.join(&player2);
+
+    // Wins before any season only count towards the all-time leaderboard
+    client.add_win(&player1);
+
+    env.ledger().set_timestamp(100);
+    client.start_season(&1, &1_000);
+    assert_eq!(client.get_current_season().unwrap().id, 1);
+
+    client.add_win(&player2);
+    client.add_win(&player2);
+    client.add_win(&player1);
+
+    assert_eq!(client.get_season_wins(&1, &player1), 1);
+    assert_eq!(client.get_season_wins(&1, &player2), 2);
+    assert_eq!(client.get_wins(&player1), 2);
+
+    let season = client.get_leaderboard_for_season(&1, &0);
+    assert_eq!(season.len(), 2);
+    assert_eq!(season.get(0).unwrap().address, player2);
+    assert_eq!(season.get(0).unwrap().wins, 2);
+
+    // Wins after the season ends don't change its standings
+    env.ledger().set_timestamp(1_000);
+    client.add_win(&player1);
+    client.add_win(&player1);
+    assert_eq!(client.get_season_wins(&1, &player1), 1);
+
+    // Rolling over archives the final standings
+    client.start_season(&2, &2_000);
+    client.add_win(&player1);
+
+    let archived = client.get_leaderboard_for_season(&1, &1);
+    assert_eq!(archived.len(), 1);
+    assert_eq!(archived.get(0).unwrap().address, player2);
+    assert_eq!(archived.get(0).unwrap().wins, 2);
+
+    let season = client.get_leaderboard_for_season(&2, &0);
+    assert_eq!(season.len(), 1);
+    assert_eq!(season.get(0).unwrap().address, player1);
+    assert_eq!(season.get(0).unwrap().wins, 1);
+
+    assert_eq!(client.get_leaderboard_for_season(&3, &0).len(), 0);
+}
+
+#[test]
+fn test_start_season_validation() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    env.ledger().set_timestamp(500);
+
+    // The season must end in the future
+    let result = client.try_start_season(&1, &500);
+    assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+    client.start_season(&2, &1_000);
+
+    // Season ids only move forward
+    let result = client.try_start_season(&2, &2_000);
+    assert_eq!(result, Err(Ok(Error::InvalidSeason)));
+    let result = client.try_start_season(&1, &2_000);
+    assert_eq!(result, Err(Ok(Error::InvalidSeason)));
+
+    // Only the oracle can start seasons
+    env.mock_auths(&[MockAuth {
+        address: &player,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "start_season",
+            args: (3u32, 2_000u64).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    let result = client.try_start_season(&3, &2_000);
+    assert!(result.is_err());
+    assert_eq!(client.get_current_season().unwrap().id, 2);
+}
+
+#[test]
+fn test_elo_expected_score_matches_reference_values() {
+    use crate::rating::{expected_score, FIXED_ONE};
+
+    // Reference values from the floating point formula 1 / (1 + 10^((Rb - Ra) / 400))
+    let cases = [
+        (1500, 1500, 500_000_000),
+        (1600, 1400, 759_746_927),
+        (1400, 1600, 240_253_073),
+        (1200, 1800, 30_653_430),
+        (1537, 1490, 567_228_844),
+        // Differences beyond 800 points are clamped
+        (2400, 1000, 990_099_010),
+        (1000, 2400, 9_900_990),
+    ];
+
+    for (rating, opponent_rating, expected) in cases {
+        let actual = expected_score(rating, opponent_rating);
+        assert!(
+            (actual - expected).abs() <= 10,
+            "expected_score({}, {}) = {}, reference {}",
+            rating,
+            opponent_rating,
+            actual,
+            expected
+        );
+        // Both sides' expectations always sum to one
+        let total = actual + expected_score(opponent_rating, rating);
+        assert!((total - FIXED_ONE).abs() <= 2);
+    }
+}
+
+#[test]
+fn test_elo_rating_changes_match_reference_values() {
+    use crate::rating::{rating_delta, updated_rating, Outcome};
+
+    assert_eq!(rating_delta(1500, 1500, Outcome::Win), 16);
+    assert_eq!(rating_delta(1500, 1500, Outcome::Draw), 0);
+    assert_eq!(rating_delta(1500, 1500, Outcome::Loss), -16);
+
+    // Favourites gain little for winning and lose a lot for losing
+    assert_eq!(rating_delta(1600, 1400, Outcome::Win), 8);
+    assert_eq!(rating_delta(1600, 1400, Outcome::Draw), -8);
+    assert_eq!(rating_delta(1600, 1400, Outcome::Loss), -24);
+    assert_eq!(rating_delta(1400, 1600, Outcome::Draw), 8);
+
+    assert_eq!(rating_delta(1200, 1800, Outcome::Win), 31);
+    assert_eq!(rating_delta(1200, 1800, Outcome::Loss), -1);
+    assert_eq!(rating_delta(2400, 1000, Outcome::Win), 0);
+
+    // Ratings never go negative
+    assert_eq!(updated_rating(10, 10, Outcome::Loss), 0);
+    assert_eq!(updated_rating(1537, 1490, Outcome::Loss), 1519);
+}
+
+#[test]
+fn test_record_match_updates_ratings() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+    let opponent = Address::generate(&env);
+    let grinder = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.join(&player);
+    client.join(&opponent);
+    client.join(&grinder);
+
+    assert_eq!(client.get_rating(&player), rating::INITIAL_RATING);
+    assert_eq!(client.get_rating(&Address::generate(&env)), 0);
+
+    // Evenly matched players move by half the K-factor
+    client.record_match(&player, &Some(opponent.clone()), &String::from_str(&env, "session-1"), &3, &1);
+
+    // Each side's new rating is published for off-chain indexers
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| *topics == (Symbol::new(&env, "rating_changed"), opponent.clone()).into_val(&env))
+        .unwrap();
+    let published: u32 = data.into_val(&env);
+    assert_eq!(published, 1184);
+
+    assert_eq!(client.get_rating(&player), 1216);
+    assert_eq!(client.get_rating(&opponent), 1184);
+
+    // Beating the AI pays less and less as the rating climbs
+    let mut previous_gain = i64::MAX;
+    for i in 0..20u32 {
+        let before = client.get_rating(&grinder) as i64;
+        let session = String::from_bytes(&env, &[b'a', b'i', b'-', b'a' + i as u8]);
+        client.record_match(&grinder, &None, &session, &5, &0);
+        let gain = client.get_rating(&grinder) as i64 - before;
+        assert!(gain <= previous_gain);
+        previous_gain = gain;
+    }
+    assert!(previous_gain < 16);
+    assert_eq!(client.get_wins(&grinder), 20);
+
+    // The rating leaderboard is sorted by rating, not wins
+    let rated = client.get_rating_leaderboard(&0);
+    assert_eq!(rated.len(), 3);
+    assert_eq!(rated.get(0).unwrap().address, grinder);
+    assert_eq!(rated.get(1).unwrap().address, player);
+    assert_eq!(rated.get(1).unwrap().rating, 1216);
+    assert_eq!(rated.get(2).unwrap().address, opponent);
+    assert_eq!(client.get_rating_leaderboard(&1).len(), 1);
+
+    assert_eq!(client.get_player_stats(&player).unwrap().rating, 1216);
+}
+
+/// Smallest module the host accepts as contract code: an empty WASM module
+/// carrying the `contractenvmetav0` interface version section
+fn empty_contract_wasm() -> [u8; 40] {
+    let mut wasm = [0u8; 40];
+    let header = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    // Custom section: id 0, 30 bytes, 17 byte name
+    let section = [0x00, 30, 17];
+    let name = b"contractenvmetav0";
+    // ScEnvMetaEntry::InterfaceVersion { protocol: 22, pre_release: 0 }
+    let meta = [0, 0, 0, 0, 0, 0, 0, 22, 0, 0, 0, 0];
+
+    wasm[..8].copy_from_slice(&header);
+    wasm[8..11].copy_from_slice(&section);
+    wasm[11..28].copy_from_slice(name);
+    wasm[28..].copy_from_slice(&meta);
+    wasm
+}
+
+#[test]
+fn test_set_admin() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let new_admin = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.join(&player);
+
+    client.set_admin(&new_admin);
+    assert_eq!(client.get_admin(), Some(new_admin.clone()));
+
+    // The previous admin can no longer record results
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "add_win",
+            args: (player.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_add_win(&player).is_err());
+
+    // Nor hand the role back to themselves
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "set_admin",
+            args: (admin.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_set_admin(&admin).is_err());
+    assert_eq!(client.get_admin(), Some(new_admin));
+}
+
+#[test]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let attacker = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let wasm_hash = env.deployer().upload_contract_wasm(empty_contract_wasm());
+
+    env.mock_auths(&[MockAuth {
+        address: &attacker,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "upgrade",
+            args: (wasm_hash.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_upgrade(&wasm_hash).is_err());
+
+    // The admin can swap the code
+    env.mock_all_auths();
+    client.upgrade(&wasm_hash);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "upgraded"),).into_val(&env));
+    let upgraded_to: soroban_sdk::BytesN<32> = data.into_val(&env);
+    assert_eq!(upgraded_to, wasm_hash);
+}
+
+#[test]
+fn test_migrate_from_players_map() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let veteran = Address::generate(&env);
+    let rookie = Address::generate(&env);
+
+    // Seed storage the way version 1 of the contract laid it out; it kept no version
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Version);
+        let mut players = soroban_sdk::Map::new(&env);
+        players.set(
+            veteran.clone(),
+            LegacyPlayer {
+                address: veteran.clone(),
+                wins: 7,
+                joined_at: 10,
+            },
+        );
+        players.set(
+            rookie.clone(),
+            LegacyPlayer {
+                address: rookie.clone(),
+                wins: 2,
+                joined_at: 20,
+            },
+        );
+        env.storage().persistent().set(&DataKey::Players, &players);
+        env.storage().persistent().set(&DataKey::PlayerWins(veteran.clone()), &7u32);
+        env.storage().persistent().set(&DataKey::PlayerWins(rookie.clone()), &2u32);
+    });
+
+    env.mock_all_auths();
+    assert_eq!(client.get_version(), 1);
+    assert!(!client.has_joined(&veteran));
+
+    assert_eq!(client.try_migrate(&0, &0), Err(Ok(Error::InvalidInput)));
+
+    // One player per batch; the version only moves once the legacy map is drained
+    assert_eq!(client.migrate(&0, &1), 1);
+    assert_eq!(client.get_version(), 1);
+    assert_eq!(client.get_player_count(), 1);
+    assert_eq!(client.migrate(&1, &1), 0);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+
+    // Players are available under the new layout with their wins intact
+    assert!(client.has_joined(&veteran));
+    assert_eq!(client.get_player_count(), 2);
+    let player = client.get_player(&veteran).unwrap();
+    assert_eq!(player.wins, 7);
+    assert_eq!(player.joined_at, 10);
+    assert_eq!(player.rating, rating::INITIAL_RATING);
+    assert_eq!(client.get_wins(&rookie), 2);
+
+    // Both enter the leaderboard at the starting rating, keeping their wins
+    let leaderboard = client.get_leaderboard(&0);
+    assert_eq!(leaderboard.len(), 2);
+    for entry in leaderboard.iter() {
+        assert_eq!(entry.rating, rating::INITIAL_RATING);
+        assert_eq!(entry.wins, if entry.address == veteran { 7 } else { 2 });
+    }
+
+    // The legacy map is gone and the migration can't run twice
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::Players));
+    });
+    assert_eq!(client.try_migrate(&0, &MAX_MIGRATE_BATCH), Err(Ok(Error::AlreadyMigrated)));
+
+    // Migrated players keep playing as normal
+    client.add_win(&rookie);
+    assert_eq!(client.get_wins(&rookie), 3);
+}
+
+/// Version 1 of the contract as first deployed, with every player in one Players map
+/// Its keys and records encode exactly like the originals, so its storage is real v1 data
+mod v1 {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map};
+
+    #[derive(Clone)]
+    #[contracttype]
+    pub enum DataKey {
+        Players,
+        PlayerWins(Address),
+    }
+
+    #[derive(Clone)]
+    #[contracttype]
+    pub struct Player {
+        pub address: Address,
+        pub wins: u32,
+        pub joined_at: u64,
+    }
+
+    #[contract]
+    pub struct LeaderboardV1;
+
+    #[contractimpl]
+    impl LeaderboardV1 {
+        pub fn join(env: Env, player: Address) {
+            player.require_auth();
+
+            let mut players: Map<Address, Player> =
+                env.storage().persistent().get(&DataKey::Players).unwrap_or(Map::new(&env));
+            players.set(
+                player.clone(),
+                Player {
+                    address: player.clone(),
+                    wins: 0,
+                    joined_at: env.ledger().timestamp(),
+                },
+            );
+            env.storage().persistent().set(&DataKey::PlayerWins(player), &0u32);
+            env.storage().persistent().set(&DataKey::Players, &players);
+        }
+
+        pub fn add_win(env: Env, player: Address) -> u32 {
+            player.require_auth();
+
+            let mut players: Map<Address, Player> =
+                env.storage().persistent().get(&DataKey::Players).unwrap();
+            let mut record = players.get(player.clone()).unwrap();
+            record.wins += 1;
+            players.set(player.clone(), record.clone());
+            env.storage().persistent().set(&DataKey::PlayerWins(player), &record.wins);
+            env.storage().persistent().set(&DataKey::Players, &players);
+            record.wins
+        }
+    }
+}
+
+#[test]
+fn test_upgrade_from_v1_keeps_player_data() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100);
+
+    // Players join and win under the old code
+    let contract_id = env.register(v1::LeaderboardV1, ());
+    let old_client = v1::LeaderboardV1Client::new(&env, &contract_id);
+
+    let players = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let wins = [0u32, 3, 1];
+    for (player, player_wins) in players.iter().zip(wins) {
+        old_client.join(player);
+        for _ in 0..player_wins {
+            old_client.add_win(player);
+        }
+    }
+
+    // Put the current code over the v1 storage; the test host only runs native
+    // contracts, so the code swap goes through register_at instead of an uploaded WASM
+    let admin = Address::generate(&env);
+    env.register_at(&contract_id, StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    assert_eq!(client.get_admin(), Some(admin));
+    assert_eq!(client.get_version(), 1);
+
+    let mut batches = 1;
+    let mut start = client.migrate(&0, &2);
+    while start != 0 {
+        start = client.migrate(&start, &2);
+        batches += 1;
+    }
+    assert_eq!(batches, 2);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+
+    // Every v1 player survives with their wins and join time
+    assert_eq!(client.get_player_count(), 3);
+    for (player, player_wins) in players.iter().zip(wins) {
+        assert!(client.has_joined(player));
+        assert_eq!(client.get_wins(player), player_wins);
+
+        let record = client.get_player(player).unwrap();
+        assert_eq!(record.wins, player_wins);
+        assert_eq!(record.joined_at, 100);
+        assert_eq!(record.rating, rating::INITIAL_RATING);
+    }
+
+    // And keep playing under the new code
+    client.record_match(&players[0], &Some(players[1].clone()), &String::from_str(&env, "after-upgrade"), &2, &1);
+    assert_eq!(client.get_wins(&players[0]), 1);
+    assert_eq!(client.get_player(&players[1]).unwrap().losses, 1);
+}
+
+#[test]
+fn test_fresh_deployment_needs_no_migration() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    assert_eq!(client.try_migrate(&0, &MAX_MIGRATE_BATCH), Err(Ok(Error::AlreadyMigrated)));
+}
+
+fn persistent_ttl(env: &Env, contract_id: &Address, key: &DataKey) -> u32 {
+    env.as_contract(contract_id, || env.storage().persistent().get_ttl(key))
+}
+
+#[test]
+fn test_reads_and_writes_extend_ttl() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+    let player_key = DataKey::Player(player.clone());
+
+    env.mock_all_auths();
+    client.join(&player);
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &player_key),
+        storage::PERSISTENT_BUMP_AMOUNT
+    );
+
+    // Within the threshold a read leaves the TTL alone
+    env.ledger().with_mut(|li| li.sequence_number += storage::DAY_IN_LEDGERS);
+    client.get_player(&player);
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &player_key),
+        storage::PERSISTENT_BUMP_AMOUNT - storage::DAY_IN_LEDGERS
+    );
+
+    // Past it, reading the player pushes the TTL back out
+    env.ledger().with_mut(|li| li.sequence_number += 9 * storage::DAY_IN_LEDGERS);
+    client.get_player(&player);
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &player_key),
+        storage::PERSISTENT_BUMP_AMOUNT
+    );
+
+    // Instance storage is extended whenever instance entries are touched
+    client.get_player_count();
+    let instance_ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    assert_eq!(instance_ttl, storage::INSTANCE_BUMP_AMOUNT);
+}
+
+#[test]
+fn test_bump_all_extends_inactive_players() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let players = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+
+    env.mock_all_auths();
+    for player in players.iter() {
+        client.join(player);
+    }
+
+    env.ledger().with_mut(|li| li.sequence_number += 20 * storage::DAY_IN_LEDGERS);
+    let aged_ttl = storage::PERSISTENT_BUMP_AMOUNT - 20 * storage::DAY_IN_LEDGERS;
+
+    // First batch covers the first two players and reports where to continue
+    assert_eq!(client.bump_all(&0, &2), 2);
+    for player in players[..2].iter() {
+        assert_eq!(
+            persistent_ttl(&env, &contract_id, &DataKey::Player(player.clone())),
+            storage::PERSISTENT_BUMP_AMOUNT
+        );
+        assert_eq!(
+            persistent_ttl(&env, &contract_id, &DataKey::PlayerWins(player.clone())),
+            storage::PERSISTENT_BUMP_AMOUNT
+        );
+    }
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::Player(players[2].clone())),
+        aged_ttl
+    );
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::TopRated),
+        storage::PERSISTENT_BUMP_AMOUNT
+    );
+
+    // The last batch wraps the cursor back to 0
+    assert_eq!(client.bump_all(&2, &2), 0);
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::Player(players[2].clone())),
+        storage::PERSISTENT_BUMP_AMOUNT
+    );
+
+    assert_eq!(client.try_bump_all(&0, &0), Err(Ok(Error::InvalidInput)));
+    assert_eq!(
+        client.try_bump_all(&0, &(MAX_BUMP_BATCH + 1)),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_bump_all_extends_achievements_tournaments_and_wagers() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100);
+
+    let (token, asset) = create_token(&env);
+    let [winner, loser]: [Address; 2] = joined_players(&env, &client);
+    asset.mint(&winner, &1_000);
+    client.record_match(&winner, &Some(loser.clone()), &String::from_str(&env, "match-1"), &2, &0);
+    client.create_wager(&winner, &loser, &token.address, &100);
+    for _ in 0..3 {
+        client.create_tournament(&admin, &String::from_str(&env, "Cup"), &4, &1_000);
+    }
+
+    env.ledger().with_mut(|li| li.sequence_number += 20 * storage::DAY_IN_LEDGERS);
+    let aged_ttl = storage::PERSISTENT_BUMP_AMOUNT - 20 * storage::DAY_IN_LEDGERS;
+
+    // Three tournaments outnumber the two players, so a batch of two doesn't finish
+    assert_eq!(client.bump_all(&0, &2), 2);
+    for key in [
+        DataKey::Achievements(winner.clone()),
+        DataKey::LostLastMatch(loser.clone()),
+        DataKey::Wager(1),
+        DataKey::Tournament(2),
+    ] {
+        assert_eq!(persistent_ttl(&env, &contract_id, &key), storage::PERSISTENT_BUMP_AMOUNT);
+    }
+    assert_eq!(persistent_ttl(&env, &contract_id, &DataKey::Tournament(3)), aged_ttl);
+
+    assert_eq!(client.bump_all(&2, &2), 0);
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::Tournament(3)),
+        storage::PERSISTENT_BUMP_AMOUNT
+    );
+}
+
+#[test]
+fn test_bump_all_requires_admin() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let player = Address::generate(&env);
+
+    // Nobody has signed
+    assert!(client.try_bump_all(&0, &10).is_err());
+
+    env.mock_all_auths();
+
+    env.mock_auths(&[MockAuth {
+        address: &player,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "bump_all",
+            args: (0u32, 10u32).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_bump_all(&0, &10).is_err());
+}
+
+fn joined_players<const N: usize>(env: &Env, client: &StellarHeadsLeaderboardClient) -> [Address; N] {
+    core::array::from_fn(|_| {
+        let player = Address::generate(env);
+        client.join(&player);
+        player
+    })
+}
+
+#[test]
+fn test_tournament_bracket_advances_to_a_winner() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100);
+
+    let name = String::from_str(&env, "Weekly Cup");
+    let id = client.create_tournament(&organizer, &name, &4, &1_000);
+    assert_eq!(id, 1);
+
+    let players: [Address; 4] = joined_players(&env, &client);
+    for player in players.iter() {
+        client.register(player, &id);
+    }
+
+    // Filling the tournament draws the bracket: seed 1 vs 4, seed 2 vs 3
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "tournament_started"), id).into_val(&env));
+    let started_with: u32 = data.into_val(&env);
+    assert_eq!(started_with, 4);
+
+    let tournament = client.get_tournament(&id).unwrap();
+    assert_eq!(tournament.status, TournamentStatus::InProgress);
+
+    let bracket = client.get_bracket(&id);
+    assert_eq!(bracket.len(), 3);
+    let first = bracket.get(0).unwrap();
+    assert_eq!(first.player_a, Some(players[0].clone()));
+    assert_eq!(first.player_b, Some(players[3].clone()));
+    let second = bracket.get(1).unwrap();
+    assert_eq!(second.player_a, Some(players[1].clone()));
+    assert_eq!(second.player_b, Some(players[2].clone()));
+
+    // Semi-final winners advance into the final
+    client.report_result(&id, &1, &players[3], &players[0]);
+    client.report_result(&id, &1, &players[1], &players[2]);
+
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "tournament_result"), id, 1u32).into_val(&env)
+    );
+
+    let final_match = client.get_bracket(&id).get(2).unwrap();
+    assert_eq!(final_match.round, 2);
+    assert_eq!(final_match.player_a, Some(players[3].clone()));
+    assert_eq!(final_match.player_b, Some(players[1].clone()));
+
+    client.report_result(&id, &2, &players[1], &players[3]);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "tournament_won"), id).into_val(&env));
+    let champion: Address = data.into_val(&env);
+    assert_eq!(champion, players[1]);
+
+    let tournament = client.get_tournament(&id).unwrap();
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert_eq!(tournament.winner, Some(players[1].clone()));
+    assert_eq!(
+        client.try_report_result(&id, &2, &players[1], &players[3]),
+        Err(Ok(Error::InvalidMatch))
+    );
+}
+
+#[test]
+fn test_tournament_byes_after_entry_deadline() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100);
+
+    let id = client.create_tournament(&organizer, &String::from_str(&env, "Open"), &8, &1_000);
+    let players: [Address; 3] = joined_players(&env, &client);
+    for player in players.iter() {
+        client.register(player, &id);
+    }
+
+    // Until the deadline the bracket is only a preview
+    assert_eq!(
+        client.try_report_result(&id, &1, &players[1], &players[2]),
+        Err(Ok(Error::TournamentNotStarted))
+    );
+    let preview = client.get_bracket(&id);
+    assert_eq!(preview.len(), 3);
+    assert_eq!(preview.get(0).unwrap().winner, Some(players[0].clone()));
+
+    env.ledger().set_timestamp(1_000);
+    let late = Address::generate(&env);
+    client.join(&late);
+    assert_eq!(client.try_register(&late, &id), Err(Ok(Error::RegistrationClosed)));
+
+    // The top seed has a bye and waits in the final
+    client.report_result(&id, &1, &players[2], &players[1]);
+    let final_match = client.get_bracket(&id).get(2).unwrap();
+    assert_eq!(final_match.player_a, Some(players[0].clone()));
+    assert_eq!(final_match.player_b, Some(players[2].clone()));
+
+    client.report_result(&id, &2, &players[0], &players[2]);
+    assert_eq!(client.get_tournament(&id).unwrap().winner, Some(players[0].clone()));
+}
+
+#[test]
+fn test_tournament_validation() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let name = String::from_str(&env, "Cup");
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100);
+
+    assert_eq!(
+        client.try_create_tournament(&organizer, &name, &1, &1_000),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_create_tournament(&organizer, &name, &(MAX_TOURNAMENT_PLAYERS + 1), &1_000),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_create_tournament(&organizer, &name, &4, &100),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    let id = client.create_tournament(&organizer, &name, &4, &1_000);
+    assert_eq!(client.try_get_bracket(&99).err(), Some(Ok(Error::TournamentNotFound)));
+
+    let stranger = Address::generate(&env);
+    assert_eq!(client.try_register(&stranger, &id), Err(Ok(Error::NotJoined)));
+
+    let players: [Address; 3] = joined_players(&env, &client);
+    client.register(&players[0], &id);
+    assert_eq!(client.try_register(&players[0], &id), Err(Ok(Error::AlreadyRegistered)));
+    client.register(&players[1], &id);
+    client.register(&players[2], &id);
+
+    env.ledger().set_timestamp(1_000);
+
+    // Players who aren't paired in that round can't be reported
+    assert_eq!(
+        client.try_report_result(&id, &1, &players[0], &players[1]),
+        Err(Ok(Error::InvalidMatch))
+    );
+    assert_eq!(
+        client.try_report_result(&id, &3, &players[1], &players[2]),
+        Err(Ok(Error::InvalidMatch))
+    );
+
+    // Only the organizer can report results
+    env.mock_auths(&[MockAuth {
+        address: &players[1],
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "report_result",
+            args: (id, 1u32, players[1].clone(), players[2].clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_report_result(&id, &1, &players[1], &players[2]).is_err());
+}
+
+fn create_token<'a>(env: &Env) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+    let issuer = Address::generate(env);
+    let asset = env.register_stellar_asset_contract_v2(issuer);
+    (
+        token::Client::new(env, &asset.address()),
+        token::StellarAssetClient::new(env, &asset.address()),
+    )
+}
+
+#[test]
+fn test_wager_escrow_and_settlement() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let (token, asset) = create_token(&env);
+    let [challenger, opponent]: [Address; 2] = joined_players(&env, &client);
+    asset.mint(&challenger, &1_000);
+    asset.mint(&opponent, &1_000);
+
+    let id = client.create_wager(&challenger, &opponent, &token.address, &250);
+    assert_eq!(token.balance(&challenger), 750);
+    assert_eq!(token.balance(&contract_id), 250);
+
+    // The oracle can't settle before both stakes are in
+    assert_eq!(
+        client.try_settle_wager(&id, &Some(challenger.clone())),
+        Err(Ok(Error::InvalidWagerState))
+    );
+
+    client.accept_wager(&id);
+    assert_eq!(token.balance(&opponent), 750);
+    assert_eq!(token.balance(&contract_id), 500);
+    assert_eq!(client.get_wager(&id).unwrap().status, WagerStatus::Accepted);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_settle_wager(&id, &Some(outsider)),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    client.settle_wager(&id, &Some(opponent.clone()));
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (Symbol::new(&env, "wager_settled"), id).into_val(&env));
+    let winner: Option<Address> = data.into_val(&env);
+    assert_eq!(winner, Some(opponent.clone()));
+
+    assert_eq!(token.balance(&opponent), 1_250);
+    assert_eq!(token.balance(&challenger), 750);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    let wager = client.get_wager(&id).unwrap();
+    assert_eq!(wager.status, WagerStatus::Settled);
+    assert_eq!(
+        client.try_settle_wager(&id, &Some(opponent)),
+        Err(Ok(Error::InvalidWagerState))
+    );
+
+    // A drawn match returns each stake
+    let id = client.create_wager(&challenger, &wager.player_two, &token.address, &100);
+    client.accept_wager(&id);
+    client.settle_wager(&id, &None);
+    assert_eq!(token.balance(&challenger), 750);
+    assert_eq!(token.balance(&wager.player_two), 1_250);
+}
+
+#[test]
+fn test_wager_timeout_refunds() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let (token, asset) = create_token(&env);
+    let [challenger, opponent]: [Address; 2] = joined_players(&env, &client);
+    asset.mint(&challenger, &500);
+    asset.mint(&opponent, &500);
+
+    // An unanswered challenge returns the challenger's stake
+    let open = client.create_wager(&challenger, &opponent, &token.address, &200);
+    let accepted = client.create_wager(&challenger, &opponent, &token.address, &100);
+    client.accept_wager(&accepted);
+    assert_eq!(client.try_refund_wager(&open), Err(Ok(Error::WagerNotExpired)));
+
+    env.ledger().set_timestamp(1_000 + WAGER_TIMEOUT_SECS);
+    assert_eq!(client.try_accept_wager(&open), Err(Ok(Error::WagerExpired)));
+
+    // Once timed out, the oracle can't race the refund with a late settlement
+    assert_eq!(
+        client.try_settle_wager(&accepted, &Some(challenger.clone())),
+        Err(Ok(Error::WagerExpired))
+    );
+
+    client.refund_wager(&open);
+    assert_eq!(token.balance(&challenger), 400);
+
+    // An accepted wager the oracle never settled returns both stakes
+    client.refund_wager(&accepted);
+    assert_eq!(token.balance(&challenger), 500);
+    assert_eq!(token.balance(&opponent), 500);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    assert_eq!(client.get_wager(&accepted).unwrap().status, WagerStatus::Refunded);
+    assert_eq!(client.try_refund_wager(&accepted), Err(Ok(Error::InvalidWagerState)));
+    assert_eq!(client.try_settle_wager(&accepted, &None), Err(Ok(Error::InvalidWagerState)));
+}
+
+#[test]
+fn test_wager_validation() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let (token, asset) = create_token(&env);
+    let [challenger, opponent]: [Address; 2] = joined_players(&env, &client);
+    asset.mint(&challenger, &100);
+
+    assert_eq!(
+        client.try_create_wager(&challenger, &opponent, &token.address, &0),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_create_wager(&challenger, &challenger, &token.address, &10),
+        Err(Ok(Error::InvalidInput))
+    );
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_create_wager(&challenger, &stranger, &token.address, &10),
+        Err(Ok(Error::NotJoined))
+    );
+    assert_eq!(client.try_accept_wager(&7), Err(Ok(Error::WagerNotFound)));
+
+    // The opponent has to authorize accepting, and only the oracle can settle
+    let id = client.create_wager(&challenger, &opponent, &token.address, &10);
+    env.mock_auths(&[MockAuth {
+        address: &challenger,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "accept_wager",
+            args: (id,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_accept_wager(&id).is_err());
+
+    env.mock_auths(&[MockAuth {
+        address: &challenger,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "settle_wager",
+            args: (id, Some(challenger.clone())).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_settle_wager(&id, &Some(challenger.clone())).is_err());
+}
+
+fn achievement_ids(env: &Env, client: &StellarHeadsLeaderboardClient, player: &Address) -> Vec<Achievement> {
+    let mut ids = Vec::new(env);
+    for badge in client.get_achievements(player).iter() {
+        ids.push_back(badge.achievement);
+    }
+    ids
+}
+
+#[test]
+fn test_record_match_awards_achievements() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(500);
+
+    let [player, rival]: [Address; 2] = joined_players(&env, &client);
+    assert_eq!(client.get_achievements(&player).len(), 0);
+
+    // A narrow loss earns nothing
+    client.record_match(&player, &Some(rival.clone()), &String::from_str(&env, "s1"), &1, &2);
+    assert_eq!(client.get_achievements(&player).len(), 0);
+
+    // Bouncing back with a 3-0 win is a first win, clean sheet, comeback and hat-trick at once
+    client.record_match(&player, &Some(rival.clone()), &String::from_str(&env, "s2"), &3, &0);
+
+    let awarded_topics: Vec<soroban_sdk::Val> =
+        (Symbol::new(&env, "achievement_awarded"), player.clone()).into_val(&env);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| *topics == awarded_topics)
+        .unwrap();
+    let first: Achievement = data.into_val(&env);
+    assert_eq!(first, Achievement::FirstWin);
+
+    assert_eq!(
+        achievement_ids(&env, &client, &player),
+        vec![
+            &env,
+            Achievement::FirstWin,
+            Achievement::HatTrick,
+            Achievement::CleanSheet,
+            Achievement::Comeback,
+        ]
+    );
+    let badge = client.get_achievements(&player).get(0).unwrap();
+    assert_eq!(badge.awarded_at, 500);
+
+    // The opponent's side counts too, and badges are only awarded once
+    client.record_match(&player, &Some(rival.clone()), &String::from_str(&env, "s3"), &4, &5);
+    assert_eq!(
+        achievement_ids(&env, &client, &rival),
+        vec![&env, Achievement::FirstWin, Achievement::HatTrick, Achievement::Comeback]
+    );
+    assert_eq!(client.get_achievements(&player).len(), 4);
+}
+
+#[test]
+fn test_ten_wins_achievement() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let [player]: [Address; 1] = joined_players(&env, &client);
+    for _ in 0..9 {
+        client.add_win(&player);
+    }
+    assert_eq!(achievement_ids(&env, &client, &player), vec![&env, Achievement::FirstWin]);
+
+    client.add_win(&player);
+    assert_eq!(
+        achievement_ids(&env, &client, &player),
+        vec![&env, Achievement::FirstWin, Achievement::TenWins]
+    );
+}
+
+#[test]
+fn test_players_page_walks_join_order() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    let players: [Address; 5] = joined_players(&env, &client);
+
+    let first = client.get_players_page(&0, &2);
+    assert_eq!(first.total, 5);
+    assert_eq!(first.players.len(), 2);
+    assert_eq!(first.players.get(0).unwrap().address, players[0]);
+    assert_eq!(first.players.get(1).unwrap().address, players[1]);
+    assert_eq!(first.next_cursor, Some(2));
+
+    // Players joining mid-walk are appended and don't shift earlier pages
+    let late = Address::generate(&env);
+    client.join(&late);
+
+    let second = client.get_players_page(&2, &2);
+    assert_eq!(second.players.get(0).unwrap().address, players[2]);
+    assert_eq!(second.next_cursor, Some(4));
+
+    let last = client.get_players_page(&4, &2);
+    assert_eq!(last.players.get(0).unwrap().address, players[4]);
+    assert_eq!(last.players.get(1).unwrap().address, late);
+    assert_eq!(last.next_cursor, None);
+    assert_eq!(last.total, 6);
+
+    assert_eq!(client.get_players_page(&10, &2).players.len(), 0);
+    assert_eq!(client.try_get_players_page(&0, &0).err(), Some(Ok(Error::InvalidInput)));
+    assert_eq!(
+        client.try_get_players_page(&0, &(MAX_PAGE_SIZE + 1)).err(),
+        Some(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_batch_queries_keep_request_order() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let [first, second]: [Address; 2] = joined_players(&env, &client);
+    let stranger = Address::generate(&env);
+    client.add_win(&second);
+    client.add_win(&second);
+
+    let query = vec![&env, second.clone(), stranger.clone(), first.clone()];
+    assert_eq!(client.get_wins_batch(&query), vec![&env, 2u32, 0, 0]);
+
+    let records = client.get_players_batch(&query);
+    assert_eq!(records.len(), 3);
+    assert_eq!(records.get(0).unwrap().unwrap().wins, 2);
+    assert!(records.get(1).unwrap().is_none());
+    assert_eq!(records.get(2).unwrap().unwrap().address, first);
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_PAGE_SIZE {
+        too_many.push_back(first.clone());
+    }
+    assert_eq!(client.try_get_wins_batch(&too_many), Err(Ok(Error::InvalidInput)));
+    assert_eq!(client.try_get_players_batch(&too_many).err(), Some(Ok(Error::InvalidInput)));
+}
+
+#[test]
+fn test_profile_is_returned_with_player_and_leaderboard() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let [player, anonymous]: [Address; 2] = joined_players(&env, &client);
+    client.add_win(&player);
+
+    let name = String::from_str(&env, "Striker");
+    client.set_profile(&player, &name, &7, &String::from_str(&env, "BR"));
+
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "profile_updated"), player.clone()).into_val(&env)
+    );
+
+    let data = client.get_player(&player).unwrap();
+    assert_eq!(data.display_name, Some(name.clone()));
+    assert_eq!(data.avatar_id, Some(7));
+    assert_eq!(data.country, Some(String::from_str(&env, "BR")));
+    assert!(client.get_player(&anonymous).unwrap().display_name.is_none());
+
+    let leaderboard = client.get_leaderboard(&0);
+    let top = leaderboard.get(0).unwrap();
+    assert_eq!(top.address, player);
+    assert_eq!(top.display_name, Some(name));
+    assert_eq!(top.avatar_id, Some(7));
+    assert!(leaderboard.get(1).unwrap().display_name.is_none());
+
+    // Profiles can be replaced, and the country left out
+    let renamed = String::from_str(&env, "Keeper");
+    client.set_profile(&player, &renamed, &0, &String::from_str(&env, ""));
+    let profile = client.get_profile(&player).unwrap();
+    assert_eq!(profile.display_name, renamed);
+    assert_eq!(profile.country.len(), 0);
+}
+
+#[test]
+fn test_set_profile_validation() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(StellarHeadsLeaderboard, (&admin,));
+    let client = StellarHeadsLeaderboardClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    let [player]: [Address; 1] = joined_players(&env, &client);
+    let name = String::from_str(&env, "Striker");
+    let country = String::from_str(&env, "GB");
+
+    let invalid = [
+        (String::from_str(&env, ""), 1, country.clone()),
+        (String::from_str(&env, "a display name well over the limit"), 1, country.clone()),
+        (name.clone(), MAX_AVATAR_ID + 1, country.clone()),
+        (name.clone(), 1, String::from_str(&env, "GBR")),
+        (name.clone(), 1, String::from_str(&env, "gb")),
+    ];
+    for (display_name, avatar_id, country) in invalid.iter() {
+        assert_eq!(
+            client.try_set_profile(&player, display_name, avatar_id, country),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
+    assert!(client.get_profile(&player).is_none());
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_profile(&stranger, &name, &1, &country),
+        Err(Ok(Error::NotJoined))
+    );
+
+    // Only the player can set their own profile
+    let other: [Address; 1] = joined_players(&env, &client);
+    env.mock_auths(&[MockAuth {
+        address: &other[0],
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "set_profile",
+            args: (player.clone(), name.clone(), 1u32, country.clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_set_profile(&player, &name, &1, &country).is_err());
+}
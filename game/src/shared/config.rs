@@ -265,9 +265,11 @@ pub struct ScoringConfig {
 
 impl Default for ScoringConfig {
     fn default() -> Self {
+        // Shared with the backend, which rejects results these rules can't produce
+        let rules = shared::dto::game::MatchRules::default();
         Self {
-            winning_score: 5,
-            match_duration_seconds: 180.0, // 3 minutes
+            winning_score: rules.winning_score,
+            match_duration_seconds: rules.match_duration_seconds,
         }
     }
 }
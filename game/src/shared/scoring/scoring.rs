@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use crate::shared::{AppState, config::ScoringConfig};
-use shared::dto::game::{GameResult, MatchResult};
+use shared::dto::game::{GameBootstrap, GameResult, MatchResult, MatchRules};
+use shared::transcript::{GoalEvent, InputDigest, MatchTranscript, Side};
 
 // ================= HTTP Client for Direct Communication =================
 
@@ -14,21 +15,32 @@ pub struct PlayerInfo {
 
 // ================= Game Session Tracking =================
 
-gtgg#[derive(Resource)]
+/// The server session for the match in progress, from the page's bootstrap config
+#[derive(Resource)]
 pub struct GameSession {
     pub session_id: String,
+    /// Results are signed with it
+    pub session_key: Vec<u8>,
 }
 
-impl Default for GameSession {
-    fn default() -> Self {
-        Self::new()
-    }
+// ================= Match Transcript =================
+
+/// Goals and input recorded for the transcript the result is signed over
+#[derive(Resource, Default)]
+pub struct MatchRecorder {
+    pub goals: Vec<GoalEvent>,
+    pub inputs: InputDigest,
 }
 
-impl GameSession {
-    pub fn new() -> Self {
-        Self {
-            session_id: uuid::Uuid::new_v4().to_string(),
+impl MatchRecorder {
+    pub fn transcript(&self, session_id: String, score: &Score, duration_seconds: f32) -> MatchTranscript {
+        MatchTranscript {
+            session_id,
+            goals: self.goals.clone(),
+            player_score: score.left_team as i32,
+            opponent_score: score.right_team as i32,
+            duration_seconds,
+            input_digest: self.inputs.finish(),
         }
     }
 }
@@ -153,6 +165,8 @@ pub enum GoalTeam {
 
 pub fn handle_goal_scored(
     scoring_config: Res<ScoringConfig>,
+    timer: Res<GameTimer>,
+    mut recorder: ResMut<MatchRecorder>,
     mut score: ResMut<Score>,
     mut goal_events: EventReader<GoalScored>,
     mut notifications: ResMut<ScoreNotifications>,
@@ -172,6 +186,14 @@ pub fn handle_goal_scored(
         println!("🎯 PROCESSING GoalScored event for {:?} team at {:?}", event.scoring_team, event.goal_position);
 
         score.add_point(event.scoring_team.clone());
+        // Local player is the left team
+        recorder.goals.push(GoalEvent {
+            side: match event.scoring_team {
+                GoalTeam::Left => Side::Player,
+                GoalTeam::Right => Side::Opponent,
+            },
+            at_seconds: timer.match_duration - timer.remaining_time,
+        });
 
         #[cfg(not(target_arch = "wasm32"))]
         println!("🎯 ⚽ GOAL SCORED by {:?}! NEW SCORE: {} - {}", event.scoring_team, score.left_team, score.right_team);
@@ -206,6 +228,7 @@ pub fn handle_goal_scored(
 pub fn reset_score_system(
     mut score: ResMut<Score>,
     mut timer: ResMut<GameTimer>,
+    mut recorder: ResMut<MatchRecorder>,
     keyboard: Option<Res<ButtonInput<KeyCode>>>,
     mut notifications: ResMut<ScoreNotifications>,
     mut reset_events: EventWriter<PlayerReset>,
@@ -219,6 +242,7 @@ pub fn reset_score_system(
                     .push(ScoreNotification::new("🔄 MATCH RESET".to_string(), 2.0));
             }
             score.reset();
+            recorder.goals.clear();
             timer.remaining_time = timer.match_duration;
             timer.is_finished = false;
             reset_events.write(PlayerReset);
@@ -229,6 +253,49 @@ pub fn reset_score_system(
     }
 }
 
+/// Feed this frame's movement, jump and kick buttons into the input digest
+pub fn record_match_input(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mut recorder: ResMut<MatchRecorder>,
+) {
+    let Some(keys) = keys else {
+        return;
+    };
+
+    let buttons = [
+        keys.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]),
+        keys.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]),
+        keys.pressed(KeyCode::Space),
+        keys.pressed(KeyCode::KeyX),
+    ]
+    .iter()
+    .enumerate()
+    .fold(0u8, |mask, (bit, pressed)| mask | ((*pressed as u8) << bit));
+
+    recorder.inputs.record(buttons);
+}
+
+/// Take up the session the page started for this match
+pub fn apply_bootstrap(mut commands: Commands, mut player_info: ResMut<PlayerInfo>) {
+    // Only the newest matters if several arrived
+    let Some(bootstrap) = take_received_bootstraps().pop() else {
+        return;
+    };
+
+    let Ok(session_key) = hex::decode(&bootstrap.session_key) else {
+        println!("⚠️ Session key for {} isn't hex, ignoring it", bootstrap.session_id);
+        return;
+    };
+
+    println!("🎲 Game session {} issued by the server", bootstrap.session_id);
+    player_info.username = bootstrap.player_username;
+    player_info.wallet_address = bootstrap.player_wallet_address;
+    commands.insert_resource(GameSession {
+        session_id: bootstrap.session_id,
+        session_key,
+    });
+}
+
 pub fn game_timer_system(
     mut timer: ResMut<GameTimer>,
     mut match_events: EventWriter<MatchFinished>,
@@ -298,6 +365,7 @@ pub fn send_game_result_system(
     score: Res<Score>,
     timer: Res<GameTimer>,
     player_info: Res<PlayerInfo>,
+    recorder: Res<MatchRecorder>,
     game_session: Option<Res<GameSession>>,
 ) {
     for event in match_events.read() {
@@ -306,13 +374,12 @@ pub fn send_game_result_system(
             continue;
         }
 
-        let session_id = match &game_session {
-            Some(session) => session.session_id.clone(),
-            None => {
-                println!("⚠️ No game session found, creating temporary ID");
-                uuid::Uuid::new_v4().to_string()
-            }
+        // The backend only accepts results for a session it issued, signed with its key
+        let Some(game_session) = &game_session else {
+            println!("⚠️ No game session was issued for this match, result can't be submitted");
+            continue;
         };
+        let session_id = game_session.session_id.clone();
 
         // Determine match result from LOCAL PLAYER's perspective
         // Local player is left team (blue), AI opponent is right team (red)
@@ -333,16 +400,16 @@ pub fn send_game_result_system(
             session_id,                          // Unique game session ID
         ).with_game_mode("single_player_vs_ai".to_string());
 
-        // Send game result directly to backend via HTTP
         println!("🎮 Sending game result to backend: {game_result:?}");
 
-        // Spawn task to submit game result
-        let game_result_clone = game_result.clone();
+        let transcript = recorder
+            .transcript(game_result.game_session_id.clone(), &score, game_result.duration_seconds)
+            .sign(&game_session.session_key);
 
         // Send game result via PostMessage for iframe communication
         #[cfg(target_arch = "wasm32")]
         {
-            if let Err(e) = send_game_result_via_postmessage(&game_result_clone) {
+            if let Err(e) = send_game_result_via_postmessage(&game_result, &transcript) {
                 web_sys::console::log_1(&format!("Failed to send game result via PostMessage: {:?}", e).into());
             }
         }
@@ -350,7 +417,7 @@ pub fn send_game_result_system(
         #[cfg(not(target_arch = "wasm32"))]
         {
             // For native builds, just log (could add file logging here)
-            println!("Game result: {game_result_clone:?}");
+            println!("Game result: {game_result:?}, transcript: {transcript:?}");
         }
     }
 }
@@ -366,17 +433,29 @@ pub fn setup_player_info(mut player_info: ResMut<PlayerInfo>) {
     }
 }
 
-/// System that runs when entering InGame state - creates a new game session
-pub fn create_game_session(
+/// System that runs when entering InGame state - asks the page for a server session
+pub fn request_game_session(
     mut commands: Commands,
     scoring_config: Res<ScoringConfig>,
 ) {
-    let session = GameSession::new();
+    // The previous match's session is spent
+    commands.remove_resource::<GameSession>();
+    commands.insert_resource(MatchRecorder::default());
+
+    // The server checks the rules we're about to play by
+    let config_hash = MatchRules {
+        winning_score: scoring_config.winning_score,
+        match_duration_seconds: scoring_config.match_duration_seconds,
+    }
+    .config_hash();
 
     #[cfg(not(target_arch = "wasm32"))]
-    println!("🎲 New game session created: {}", session.session_id);
+    println!("🎲 Requesting a game session for rules {config_hash}");
 
-    commands.insert_resource(session);
+    #[cfg(target_arch = "wasm32")]
+    if let Err(e) = send_session_request_via_postmessage(&config_hash) {
+        web_sys::console::log_1(&format!("Failed to request a game session via PostMessage: {:?}", e).into());
+    }
 
     // Initialize timer with configured duration
     let timer = GameTimer::new(scoring_config.match_duration_seconds);
@@ -395,16 +474,19 @@ impl Plugin for ScoringPlugin {
             .init_resource::<ScoreNotifications>()
             .init_resource::<GameTimer>()
             .init_resource::<PlayerInfo>()
+            .init_resource::<MatchRecorder>()
             // Add events
             .add_event::<GoalScored>()
             .add_event::<MatchFinished>()
             .add_event::<PlayerReset>()
             // Add systems
-            .add_systems(Startup, setup_player_info)
-            .add_systems(OnEnter(AppState::InGame), create_game_session)
+            .add_systems(Startup, (setup_player_info, listen_for_bootstrap))
+            .add_systems(OnEnter(AppState::InGame), request_game_session)
             .add_systems(
                 Update,
                 (
+                    apply_bootstrap,
+                    record_match_input,
                     handle_goal_scored,
                     reset_score_system,
                     game_timer_system,
@@ -417,42 +499,110 @@ impl Plugin for ScoringPlugin {
 
 // ================= PostMessage Communication =================
 
+/// Bootstrap configs posted by the page, waiting for `apply_bootstrap`
+static RECEIVED_BOOTSTRAPS: std::sync::Mutex<Vec<GameBootstrap>> = std::sync::Mutex::new(Vec::new());
+
+fn take_received_bootstraps() -> Vec<GameBootstrap> {
+    RECEIVED_BOOTSTRAPS
+        .lock()
+        .map(|mut bootstraps| std::mem::take(&mut *bootstraps))
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(serde::Deserialize)]
+struct BootstrapMessage {
+    #[serde(rename = "type")]
+    message_type: String,
+    config: GameBootstrap,
+}
+
+/// Queue `bootstrap` messages from the page for the ECS to pick up
+#[cfg(target_arch = "wasm32")]
+pub fn listen_for_bootstrap() {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let on_message = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(|event: web_sys::MessageEvent| {
+        let Some(text) = event.data().as_string() else {
+            return;
+        };
+        let Ok(message) = serde_json::from_str::<BootstrapMessage>(&text) else {
+            return;
+        };
+        if message.message_type == "bootstrap" {
+            if let Ok(mut bootstraps) = RECEIVED_BOOTSTRAPS.lock() {
+                bootstraps.push(message.config);
+            }
+        }
+    });
+
+    if window
+        .add_event_listener_with_callback("message", on_message.as_ref().unchecked_ref())
+        .is_ok()
+    {
+        // Lives as long as the page
+        on_message.forget();
+    }
+}
+
+/// Native builds have no page to issue sessions, so their results are only logged
+#[cfg(not(target_arch = "wasm32"))]
+pub fn listen_for_bootstrap() {}
+
 #[cfg(target_arch = "wasm32")]
-fn send_game_result_via_postmessage(game_result: &GameResult) -> Result<(), Box<dyn std::error::Error>> {
-    use wasm_bindgen::JsValue;
-    use serde_json;
-    use web_sys;
+fn send_session_request_via_postmessage(config_hash: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let message = serde_json::json!({
+        "type": "session_request",
+        "game_mode": "single_player_vs_ai",
+        "config_hash": config_hash,
+    });
+    post_to_parent(&message)
+}
 
-    // Create structured message for parent window
+#[cfg(target_arch = "wasm32")]
+fn post_to_parent(message: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+    let parent = web_sys::window()
+        .ok_or("No window object available")?
+        .parent()
+        .map_err(|_| "Failed to get parent window")?
+        .ok_or("No parent window available")?;
+
+    parent.post_message(&wasm_bindgen::JsValue::from_str(&message.to_string()), "*")
+        .map_err(|e| format!("PostMessage failed: {:?}", e))?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn send_game_result_via_postmessage(
+    game_result: &GameResult,
+    transcript: &shared::transcript::SignedTranscript,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Create structured message for parent window; `won` is null for a draw
     let message = serde_json::json!({
         "type": "game_result",
         "timestamp": chrono::Utc::now().timestamp(),
         "data": {
             "player_address": game_result.player_wallet_address,
             "player_username": game_result.player_username,
-            "won": matches!(game_result.player_result, shared::dto::game::MatchResult::Win),
+            "won": match game_result.player_result {
+                MatchResult::Win => Some(true),
+                MatchResult::Loss => Some(false),
+                MatchResult::Draw => None,
+            },
             "score_left": game_result.player_score,
             "score_right": game_result.opponent_score,
-            "match_duration_seconds": game_result.duration_seconds
+            "match_duration_seconds": game_result.duration_seconds,
+            "transcript": transcript
         }
     });
 
-    // Get window object and parent reference
-    let window = web_sys::window()
-        .ok_or("No window object available")?;
-
-    let parent = window.parent()
-        .map_err(|_| "Failed to get parent window")?
-        .ok_or("No parent window available")?;
-
-    // Convert message to JsValue
-    let js_message = JsValue::from_str(&message.to_string());
-
-    // Send message to parent window with wildcard origin (can be restricted for security)
-    parent.post_message(&js_message, "*")
-        .map_err(|e| format!("PostMessage failed: {:?}", e))?;
+    post_to_parent(&message)?;
 
     web_sys::console::log_1(&"✅ Game result sent to parent via PostMessage".into());
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -18,3 +18,31 @@ impl std::fmt::Display for UserType {
         }
     }
 }
+
+/// Query for a wallet sign-in challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthChallengeQuery {
+    pub account: String,
+}
+
+/// Challenge transaction for the wallet to countersign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthChallengeResponse {
+    /// Base64 XDR transaction envelope, already signed by the server
+    pub transaction: String,
+    pub network_passphrase: String,
+}
+
+/// A challenge countersigned by the wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthTokenRequest {
+    pub transaction: String,
+}
+
+/// Session token to send as `Authorization: Bearer <token>` on write routes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuthTokenResponse {
+    pub token: String,
+    pub wallet_address: String,
+    pub expires_at: String,
+}
@@ -27,10 +27,12 @@ impl<T> ApiResponse<T> {
 }
 
 /// Standard pagination parameters
+/// A `cursor` from a previous page takes precedence over `offset`
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PaginationParams {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    pub cursor: Option<String>,
 }
 
 impl Default for PaginationParams {
@@ -38,6 +40,7 @@ impl Default for PaginationParams {
         Self {
             limit: Some(20),
             offset: Some(0),
+            cursor: None,
         }
     }
 }
@@ -49,6 +52,9 @@ pub struct PaginatedResponse<T> {
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
+    /// Pass back as `cursor` for the page after this one; absent on the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 impl<T> PaginatedResponse<T> {
@@ -58,6 +64,12 @@ impl<T> PaginatedResponse<T> {
             total,
             limit,
             offset,
+            next_cursor: None,
         }
     }
-}
\ No newline at end of file
+
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self
+    }
+}
@@ -9,6 +9,14 @@ pub enum LeaderboardFunction {
     HasJoined { player: String },
     /// add_win(player: Address) -> u32
     AddWin { player: String },
+    /// record_match(player: Address, opponent: Option<Address>, session_id: String, score: u32, opponent_score: u32) -> u32
+    RecordMatch {
+        player: String,
+        opponent: Option<String>,
+        session_id: String,
+        score: u32,
+        opponent_score: u32,
+    },
     /// get_wins(player: Address) -> u32
     GetWins { player: String },
     /// get_my_wins(player: Address) -> u32
@@ -21,21 +29,139 @@ pub enum LeaderboardFunction {
     GetPlayerCount,
     /// get_player(player: Address) -> Option<Player>
     GetPlayer { player: String },
+    /// get_player_stats(player: Address) -> Option<PlayerStats>
+    GetPlayerStats { player: String },
+    /// get_match(session_id: String) -> Option<MatchRecord>
+    GetMatch { session_id: String },
+    /// start_season(id: u32, ends_at: u64)
+    StartSeason { id: u32, ends_at: u64 },
+    /// get_current_season() -> Option<Season>
+    GetCurrentSeason,
+    /// get_season_wins(season: u32, player: Address) -> u32
+    GetSeasonWins { season: u32, player: String },
+    /// get_leaderboard_for_season(season: u32, limit: u32) -> Vec<LeaderboardEntry>
+    GetLeaderboardForSeason { season: u32, limit: u32 },
+    /// get_rating(player: Address) -> u32
+    GetRating { player: String },
+    /// get_rating_leaderboard(limit: u32) -> Vec<RatingEntry>
+    GetRatingLeaderboard { limit: u32 },
+    /// set_admin(new_admin: Address)
+    SetAdmin { new_admin: String },
+    /// upgrade(new_wasm_hash: BytesN<32>), hash given as hex
+    Upgrade { new_wasm_hash: String },
+    /// migrate(start: u32, count: u32) -> u32
+    Migrate { start: u32, count: u32 },
+    /// get_version() -> u32
+    GetVersion,
+    /// bump_all(start: u32, count: u32) -> u32
+    BumpAll { start: u32, count: u32 },
+    /// create_tournament(admin: Address, name: String, max_players: u32, entry_deadline: u64) -> u32
+    CreateTournament { admin: String, name: String, max_players: u32, entry_deadline: u64 },
+    /// register(player: Address, tournament: u32)
+    Register { player: String, tournament: u32 },
+    /// report_result(tournament: u32, round: u32, winner: Address, loser: Address)
+    ReportResult { tournament: u32, round: u32, winner: String, loser: String },
+    /// get_bracket(tournament: u32) -> Vec<BracketMatch>
+    GetBracket { tournament: u32 },
+    /// get_tournament(tournament: u32) -> Option<Tournament>
+    GetTournament { tournament: u32 },
+    /// create_wager(player_one: Address, player_two: Address, token: Address, amount: i128) -> u32
+    CreateWager { player_one: String, player_two: String, token: String, amount: i128 },
+    /// accept_wager(id: u32)
+    AcceptWager { id: u32 },
+    /// settle_wager(id: u32, winner: Option<Address>)
+    SettleWager { id: u32, winner: Option<String> },
+    /// refund_wager(id: u32)
+    RefundWager { id: u32 },
+    /// get_wager(id: u32) -> Option<Wager>
+    GetWager { id: u32 },
+    /// get_achievements(player: Address) -> Vec<Badge>
+    GetAchievements { player: String },
+    /// get_players_page(cursor: u32, limit: u32) -> PlayerPage
+    GetPlayersPage { cursor: u32, limit: u32 },
+    /// get_wins_batch(players: Vec<Address>) -> Vec<u32>
+    GetWinsBatch { players: Vec<String> },
+    /// get_players_batch(players: Vec<Address>) -> Vec<Option<Player>>
+    GetPlayersBatch { players: Vec<String> },
+    /// set_profile(player: Address, display_name: String, avatar_id: u32, country: String)
+    SetProfile { player: String, display_name: String, avatar_id: u32, country: String },
+    /// get_profile(player: Address) -> Option<Profile>
+    GetProfile { player: String },
 }
 
 impl LeaderboardFunction {
+    /// Whether the function only reads contract state, so it can be answered by simulation
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            LeaderboardFunction::HasJoined { .. }
+                | LeaderboardFunction::GetWins { .. }
+                | LeaderboardFunction::GetMyWins { .. }
+                | LeaderboardFunction::GetAllPlayers
+                | LeaderboardFunction::GetLeaderboard { .. }
+                | LeaderboardFunction::GetPlayerCount
+                | LeaderboardFunction::GetPlayer { .. }
+                | LeaderboardFunction::GetPlayerStats { .. }
+                | LeaderboardFunction::GetMatch { .. }
+                | LeaderboardFunction::GetCurrentSeason
+                | LeaderboardFunction::GetSeasonWins { .. }
+                | LeaderboardFunction::GetLeaderboardForSeason { .. }
+                | LeaderboardFunction::GetRating { .. }
+                | LeaderboardFunction::GetRatingLeaderboard { .. }
+                | LeaderboardFunction::GetVersion
+                | LeaderboardFunction::GetBracket { .. }
+                | LeaderboardFunction::GetTournament { .. }
+                | LeaderboardFunction::GetWager { .. }
+                | LeaderboardFunction::GetAchievements { .. }
+                | LeaderboardFunction::GetPlayersPage { .. }
+                | LeaderboardFunction::GetWinsBatch { .. }
+                | LeaderboardFunction::GetPlayersBatch { .. }
+                | LeaderboardFunction::GetProfile { .. }
+        )
+    }
+
     /// Get the function name as it appears in the contract
     pub fn name(&self) -> &'static str {
         match self {
             LeaderboardFunction::Join { .. } => "join",
             LeaderboardFunction::HasJoined { .. } => "has_joined",
             LeaderboardFunction::AddWin { .. } => "add_win",
+            LeaderboardFunction::RecordMatch { .. } => "record_match",
             LeaderboardFunction::GetWins { .. } => "get_wins",
             LeaderboardFunction::GetMyWins { .. } => "get_my_wins",
             LeaderboardFunction::GetAllPlayers => "get_all_players",
             LeaderboardFunction::GetLeaderboard { .. } => "get_leaderboard",
             LeaderboardFunction::GetPlayerCount => "get_player_count",
             LeaderboardFunction::GetPlayer { .. } => "get_player",
+            LeaderboardFunction::GetPlayerStats { .. } => "get_player_stats",
+            LeaderboardFunction::GetMatch { .. } => "get_match",
+            LeaderboardFunction::StartSeason { .. } => "start_season",
+            LeaderboardFunction::GetCurrentSeason => "get_current_season",
+            LeaderboardFunction::GetSeasonWins { .. } => "get_season_wins",
+            LeaderboardFunction::GetLeaderboardForSeason { .. } => "get_leaderboard_for_season",
+            LeaderboardFunction::GetRating { .. } => "get_rating",
+            LeaderboardFunction::GetRatingLeaderboard { .. } => "get_rating_leaderboard",
+            LeaderboardFunction::SetAdmin { .. } => "set_admin",
+            LeaderboardFunction::Upgrade { .. } => "upgrade",
+            LeaderboardFunction::Migrate { .. } => "migrate",
+            LeaderboardFunction::GetVersion => "get_version",
+            LeaderboardFunction::BumpAll { .. } => "bump_all",
+            LeaderboardFunction::CreateTournament { .. } => "create_tournament",
+            LeaderboardFunction::Register { .. } => "register",
+            LeaderboardFunction::ReportResult { .. } => "report_result",
+            LeaderboardFunction::GetBracket { .. } => "get_bracket",
+            LeaderboardFunction::GetTournament { .. } => "get_tournament",
+            LeaderboardFunction::CreateWager { .. } => "create_wager",
+            LeaderboardFunction::AcceptWager { .. } => "accept_wager",
+            LeaderboardFunction::SettleWager { .. } => "settle_wager",
+            LeaderboardFunction::RefundWager { .. } => "refund_wager",
+            LeaderboardFunction::GetWager { .. } => "get_wager",
+            LeaderboardFunction::GetAchievements { .. } => "get_achievements",
+            LeaderboardFunction::GetPlayersPage { .. } => "get_players_page",
+            LeaderboardFunction::GetWinsBatch { .. } => "get_wins_batch",
+            LeaderboardFunction::GetPlayersBatch { .. } => "get_players_batch",
+            LeaderboardFunction::SetProfile { .. } => "set_profile",
+            LeaderboardFunction::GetProfile { .. } => "get_profile",
         }
     }
 
@@ -45,12 +171,42 @@ impl LeaderboardFunction {
             LeaderboardFunction::Join { .. } => "join(player: Address) -> bool",
             LeaderboardFunction::HasJoined { .. } => "has_joined(player: Address) -> bool",
             LeaderboardFunction::AddWin { .. } => "add_win(player: Address) -> u32",
+            LeaderboardFunction::RecordMatch { .. } => "record_match(player: Address, opponent: Option<Address>, session_id: String, score: u32, opponent_score: u32) -> u32",
             LeaderboardFunction::GetWins { .. } => "get_wins(player: Address) -> u32",
             LeaderboardFunction::GetMyWins { .. } => "get_my_wins(player: Address) -> u32",
             LeaderboardFunction::GetAllPlayers => "get_all_players() -> Vec<Address>",
             LeaderboardFunction::GetLeaderboard { .. } => "get_leaderboard(limit: u32) -> Vec<LeaderboardEntry>",
             LeaderboardFunction::GetPlayerCount => "get_player_count() -> u32",
             LeaderboardFunction::GetPlayer { .. } => "get_player(player: Address) -> Option<Player>",
+            LeaderboardFunction::GetPlayerStats { .. } => "get_player_stats(player: Address) -> Option<PlayerStats>",
+            LeaderboardFunction::GetMatch { .. } => "get_match(session_id: String) -> Option<MatchRecord>",
+            LeaderboardFunction::StartSeason { .. } => "start_season(id: u32, ends_at: u64)",
+            LeaderboardFunction::GetCurrentSeason => "get_current_season() -> Option<Season>",
+            LeaderboardFunction::GetSeasonWins { .. } => "get_season_wins(season: u32, player: Address) -> u32",
+            LeaderboardFunction::GetLeaderboardForSeason { .. } => "get_leaderboard_for_season(season: u32, limit: u32) -> Vec<LeaderboardEntry>",
+            LeaderboardFunction::GetRating { .. } => "get_rating(player: Address) -> u32",
+            LeaderboardFunction::GetRatingLeaderboard { .. } => "get_rating_leaderboard(limit: u32) -> Vec<RatingEntry>",
+            LeaderboardFunction::SetAdmin { .. } => "set_admin(new_admin: Address)",
+            LeaderboardFunction::Upgrade { .. } => "upgrade(new_wasm_hash: BytesN<32>)",
+            LeaderboardFunction::Migrate { .. } => "migrate(start: u32, count: u32) -> u32",
+            LeaderboardFunction::GetVersion => "get_version() -> u32",
+            LeaderboardFunction::BumpAll { .. } => "bump_all(start: u32, count: u32) -> u32",
+            LeaderboardFunction::CreateTournament { .. } => "create_tournament(admin: Address, name: String, max_players: u32, entry_deadline: u64) -> u32",
+            LeaderboardFunction::Register { .. } => "register(player: Address, tournament: u32)",
+            LeaderboardFunction::ReportResult { .. } => "report_result(tournament: u32, round: u32, winner: Address, loser: Address)",
+            LeaderboardFunction::GetBracket { .. } => "get_bracket(tournament: u32) -> Vec<BracketMatch>",
+            LeaderboardFunction::GetTournament { .. } => "get_tournament(tournament: u32) -> Option<Tournament>",
+            LeaderboardFunction::CreateWager { .. } => "create_wager(player_one: Address, player_two: Address, token: Address, amount: i128) -> u32",
+            LeaderboardFunction::AcceptWager { .. } => "accept_wager(id: u32)",
+            LeaderboardFunction::SettleWager { .. } => "settle_wager(id: u32, winner: Option<Address>)",
+            LeaderboardFunction::RefundWager { .. } => "refund_wager(id: u32)",
+            LeaderboardFunction::GetWager { .. } => "get_wager(id: u32) -> Option<Wager>",
+            LeaderboardFunction::GetAchievements { .. } => "get_achievements(player: Address) -> Vec<Badge>",
+            LeaderboardFunction::GetPlayersPage { .. } => "get_players_page(cursor: u32, limit: u32) -> PlayerPage",
+            LeaderboardFunction::GetWinsBatch { .. } => "get_wins_batch(players: Vec<Address>) -> Vec<u32>",
+            LeaderboardFunction::GetPlayersBatch { .. } => "get_players_batch(players: Vec<Address>) -> Vec<Option<Player>>",
+            LeaderboardFunction::SetProfile { .. } => "set_profile(player: Address, display_name: String, avatar_id: u32, country: String)",
+            LeaderboardFunction::GetProfile { .. } => "get_profile(player: Address) -> Option<Profile>",
         }
     }
 
@@ -59,13 +215,43 @@ impl LeaderboardFunction {
         match self {
             LeaderboardFunction::Join { .. } => "Join the leaderboard to start tracking wins",
             LeaderboardFunction::HasJoined { .. } => "Check if a player has joined the leaderboard",
-            LeaderboardFunction::AddWin { .. } => "Add a win for the player (requires authorization)",
+            LeaderboardFunction::AddWin { .. } => "Add a win for the player (requires oracle authorization)",
+            LeaderboardFunction::RecordMatch { .. } => "Record a finished match (requires oracle authorization)",
             LeaderboardFunction::GetWins { .. } => "Get total wins for a specific player",
             LeaderboardFunction::GetMyWins { .. } => "Get wins for the calling player",
             LeaderboardFunction::GetAllPlayers => "Get all players who have joined",
-            LeaderboardFunction::GetLeaderboard { .. } => "Get leaderboard sorted by wins",
+            LeaderboardFunction::GetLeaderboard { .. } => "Get leaderboard sorted by rating",
             LeaderboardFunction::GetPlayerCount => "Get total number of players",
             LeaderboardFunction::GetPlayer { .. } => "Get detailed player information",
+            LeaderboardFunction::GetPlayerStats { .. } => "Get wins, losses, draws and goals for a player",
+            LeaderboardFunction::GetMatch { .. } => "Get the recorded result of a match session",
+            LeaderboardFunction::StartSeason { .. } => "Close the current season and start a new one (requires oracle authorization)",
+            LeaderboardFunction::GetCurrentSeason => "Get the season currently in progress",
+            LeaderboardFunction::GetSeasonWins { .. } => "Get a player's wins within a season",
+            LeaderboardFunction::GetLeaderboardForSeason { .. } => "Get a season's leaderboard sorted by season wins",
+            LeaderboardFunction::GetRating { .. } => "Get a player's Elo rating",
+            LeaderboardFunction::GetRatingLeaderboard { .. } => "Get leaderboard sorted by Elo rating",
+            LeaderboardFunction::SetAdmin { .. } => "Hand the admin role to a new address (requires admin authorization)",
+            LeaderboardFunction::Upgrade { .. } => "Replace the contract code with uploaded WASM (requires admin authorization)",
+            LeaderboardFunction::Migrate { .. } => "Migrate a batch of players to the current layout version (requires admin authorization)",
+            LeaderboardFunction::GetVersion => "Get the storage layout version",
+            LeaderboardFunction::BumpAll { .. } => "Extend storage TTL for a batch of players (requires admin authorization)",
+            LeaderboardFunction::CreateTournament { .. } => "Create a single-elimination tournament you organize",
+            LeaderboardFunction::Register { .. } => "Register for a tournament before its entry deadline",
+            LeaderboardFunction::ReportResult { .. } => "Report a bracket match result (requires tournament admin authorization)",
+            LeaderboardFunction::GetBracket { .. } => "Get a tournament bracket round by round",
+            LeaderboardFunction::GetTournament { .. } => "Get tournament details and registered players",
+            LeaderboardFunction::CreateWager { .. } => "Challenge a player to a match staked with tokens held in escrow",
+            LeaderboardFunction::AcceptWager { .. } => "Accept a wager and escrow the matching stake",
+            LeaderboardFunction::SettleWager { .. } => "Pay out a wager to the match winner (requires oracle authorization)",
+            LeaderboardFunction::RefundWager { .. } => "Refund the stakes of a wager that timed out",
+            LeaderboardFunction::GetWager { .. } => "Get a wager and its escrow status",
+            LeaderboardFunction::GetAchievements { .. } => "Get the achievement badges a player has earned",
+            LeaderboardFunction::GetPlayersPage { .. } => "Get a page of players in join order",
+            LeaderboardFunction::GetWinsBatch { .. } => "Get wins for several players at once",
+            LeaderboardFunction::GetPlayersBatch { .. } => "Get several player records at once",
+            LeaderboardFunction::SetProfile { .. } => "Set your display name, avatar and country",
+            LeaderboardFunction::GetProfile { .. } => "Get a player's profile",
         }
     }
 
@@ -75,12 +261,42 @@ impl LeaderboardFunction {
             LeaderboardFunction::Join { .. } => "Join Leaderboard",
             LeaderboardFunction::HasJoined { .. } => "Check Joined",
             LeaderboardFunction::AddWin { .. } => "Add Win",
+            LeaderboardFunction::RecordMatch { .. } => "Record Match",
             LeaderboardFunction::GetWins { .. } => "Get Wins",
             LeaderboardFunction::GetMyWins { .. } => "Get My Wins",
             LeaderboardFunction::GetAllPlayers => "Get All Players",
             LeaderboardFunction::GetLeaderboard { .. } => "Get Leaderboard",
             LeaderboardFunction::GetPlayerCount => "Get Player Count",
             LeaderboardFunction::GetPlayer { .. } => "Get Player",
+            LeaderboardFunction::GetPlayerStats { .. } => "Get Player Stats",
+            LeaderboardFunction::GetMatch { .. } => "Get Match",
+            LeaderboardFunction::StartSeason { .. } => "Start Season",
+            LeaderboardFunction::GetCurrentSeason => "Get Current Season",
+            LeaderboardFunction::GetSeasonWins { .. } => "Get Season Wins",
+            LeaderboardFunction::GetLeaderboardForSeason { .. } => "Get Season Leaderboard",
+            LeaderboardFunction::GetRating { .. } => "Get Rating",
+            LeaderboardFunction::GetRatingLeaderboard { .. } => "Get Rating Leaderboard",
+            LeaderboardFunction::SetAdmin { .. } => "Set Admin",
+            LeaderboardFunction::Upgrade { .. } => "Upgrade Contract",
+            LeaderboardFunction::Migrate { .. } => "Migrate Storage",
+            LeaderboardFunction::GetVersion => "Get Version",
+            LeaderboardFunction::BumpAll { .. } => "Extend Storage TTL",
+            LeaderboardFunction::CreateTournament { .. } => "Create Tournament",
+            LeaderboardFunction::Register { .. } => "Register for Tournament",
+            LeaderboardFunction::ReportResult { .. } => "Report Tournament Result",
+            LeaderboardFunction::GetBracket { .. } => "Get Bracket",
+            LeaderboardFunction::GetTournament { .. } => "Get Tournament",
+            LeaderboardFunction::CreateWager { .. } => "Create Wager",
+            LeaderboardFunction::AcceptWager { .. } => "Accept Wager",
+            LeaderboardFunction::SettleWager { .. } => "Settle Wager",
+            LeaderboardFunction::RefundWager { .. } => "Refund Wager",
+            LeaderboardFunction::GetWager { .. } => "Get Wager",
+            LeaderboardFunction::GetAchievements { .. } => "Get Achievements",
+            LeaderboardFunction::GetPlayersPage { .. } => "Get Players Page",
+            LeaderboardFunction::GetWinsBatch { .. } => "Get Wins (Batch)",
+            LeaderboardFunction::GetPlayersBatch { .. } => "Get Players (Batch)",
+            LeaderboardFunction::SetProfile { .. } => "Set Profile",
+            LeaderboardFunction::GetProfile { .. } => "Get Profile",
         }
     }
 
@@ -90,12 +306,42 @@ impl LeaderboardFunction {
             LeaderboardFunction::Join { .. } => "🎯",
             LeaderboardFunction::HasJoined { .. } => "❓",
             LeaderboardFunction::AddWin { .. } => "🏆",
+            LeaderboardFunction::RecordMatch { .. } => "📝",
             LeaderboardFunction::GetWins { .. } => "📊",
             LeaderboardFunction::GetMyWins { .. } => "🏅",
             LeaderboardFunction::GetAllPlayers => "👥",
             LeaderboardFunction::GetLeaderboard { .. } => "🏆",
             LeaderboardFunction::GetPlayerCount => "🔢",
             LeaderboardFunction::GetPlayer { .. } => "👤",
+            LeaderboardFunction::GetPlayerStats { .. } => "📈",
+            LeaderboardFunction::GetMatch { .. } => "⚽",
+            LeaderboardFunction::StartSeason { .. } => "🗓️",
+            LeaderboardFunction::GetCurrentSeason => "📅",
+            LeaderboardFunction::GetSeasonWins { .. } => "🥇",
+            LeaderboardFunction::GetLeaderboardForSeason { .. } => "🏁",
+            LeaderboardFunction::GetRating { .. } => "⭐",
+            LeaderboardFunction::GetRatingLeaderboard { .. } => "🌟",
+            LeaderboardFunction::SetAdmin { .. } => "🛡️",
+            LeaderboardFunction::Upgrade { .. } => "⬆️",
+            LeaderboardFunction::Migrate { .. } => "🚚",
+            LeaderboardFunction::GetVersion => "🏷️",
+            LeaderboardFunction::BumpAll { .. } => "⏳",
+            LeaderboardFunction::CreateTournament { .. } => "🏟️",
+            LeaderboardFunction::Register { .. } => "📝",
+            LeaderboardFunction::ReportResult { .. } => "🥊",
+            LeaderboardFunction::GetBracket { .. } => "🗂️",
+            LeaderboardFunction::GetTournament { .. } => "🏆",
+            LeaderboardFunction::CreateWager { .. } => "💰",
+            LeaderboardFunction::AcceptWager { .. } => "🤝",
+            LeaderboardFunction::SettleWager { .. } => "⚖️",
+            LeaderboardFunction::RefundWager { .. } => "↩️",
+            LeaderboardFunction::GetWager { .. } => "🎲",
+            LeaderboardFunction::GetAchievements { .. } => "🎖️",
+            LeaderboardFunction::GetPlayersPage { .. } => "📄",
+            LeaderboardFunction::GetWinsBatch { .. } => "🧮",
+            LeaderboardFunction::GetPlayersBatch { .. } => "👥",
+            LeaderboardFunction::SetProfile { .. } => "🪪",
+            LeaderboardFunction::GetProfile { .. } => "🙂",
         }
     }
 }
@@ -105,6 +351,144 @@ impl LeaderboardFunction {
 pub struct ContractLeaderboardEntry {
     pub address: String,
     pub wins: u32,
+    pub rating: u32,
+    pub display_name: Option<String>,
+    pub avatar_id: Option<u32>,
+    pub country: Option<String>,
+}
+
+/// Rating leaderboard entry as returned by the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractRatingEntry {
+    pub address: String,
+    pub rating: u32,
+}
+
+/// Season information from the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractSeason {
+    pub id: u32,
+    pub started_at: u64,
+    pub ends_at: u64,
+}
+
+/// Tournament lifecycle as reported by the contract
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ContractTournamentStatus {
+    Registration,
+    InProgress,
+    Finished,
+}
+
+/// Tournament information from the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractTournament {
+    pub id: u32,
+    pub name: String,
+    pub admin: String,
+    pub max_players: u32,
+    pub entry_deadline: u64,
+    pub players: Vec<String>,
+    pub status: ContractTournamentStatus,
+    pub winner: Option<String>,
+}
+
+/// Single bracket match from the contract; players are None until decided
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractBracketMatch {
+    pub round: u32,
+    pub slot: u32,
+    pub player_a: Option<String>,
+    pub player_b: Option<String>,
+    pub winner: Option<String>,
+}
+
+/// Achievement ids awarded by the contract
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ContractAchievement {
+    FirstWin = 1,
+    TenWins = 2,
+    CleanSheet = 3,
+    Comeback = 4,
+    HatTrick = 5,
+}
+
+impl ContractAchievement {
+    pub const ALL: [ContractAchievement; 5] = [
+        ContractAchievement::FirstWin,
+        ContractAchievement::TenWins,
+        ContractAchievement::CleanSheet,
+        ContractAchievement::Comeback,
+        ContractAchievement::HatTrick,
+    ];
+
+    pub fn id(&self) -> u32 {
+        *self as u32
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|achievement| achievement.id() == id)
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ContractAchievement::FirstWin => "First Win",
+            ContractAchievement::TenWins => "Ten Wins",
+            ContractAchievement::CleanSheet => "Clean Sheet",
+            ContractAchievement::Comeback => "Comeback",
+            ContractAchievement::HatTrick => "Hat-Trick",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ContractAchievement::FirstWin => "Win a match",
+            ContractAchievement::TenWins => "Reach 10 wins",
+            ContractAchievement::CleanSheet => "Win a match without conceding",
+            ContractAchievement::Comeback => "Win the match straight after a loss",
+            ContractAchievement::HatTrick => "Score 3 or more goals in one match",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ContractAchievement::FirstWin => "🥇",
+            ContractAchievement::TenWins => "🔟",
+            ContractAchievement::CleanSheet => "🧤",
+            ContractAchievement::Comeback => "🔄",
+            ContractAchievement::HatTrick => "🎩",
+        }
+    }
+}
+
+/// Achievement badge as awarded by the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractBadge {
+    pub achievement: ContractAchievement,
+    pub awarded_at: u64,
+}
+
+/// Wager lifecycle as reported by the contract
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ContractWagerStatus {
+    Open,
+    Accepted,
+    Settled,
+    Refunded,
+}
+
+/// Token-staked head-to-head wager from the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractWager {
+    pub id: u32,
+    pub player_one: String,
+    pub player_two: String,
+    pub token: String,
+    pub amount: i128,
+    pub status: ContractWagerStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub winner: Option<String>,
 }
 
 /// Player information from the contract
@@ -112,7 +496,57 @@ pub struct ContractLeaderboardEntry {
 pub struct ContractPlayer {
     pub address: String,
     pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub goals_for: u32,
+    pub goals_against: u32,
+    pub rating: u32,
     pub joined_at: u64,
+    pub last_played: u64,
+    pub display_name: Option<String>,
+    pub avatar_id: Option<u32>,
+    pub country: Option<String>,
+}
+
+/// Player profile metadata from the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractProfile {
+    pub display_name: String,
+    pub avatar_id: u32,
+    pub country: String,
+}
+
+/// One page of players from the contract, in join order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractPlayerPage {
+    pub players: Vec<ContractPlayer>,
+    pub next_cursor: Option<u32>,
+    pub total: u32,
+}
+
+/// Aggregated player statistics from the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractPlayerStats {
+    pub address: String,
+    pub total_games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub goals_for: u32,
+    pub goals_against: u32,
+    pub rating: u32,
+    pub last_played: u64,
+}
+
+/// Match result as recorded by the contract
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractMatchRecord {
+    pub session_id: String,
+    pub player: String,
+    pub opponent: Option<String>,
+    pub score: u32,
+    pub opponent_score: u32,
+    pub played_at: u64,
 }
 
 /// XDR generation request for contract functions
@@ -200,6 +634,9 @@ pub struct ContractSubmitResponse {
     pub transaction_hash: Option<String>,
     pub result: Option<String>,
     pub message: String,
+    /// Id to poll /api/contract/transactions/{id} with once the transaction is queued
+    #[serde(default)]
+    pub operation_id: Option<String>,
 }
 
 impl ContractSubmitResponse {
@@ -209,6 +646,17 @@ impl ContractSubmitResponse {
             transaction_hash: Some(hash),
             result: Some(result),
             message,
+            operation_id: None,
+        }
+    }
+
+    pub fn queued(operation_id: String, message: String) -> Self {
+        Self {
+            success: true,
+            transaction_hash: None,
+            result: None,
+            message,
+            operation_id: Some(operation_id),
         }
     }
 
@@ -218,10 +666,107 @@ impl ContractSubmitResponse {
             transaction_hash: None,
             result: None,
             message,
+            operation_id: None,
         }
     }
 }
 
+/// Contract error names, indexed by error code - 1
+const CONTRACT_ERRORS: [&str; 18] = [
+    "AlreadyJoined",
+    "NotJoined",
+    "InvalidInput",
+    "NotInitialized",
+    "AlreadyInitialized",
+    "SessionAlreadyRecorded",
+    "InvalidSeason",
+    "AlreadyMigrated",
+    "TournamentNotFound",
+    "RegistrationClosed",
+    "TournamentFull",
+    "AlreadyRegistered",
+    "TournamentNotStarted",
+    "InvalidMatch",
+    "WagerNotFound",
+    "InvalidWagerState",
+    "WagerExpired",
+    "WagerNotExpired",
+];
+
+/// Name of a contract error code as declared in the contract's Error enum
+pub fn contract_error_name(code: u32) -> Option<&'static str> {
+    CONTRACT_ERRORS.get((code as usize).checked_sub(1)?).copied()
+}
+
+/// Lifecycle of a signed transaction submitted through the backend
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ContractTransactionStatus {
+    /// Queued, not yet sent to the network
+    Pending,
+    /// Accepted by RPC, waiting to be included in a ledger
+    Submitted { hash: String },
+    /// Included in a ledger and succeeded
+    Confirmed { hash: String, ledger: u32 },
+    /// Rejected or failed on-chain; contract_error is the contract's Error code if it raised one
+    Failed {
+        hash: Option<String>,
+        error: String,
+        contract_error: Option<u32>,
+    },
+}
+
+impl ContractTransactionStatus {
+    /// Whether the transaction has reached a state it won't leave
+    pub fn is_final(&self) -> bool {
+        matches!(self, ContractTransactionStatus::Confirmed { .. } | ContractTransactionStatus::Failed { .. })
+    }
+}
+
+/// Status of a submitted transaction, as returned by /api/contract/transactions/{id}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractTransactionResponse {
+    pub operation_id: String,
+    pub function: String,
+    pub status: ContractTransactionStatus,
+    pub updated_at: String,
+}
+
+/// Value returned by a read-only contract function
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "value")]
+pub enum ContractValue {
+    Bool(bool),
+    U32(u32),
+    Leaderboard(Vec<ContractLeaderboardEntry>),
+    Player(Option<ContractPlayer>),
+    Badges(Vec<ContractBadge>),
+    /// Any other return type, with structs as objects keyed by field name
+    Json(serde_json::Value),
+}
+
+/// Request to run a read-only contract function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractQueryRequest {
+    pub function: LeaderboardFunction,
+}
+
+impl ContractQueryRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.function.is_read_only() {
+            return Err(format!("{} changes contract state and can't be queried", self.function.name()));
+        }
+        Ok(())
+    }
+}
+
+/// Result of a read-only contract function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractQueryResponse {
+    pub function: String,
+    pub value: ContractValue,
+}
+
 /// Leaderboard query parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaderboardQuery {
@@ -250,6 +795,7 @@ pub struct LeaderboardEntryWithMetadata {
     pub rank: u32,
     pub address: String,
     pub wins: u32,
+    pub rating: Option<u32>,
     pub username: Option<String>, // From game database if available
     pub joined_at: Option<u64>,
 }
\ No newline at end of file
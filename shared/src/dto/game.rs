@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::transcript::SignedTranscript;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct GameResult {
@@ -77,12 +78,84 @@ pub struct StoreGameResultRequest {
     pub opponent_score: i32,
     pub duration_seconds: f32,
     pub game_mode: Option<String>,
+    /// Signed by the game with the key issued for `game_session_id`
+    pub transcript: SignedTranscript,
+}
+
+/// Start a match; sent on behalf of the game when it kicks off
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateGameSessionRequest {
+    pub game_mode: Option<String>,
+    /// `MatchRules::config_hash` of the rules the game is running
+    pub config_hash: String,
+}
+
+/// End a match that won't produce a result; storing a result finishes its session too
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateGameSessionRequest {
+    /// Only "finished"
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSessionResponse {
+    pub session_id: String,
+    /// Hex HMAC-SHA256 key the game signs this match's transcript with
+    pub session_key: String,
+    pub game_mode: String,
+    /// 'active', 'finished' or 'expired'
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// What the page hands the game for each match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameBootstrap {
+    pub session_id: String,
+    pub session_key: String,
+    pub player_username: String,
+    pub player_wallet_address: String,
 }
 
 #[derive(Debug, Serialize)]
 pub struct StoreGameResultResponse {
     pub game_id: i32,
     pub contract_xdr: Option<ContractXdrInfo>,
+    /// Queued record_match operation, signed by the backend's oracle key
+    #[serde(default)]
+    pub contract_operation_id: Option<String>,
+    /// Stored but held for review; no contract transaction is offered until it's cleared
+    pub under_review: bool,
+}
+
+/// Rules every finished match follows; the game's ScoringConfig and the backend's
+/// result validation both start from these
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MatchRules {
+    /// Goals that end the match early
+    pub winning_score: i32,
+    /// Length of a match that runs to the final whistle
+    pub match_duration_seconds: f32,
+}
+
+impl MatchRules {
+    /// Hex SHA-256 of the rules, so the backend can tell which ones a game is running
+    pub fn config_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let encoded = serde_json::to_vec(self).expect("rules serialize");
+        hex::encode(Sha256::digest(encoded))
+    }
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self {
+            winning_score: 5,
+            match_duration_seconds: 180.0, // 3 minutes
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,10 +186,65 @@ pub struct PlayerStats {
 #[derive(Debug, Deserialize)]
 pub struct PlayerGamesQuery {
     pub wallet_address: String,
-    pub limit: Option<i64>,
+    #[serde(flatten)]
+    pub filter: GameHistoryFilter,
 }
 
-#[derive(Debug, Serialize)]
+/// Order of a game history page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameSort {
+    #[default]
+    Newest,
+    Oldest,
+}
+
+impl GameSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameSort::Newest => "newest",
+            GameSort::Oldest => "oldest",
+        }
+    }
+}
+
+/// Filters for the game history endpoints; every field is optional
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameHistoryFilter {
+    /// "Win", "Loss" or "Draw"
+    pub result: Option<String>,
+    pub game_mode: Option<String>,
+    /// Games played at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Games played before this time
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sort: GameSort,
+}
+
+/// Position in a game history, after the game with this timestamp and id
+/// Encoded as `<microseconds since epoch>.<id>` so it round-trips through a query string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: i32,
+}
+
+impl GameCursor {
+    pub fn encode(&self) -> String {
+        format!("{}.{}", self.created_at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (micros, id) = cursor.split_once('.')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameInstance {
     pub id: i32,
     pub game_session_id: String,
@@ -130,11 +258,12 @@ pub struct GameInstance {
     pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LeaderboardEntry {
     pub rank: i64,
     pub username: String,
     pub wallet_address: String,
+    pub rating: Option<i32>, // On-chain rating, None for players who never joined the contract
     pub wins: i64,
     pub total_games: i64,
     pub win_rate: f64,
@@ -1,5 +1,6 @@
 pub mod domain;
 pub mod dto;
+pub mod transcript;
 
 pub use domain::*;
 pub use dto::*;
@@ -27,7 +27,7 @@ pub fn contract_section(props: &ContractProps) -> Html {
     let functions = get_all_leaderboard_functions();
 
     // State for selected dropdown value
-    let selected_dropdown_value = use_state(|| String::new());
+    let selected_dropdown_value = use_state(String::new);
 
     let on_dropdown_change = {
         let selected_dropdown_value = selected_dropdown_value.clone();
@@ -145,9 +145,6 @@ fn get_all_leaderboard_functions() -> Vec<LeaderboardFunction> {
         LeaderboardFunction::HasJoined {
             player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
         },
-        LeaderboardFunction::AddWin {
-            player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
-        },
         LeaderboardFunction::GetWins {
             player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
         },
@@ -159,8 +156,46 @@ fn get_all_leaderboard_functions() -> Vec<LeaderboardFunction> {
             limit: 10
         },
         LeaderboardFunction::GetPlayerCount,
+        LeaderboardFunction::GetPlayersPage {
+            cursor: 0,
+            limit: 20
+        },
         LeaderboardFunction::GetPlayer {
             player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
         },
+        LeaderboardFunction::GetPlayerStats {
+            player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
+        },
+        LeaderboardFunction::GetRating {
+            player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
+        },
+        LeaderboardFunction::GetRatingLeaderboard {
+            limit: 10
+        },
+        LeaderboardFunction::GetAchievements {
+            player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
+        },
+        LeaderboardFunction::SetProfile {
+            player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string(),
+            display_name: "Player".to_string(),
+            avatar_id: 0,
+            country: "".to_string()
+        },
+        LeaderboardFunction::GetProfile {
+            player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string()
+        },
+        LeaderboardFunction::Register {
+            player: "GCRBGOBUEZCZF5GIMWSE7MUTY22MGYUMFZY7NZNVPUEDB6MSJIPAJMBZ".to_string(),
+            tournament: 1
+        },
+        LeaderboardFunction::GetTournament {
+            tournament: 1
+        },
+        LeaderboardFunction::GetBracket {
+            tournament: 1
+        },
+        LeaderboardFunction::GetWager {
+            id: 1
+        },
     ]
 }
\ No newline at end of file
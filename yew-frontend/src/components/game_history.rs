@@ -0,0 +1,162 @@
+use yew::prelude::*;
+use web_sys::HtmlSelectElement;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use shared::dto::common::{PaginatedResponse, PaginationParams};
+use shared::dto::game::{GameHistoryFilter, GameInstance, GameSort};
+use crate::services::ApiClient;
+
+/// Games fetched per page
+const PAGE_SIZE: i64 = 10;
+
+#[derive(Properties, PartialEq)]
+pub struct GameHistoryProps {
+    /// Show this wallet's games, or everyone's recent games without one
+    pub wallet_address: Option<String>,
+}
+
+#[function_component(GameHistory)]
+pub fn game_history(props: &GameHistoryProps) -> Html {
+    let filter = use_state(GameHistoryFilter::default);
+    let games = use_state(Vec::<GameInstance>::new);
+    let total = use_state(|| 0i64);
+    let next_cursor = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+
+    // Start over from the first page whenever the wallet or the filter changes
+    {
+        let games = games.clone();
+        let total = total.clone();
+        let next_cursor = next_cursor.clone();
+        let error = error.clone();
+
+        use_effect_with((props.wallet_address.clone(), (*filter).clone()), move |(wallet_address, filter)| {
+            let (wallet_address, filter) = (wallet_address.clone(), filter.clone());
+            spawn_local(async move {
+                match fetch_page(wallet_address, filter, None).await {
+                    Ok(page) => {
+                        total.set(page.total);
+                        next_cursor.set(page.next_cursor);
+                        games.set(page.items);
+                        error.set(None);
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_load_more = {
+        let wallet_address = props.wallet_address.clone();
+        let filter = filter.clone();
+        let games = games.clone();
+        let next_cursor = next_cursor.clone();
+        let error = error.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            let Some(cursor) = (*next_cursor).clone() else {
+                return;
+            };
+            let (wallet_address, filter) = (wallet_address.clone(), (*filter).clone());
+            let (games, next_cursor, error) = (games.clone(), next_cursor.clone(), error.clone());
+
+            spawn_local(async move {
+                match fetch_page(wallet_address, filter, Some(cursor)).await {
+                    Ok(page) => {
+                        let mut loaded = (*games).clone();
+                        loaded.extend(page.items);
+                        games.set(loaded);
+                        next_cursor.set(page.next_cursor);
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+        })
+    };
+
+    let on_result_change = {
+        let filter = filter.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target().unwrap().dyn_into::<HtmlSelectElement>().unwrap();
+            let result = Some(select.value()).filter(|value| !value.is_empty());
+            filter.set(GameHistoryFilter { result, ..(*filter).clone() });
+        })
+    };
+
+    let on_sort_change = {
+        let filter = filter.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target().unwrap().dyn_into::<HtmlSelectElement>().unwrap();
+            let sort = if select.value() == "oldest" { GameSort::Oldest } else { GameSort::Newest };
+            filter.set(GameHistoryFilter { sort, ..(*filter).clone() });
+        })
+    };
+
+    html! {
+        <div class="game-history">
+            <h2>{if props.wallet_address.is_some() { "🎮 Your Matches" } else { "🎮 Recent Matches" }}</h2>
+
+            <div class="game-history-filters">
+                <select onchange={on_result_change}>
+                    <option value="" selected={filter.result.is_none()}>{"All results"}</option>
+                    {for ["Win", "Loss", "Draw"].iter().map(|result| html! {
+                        <option value={*result} selected={filter.result.as_deref() == Some(*result)}>{*result}</option>
+                    })}
+                </select>
+                <select onchange={on_sort_change}>
+                    <option value="newest" selected={filter.sort == GameSort::Newest}>{"Newest first"}</option>
+                    <option value="oldest" selected={filter.sort == GameSort::Oldest}>{"Oldest first"}</option>
+                </select>
+                <span>{format!("{} matches", *total)}</span>
+            </div>
+
+            {if let Some(ref e) = *error {
+                html! { <p class="error">{format!("❌ Failed to load matches: {}", e)}</p> }
+            } else {
+                html! {}
+            }}
+
+            <table class="leaderboard">
+                <thead>
+                    <tr>
+                        <th>{"Played"}</th>
+                        <th>{"Player"}</th>
+                        <th>{"Result"}</th>
+                        <th>{"Score"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {games.iter().map(|game| html! {
+                        <tr key={game.id}>
+                            <td>{game.created_at.map(|at| at.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default()}</td>
+                            <td class="username">{&game.player_username}</td>
+                            <td>{&game.player_result}</td>
+                            <td>{format!("{} - {}", game.player_score, game.opponent_score)}</td>
+                        </tr>
+                    }).collect::<Html>()}
+                </tbody>
+            </table>
+
+            {if next_cursor.is_some() {
+                html! {
+                    <button class="btn btn-secondary" onclick={on_load_more}>{"Load more"}</button>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+async fn fetch_page(
+    wallet_address: Option<String>,
+    filter: GameHistoryFilter,
+    cursor: Option<String>,
+) -> Result<PaginatedResponse<GameInstance>, String> {
+    let page = PaginationParams { limit: Some(PAGE_SIZE), offset: None, cursor };
+    match wallet_address {
+        Some(wallet_address) => ApiClient::new().player_games(&wallet_address, &filter, &page).await,
+        None => ApiClient::new().recent_games(&filter, &page).await,
+    }
+}
@@ -1,5 +1,7 @@
 pub mod navbar;
 pub mod contract;
+pub mod game_history;
 
 pub use navbar::*;
-pub use contract::*;
\ No newline at end of file
+pub use contract::*;
+pub use game_history::*;
\ No newline at end of file
@@ -5,19 +5,37 @@ use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
 use serde::{Deserialize, Serialize};
 use gloo::events::EventListener;
+use crate::services::{with_auth, ApiClient};
 use crate::wallet::{connect_wallet, is_freighter_available, sign_transaction};
 use shared::dto::contract::{ContractSubmitRequest, ContractSubmitResponse, LeaderboardFunction};
+use shared::dto::game::{CreateGameSessionRequest, GameBootstrap};
+use shared::transcript::SignedTranscript;
+use std::{cell::RefCell, rc::Rc};
 // Removed unused import: use crate::soroban::complete_join_flow;
 
+/// Origin the game iframe is served from; only its messages are trusted
+const GAME_ORIGIN: &str = "http://localhost:3000";
+/// Game page loaded into the iframe, under GAME_ORIGIN
+const GAME_URL: &str = "http://localhost:3000/game/index.html";
 
 #[derive(Debug, Deserialize)]
 struct GameResultMessage {
     #[serde(rename = "type")]
     message_type: String,
+    #[allow(dead_code)]
     timestamp: i64,
     data: GameResultData,
 }
 
+/// Sent by the game when a match kicks off; answered with a bootstrap for a new server session
+#[derive(Debug, Deserialize)]
+struct SessionRequestMessage {
+    #[serde(rename = "type")]
+    message_type: String,
+    #[serde(flatten)]
+    request: CreateGameSessionRequest,
+}
+
 
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +48,7 @@ struct GameResultData {
     score_left: i32,
     score_right: i32,
     match_duration_seconds: f64,
+    transcript: SignedTranscript,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -42,6 +61,7 @@ struct StoreGameResultRequest {
     opponent_score: i32,
     duration_seconds: f32,
     game_mode: String,
+    transcript: SignedTranscript,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -49,6 +69,10 @@ struct StoreGameResultResponse {
     #[allow(dead_code)]
     game_id: i32,
     contract_xdr: Option<ContractXdrInfo>,
+    #[serde(default)]
+    contract_operation_id: Option<String>,
+    #[serde(default)]
+    under_review: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -74,7 +98,8 @@ pub fn game_page() -> Html {
     let wallet_address = use_state(|| None::<String>);
     let loading = use_state(|| true);
     let error_message = use_state(|| None::<String>);
-    let pending_xdr = use_state(|| None::<ContractXdrInfo>);
+    // Transaction offered to the player to sign, with the call it makes
+    let pending_xdr = use_state(|| None::<(LeaderboardFunction, ContractXdrInfo)>);
     let signing_transaction = use_state(|| false);
     let join_status_checked = use_state(|| false);
     let needs_to_join = use_state(|| false);
@@ -83,6 +108,8 @@ pub fn game_page() -> Html {
     let show_game = use_state(|| false);
     let contract_functions_visible = use_state(|| false);
     let auto_loading_game = use_state(|| false);
+    // Server session of the match in progress, until its result is stored
+    let active_session = use_mut_ref(|| None::<String>);
 
     // ===== On mount: load wallet =====
     {
@@ -193,11 +220,20 @@ pub fn game_page() -> Html {
         });
     }
 
+    // ===== Finish a match left without a result =====
+    {
+        let active_session = active_session.clone();
+        use_effect_with((), move |_| {
+            move || finish_session(&active_session)
+        });
+    }
+
     // ===== PostMessage listener for game results =====
     {
         let wallet_address = wallet_address.clone();
         let username = username.clone();
         let pending_xdr = pending_xdr.clone();
+        let active_session = active_session.clone();
 
         use_effect_with(wallet_address.clone(), move |wallet_addr| {
             if let Some(wallet) = &**wallet_addr {
@@ -209,6 +245,11 @@ pub fn game_page() -> Html {
                 let window = web_sys::window().unwrap();
                 let listener = EventListener::new(&window, "message", move |event| {
                     let message_event = event.dyn_ref::<MessageEvent>().unwrap();
+
+                    // Results and session requests (which hand out a signing key) only come from the game
+                    if message_event.origin() != GAME_ORIGIN {
+                        return;
+                    }
                     let data = message_event.data();
 
                     // Try to parse as string first, then as JSON
@@ -217,6 +258,8 @@ pub fn game_page() -> Html {
                             Ok(game_message) => {
                                 if game_message.message_type == "game_result" {
                                     console::log_1(&"🎮 Received game result from iframe!".into());
+                                    // Storing the result finishes the session
+                                    active_session.borrow_mut().take();
 
                                     // Transform data for backend API
                                     let player_result = match game_message.data.won {
@@ -226,7 +269,7 @@ pub fn game_page() -> Html {
                                     };
 
                                     let api_request = StoreGameResultRequest {
-                                        game_session_id: format!("session_{}", game_message.timestamp),
+                                        game_session_id: game_message.data.transcript.transcript.session_id.clone(),
                                         player_username: username_clone.clone(),
                                         player_wallet_address: wallet_clone.clone(),
                                         player_result,
@@ -234,17 +277,21 @@ pub fn game_page() -> Html {
                                         opponent_score: game_message.data.score_right,
                                         duration_seconds: game_message.data.match_duration_seconds as f32,
                                         game_mode: "single_player_vs_ai".to_string(),
+                                        transcript: game_message.data.transcript,
                                     };
 
                                     // Send to backend
                                     let api_request_clone = api_request.clone();
                                     let pending_xdr_clone = pending_xdr.clone();
+                                    let wallet_for_join = wallet_clone.clone();
                                     spawn_local(async move {
                                         match send_game_result_to_backend(api_request_clone).await {
                                             Ok(Some(xdr_info)) => {
                                                 console::log_1(&"✅ Game result sent to backend successfully!".into());
                                                 console::log_1(&format!("🏆 Win detected! XDR generated for {}", xdr_info.function_name).into());
-                                                pending_xdr_clone.set(Some(xdr_info));
+                                                // The only transaction offered for a result is joining the leaderboard
+                                                let join = LeaderboardFunction::Join { player: wallet_for_join };
+                                                pending_xdr_clone.set(Some((join, xdr_info)));
                                             },
                                             Ok(None) => {
                                                 console::log_1(&"✅ Game result sent to backend successfully! (No contract action needed)".into());
@@ -255,7 +302,20 @@ pub fn game_page() -> Html {
                                 }
                             }
                             Err(_) => {
-                                // Not a game result message, ignore
+                                if let Ok(session_request) = serde_json::from_str::<SessionRequestMessage>(&message_str) {
+                                    if session_request.message_type == "session_request" {
+                                        // A new match replaces one that never reported a result
+                                        finish_session(&active_session);
+                                        bootstrap_game(
+                                            session_request.request,
+                                            message_event.source(),
+                                            username_clone.clone(),
+                                            wallet_clone.clone(),
+                                            active_session.clone(),
+                                        );
+                                    }
+                                }
+                                // Anything else isn't for us, ignore
                             }
                         }
                     }
@@ -286,6 +346,11 @@ pub fn game_page() -> Html {
                     Ok(address) => {
                         wallet_address.set(Some(address.clone()));
                         let _ = LocalStorage::set("wallet_address", &address);
+
+                        // Results are only accepted from a signed-in wallet
+                        if let Err(e) = ApiClient::new().authenticate(&address).await {
+                            error_message.set(Some(format!("Wallet sign-in failed: {e}")));
+                        }
                     }
                     Err(e) => {
                         error_message.set(Some(format!("Failed to connect: {e}")));
@@ -296,18 +361,16 @@ pub fn game_page() -> Html {
         })
     };
 
-    // Sign transaction callback for wins
+    // Sign the pending transaction and submit it
     let on_sign_transaction = {
         let pending_xdr = pending_xdr.clone();
         let signing_transaction = signing_transaction.clone();
-        let wallet_address = wallet_address.clone();
 
         Callback::from(move |_: web_sys::MouseEvent| {
             let pending_xdr = pending_xdr.clone();
             let signing_transaction = signing_transaction.clone();
-            let wallet_address = wallet_address.clone();
 
-            if let Some(xdr_info) = (*pending_xdr).clone() {
+            if let Some((function, xdr_info)) = (*pending_xdr).clone() {
                 signing_transaction.set(true);
 
                 spawn_local(async move {
@@ -318,12 +381,6 @@ pub fn game_page() -> Html {
                             console::log_1(&"✅ Transaction signed successfully!".into());
 
                             // Submit signed transaction to backend
-                            // Create the LeaderboardFunction for add_win
-                            let wallet = (*wallet_address).clone().unwrap_or_default();
-                            let function = LeaderboardFunction::AddWin {
-                                player: wallet.clone()
-                            };
-
                             let submit_request = ContractSubmitRequest {
                                 signed_xdr,
                                 function,
@@ -460,7 +517,7 @@ pub fn game_page() -> Html {
                         match generate_contract_xdr(&wallet, function.clone()).await {
                             Ok(xdr_info) => {
                                 console::log_1(&format!("✅ {} XDR generated successfully!", function.display_name()).into());
-                                pending_xdr.set(Some(xdr_info));
+                                pending_xdr.set(Some((function, xdr_info)));
                             },
                             Err(e) => {
                                 console::log_1(&format!("❌ Failed to generate {} XDR: {e}", function.display_name()).into());
@@ -474,11 +531,6 @@ pub fn game_page() -> Html {
     };
 
     // Contract function callbacks
-    let on_add_win = create_contract_function_callback(
-        LeaderboardFunction::AddWin { player: wallet_address.as_ref().unwrap_or(&"".to_string()).clone() },
-        "Add a win to your record".to_string()
-    );
-
     let on_get_wins = create_contract_function_callback(
         LeaderboardFunction::GetWins { player: wallet_address.as_ref().unwrap_or(&"".to_string()).clone() },
         "Get your total wins".to_string()
@@ -624,14 +676,6 @@ pub fn game_page() -> Html {
                                     </button>
                                 </div>
                             </div>
-                            <div class="function-group">
-                                <h3>{"🏆 Action Functions"}</h3>
-                                <div class="function-buttons">
-                                    <button class="contract-btn action" onclick={on_add_win}>
-                                        {"🏆"} {"Add Win"}
-                                    </button>
-                                </div>
-                            </div>
                         </div>
                     </div>
                 }
@@ -645,7 +689,7 @@ pub fn game_page() -> Html {
                     <div class="game-section">
                         <div class="game-area">
                             <iframe
-                                src="http://localhost:3000/game/index.html"
+                                src={GAME_URL}
                                 id="stellar-heads-frame"
                                 title="Stellar Heads Game">
                             </iframe>
@@ -678,13 +722,13 @@ pub fn game_page() -> Html {
             }}
 
             // Show transaction signing modal if there's pending XDR
-            {if let Some(xdr_info) = (*pending_xdr).clone() {
+            {if let Some((_, xdr_info)) = (*pending_xdr).clone() {
                 html! {
                     <div class="modal-overlay">
                         <div class="modal-content">
                             <h2 class="modal-title">{"🏆 Victory Recorded!"}</h2>
                             <p class="modal-description">
-                                {"Congratulations! You won the match. Sign the transaction to record your win on the Stellar blockchain."}
+                                {"Congratulations! You won the match. Sign the transaction to join the leaderboard on the Stellar blockchain; the game server records your results there from then on."}
                             </p>
                             <div class="modal-info">
                                 <div class="info-row">
@@ -1141,12 +1185,62 @@ pub fn game_page() -> Html {
     }
 }
 
+/// Start a server session for the game's match and send the game its bootstrap config
+fn bootstrap_game(
+    request: CreateGameSessionRequest,
+    source: Option<js_sys::Object>,
+    player_username: String,
+    player_wallet_address: String,
+    active_session: Rc<RefCell<Option<String>>>,
+) {
+    let Some(game_window) = source.and_then(|source| source.dyn_into::<web_sys::Window>().ok()) else {
+        console::log_1(&"❌ Session request didn't come from a window".into());
+        return;
+    };
+
+    spawn_local(async move {
+        match ApiClient::new().start_session(&request).await {
+            Ok(session) => {
+                console::log_1(&format!("🎲 Game session {} started", session.session_id).into());
+                *active_session.borrow_mut() = Some(session.session_id.clone());
+
+                let message = serde_json::json!({
+                    "type": "bootstrap",
+                    "config": GameBootstrap {
+                        session_id: session.session_id,
+                        session_key: session.session_key,
+                        player_username,
+                        player_wallet_address,
+                    },
+                });
+                if let Err(e) = game_window.post_message(&message.to_string().into(), GAME_ORIGIN) {
+                    console::log_1(&format!("❌ Failed to pass bootstrap config to the game: {e:?}").into());
+                }
+            }
+            Err(e) => console::log_1(&format!("❌ Failed to start a game session: {e}").into()),
+        }
+    });
+}
+
+/// Finish the active session, if any, without a result
+fn finish_session(active_session: &Rc<RefCell<Option<String>>>) {
+    let Some(session_id) = active_session.borrow_mut().take() else {
+        return;
+    };
+
+    spawn_local(async move {
+        if let Err(e) = ApiClient::new().finish_session(&session_id).await {
+            console::log_1(&format!("❌ Failed to finish game session {session_id}: {e}").into());
+        }
+    });
+}
+
 async fn send_game_result_to_backend(request: StoreGameResultRequest) -> Result<Option<ContractXdrInfo>, Box<dyn std::error::Error>> {
-    use gloo::net::http::Request;
+    use gloo_net::http::Request;
 
     console::log_1(&format!("📡 Sending game result to backend: {request:?}").into());
 
-    let response = Request::post("http://localhost:3000/api/games/store")
+    let response = with_auth(Request::post("http://localhost:3000/api/games/store"))
         .header("Content-Type", "application/json")
         .json(&request)?
         .send()
@@ -1161,6 +1255,12 @@ async fn send_game_result_to_backend(request: StoreGameResultRequest) -> Result<
                 if api_response.success {
                     if let Some(data) = api_response.data {
                         console::log_1(&"✅ Backend confirmed game result received".into());
+                        if data.under_review {
+                            console::log_1(&"⏸️ Result is held for review before it counts on-chain".into());
+                        }
+                        if let Some(operation_id) = &data.contract_operation_id {
+                            console::log_1(&format!("⛓️ Result queued for the on-chain leaderboard as operation {operation_id}").into());
+                        }
                         Ok(data.contract_xdr)
                     } else {
                         Ok(None)
@@ -1184,11 +1284,11 @@ async fn send_game_result_to_backend(request: StoreGameResultRequest) -> Result<
 }
 
 async fn submit_signed_transaction(request: ContractSubmitRequest) -> Result<ContractSubmitResponse, Box<dyn std::error::Error>> {
-    use gloo::net::http::Request;
+    use gloo_net::http::Request;
 
-    console::log_1(&format!("📡 Submitting signed transaction to backend").into());
+    console::log_1(&"📡 Submitting signed transaction to backend".into());
 
-    let response = Request::post("http://localhost:3000/api/contract/submit-transaction")
+    let response = with_auth(Request::post("http://localhost:3000/api/contract/submit-transaction"))
         .header("Content-Type", "application/json")
         .json(&request)?
         .send()
@@ -1262,7 +1362,7 @@ async fn generate_contract_xdr(
     player_address: &str,
     function: LeaderboardFunction
 ) -> Result<ContractXdrInfo, Box<dyn std::error::Error>> {
-    use gloo::net::http::Request;
+    use gloo_net::http::Request;
 
     console::log_1(&format!("🎯 Generating XDR for function: {} with player: {}", function.name(), player_address).into());
 
@@ -1279,7 +1379,7 @@ async fn generate_contract_xdr(
         wallet_type: "Freighter".to_string(),
     };
 
-    let response = Request::post("http://localhost:3000/api/contract/generate-xdr")
+    let response = with_auth(Request::post("http://localhost:3000/api/contract/generate-xdr"))
         .header("Content-Type", "application/json")
         .json(&xdr_request)?
         .send()
@@ -1329,7 +1429,7 @@ async fn generate_join_xdr(player_address: &str) -> Result<ContractXdrInfo, Box<
 
 // Keep the old function for backward compatibility, but use the new generic one
 async fn _generate_join_xdr_old(player_address: &str) -> Result<ContractXdrInfo, Box<dyn std::error::Error>> {
-    use gloo::net::http::Request;
+    use gloo_net::http::Request;
 
     console::log_1(&format!("🎯 Generating join XDR for player: {}", player_address).into());
 
@@ -1348,7 +1448,7 @@ async fn _generate_join_xdr_old(player_address: &str) -> Result<ContractXdrInfo,
         wallet_type: "Freighter".to_string(),
     };
 
-    let response = Request::post("http://localhost:3000/api/contract/generate-xdr")
+    let response = with_auth(Request::post("http://localhost:3000/api/contract/generate-xdr"))
         .header("Content-Type", "application/json")
         .json(&join_request)?
         .send()
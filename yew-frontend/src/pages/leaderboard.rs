@@ -7,8 +7,9 @@ use shared::dto::contract::{
     LeaderboardFunction, ContractXdrRequest, ContractXdrResponse,
     ContractSubmitRequest, ContractSubmitResponse, LeaderboardResponse,
 };
+use crate::services::with_auth;
 use crate::wallet::{connect_wallet, sign_transaction, FreighterError, ConnectedWallet};
-use crate::components::ContractSection;
+use crate::components::{ContractSection, GameHistory};
 
 #[function_component(LeaderboardPage)]
 pub fn leaderboard_page() -> Html {
@@ -125,9 +126,7 @@ pub fn leaderboard_page() -> Html {
                             result_message.set(success_message);
 
                             // If it was a leaderboard-affecting function, reload the data
-                            if matches!((*selected_function).as_ref(),
-                                Some(LeaderboardFunction::Join { .. }) |
-                                Some(LeaderboardFunction::AddWin { .. })) {
+                            if matches!((*selected_function).as_ref(), Some(LeaderboardFunction::Join { .. })) {
 
                                 // Reload leaderboard after a short delay
                                 Timeout::new(2000, move || {
@@ -240,6 +239,7 @@ pub fn leaderboard_page() -> Html {
                                                     <th>{"Rank"}</th>
                                                     <th>{"Address"}</th>
                                                     <th>{"Username"}</th>
+                                                    <th>{"Rating"}</th>
                                                     <th>{"Wins"}</th>
                                                 </tr>
                                             </thead>
@@ -256,6 +256,9 @@ pub fn leaderboard_page() -> Html {
                                                             <td class="username">
                                                                 {entry.username.as_deref().unwrap_or("Anonymous")}
                                                             </td>
+                                                            <td class="rating">
+                                                                {entry.rating.map(|rating| rating.to_string()).unwrap_or_else(|| "-".to_string())}
+                                                            </td>
                                                             <td class="wins">{entry.wins}</td>
                                                         </tr>
                                                     }
@@ -275,6 +278,8 @@ pub fn leaderboard_page() -> Html {
                     }}
                 </div>
 
+                <GameHistory wallet_address={(*connected_wallet).as_ref().map(|wallet| wallet.address.clone())} />
+
                 <div class="contract-interaction">
                     <ContractSection
                         connected_wallet={(*connected_wallet).clone()}
@@ -295,10 +300,15 @@ fn update_function_with_wallet_address(function: LeaderboardFunction, wallet_add
     match function {
         LeaderboardFunction::Join { .. } => LeaderboardFunction::Join { player: wallet_address.to_string() },
         LeaderboardFunction::HasJoined { .. } => LeaderboardFunction::HasJoined { player: wallet_address.to_string() },
-        LeaderboardFunction::AddWin { .. } => LeaderboardFunction::AddWin { player: wallet_address.to_string() },
         LeaderboardFunction::GetWins { .. } => LeaderboardFunction::GetWins { player: wallet_address.to_string() },
         LeaderboardFunction::GetMyWins { .. } => LeaderboardFunction::GetMyWins { player: wallet_address.to_string() },
         LeaderboardFunction::GetPlayer { .. } => LeaderboardFunction::GetPlayer { player: wallet_address.to_string() },
+        LeaderboardFunction::GetPlayerStats { .. } => LeaderboardFunction::GetPlayerStats { player: wallet_address.to_string() },
+        LeaderboardFunction::GetRating { .. } => LeaderboardFunction::GetRating { player: wallet_address.to_string() },
+        LeaderboardFunction::GetAchievements { .. } => LeaderboardFunction::GetAchievements { player: wallet_address.to_string() },
+        LeaderboardFunction::Register { tournament, .. } => LeaderboardFunction::Register { player: wallet_address.to_string(), tournament },
+        LeaderboardFunction::SetProfile { display_name, avatar_id, country, .. } => LeaderboardFunction::SetProfile { player: wallet_address.to_string(), display_name, avatar_id, country },
+        LeaderboardFunction::GetProfile { .. } => LeaderboardFunction::GetProfile { player: wallet_address.to_string() },
         other => other,
     }
 }
@@ -330,7 +340,7 @@ async fn execute_contract_function(wallet: ConnectedWallet, function: Leaderboar
         wallet_type: Some(wallet.wallet_type.clone()),
     };
 
-    let xdr_response = Request::post("http://localhost:3000/api/contract/generate-xdr")
+    let xdr_response = with_auth(Request::post("http://localhost:3000/api/contract/generate-xdr"))
         .json(&xdr_request)
         .map_err(|e| format!("Failed to serialize request: {}", e))?
         .send()
@@ -364,7 +374,7 @@ async fn execute_contract_function(wallet: ConnectedWallet, function: Leaderboar
         wallet_type: Some(wallet.wallet_type),
     };
 
-    let submit_response = Request::post("/api/contract/submit-transaction")
+    let submit_response = with_auth(Request::post("/api/contract/submit-transaction"))
         .json(&submit_request)
         .map_err(|e| format!("Failed to serialize submit request: {}", e))?
         .send()
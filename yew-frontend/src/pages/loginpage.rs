@@ -118,8 +118,15 @@ pub fn login_page() -> Html {
                             wallet_address: address.clone(),
                         };
 
-                        web_sys::console::log_1(&"💾 Attempting to save user to backend...".into());
-                        match api_client.register_guest(guest).await {
+                        web_sys::console::log_1(&"🔐 Signing in with wallet...".into());
+                        let registered = match api_client.authenticate(&address).await {
+                            Ok(_) => {
+                                web_sys::console::log_1(&"💾 Attempting to save user to backend...".into());
+                                api_client.register_guest(guest).await
+                            }
+                            Err(e) => Err(format!("Wallet sign-in failed: {e}")),
+                        };
+                        match registered {
                             Ok(response) => {
                                 web_sys::console::log_1(&format!("✅ User saved to backend: {}", response.message).into());
 
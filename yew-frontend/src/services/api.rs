@@ -1,5 +1,33 @@
-use gloo_net::http::Request;
-use shared::dto::{auth::Guest, user::SignUpResponse, common::ApiResponse};
+use gloo::storage::{LocalStorage, Storage};
+use gloo_net::http::{Request, RequestBuilder};
+use serde::de::DeserializeOwned;
+use shared::dto::{
+    auth::{AuthChallengeResponse, AuthTokenRequest, AuthTokenResponse, Guest},
+    game::{
+        CreateGameSessionRequest, GameHistoryFilter, GameInstance, GameSessionResponse,
+        LeaderboardEntry, UpdateGameSessionRequest,
+    },
+    user::SignUpResponse,
+    common::{ApiResponse, PaginatedResponse, PaginationParams},
+};
+
+use crate::wallet::sign_transaction;
+
+/// LocalStorage key holding the wallet session token
+const AUTH_TOKEN_KEY: &str = "auth_token";
+
+/// Session token from the last wallet sign-in, if any
+pub fn auth_token() -> Option<String> {
+    LocalStorage::get(AUTH_TOKEN_KEY).ok()
+}
+
+/// Attach the wallet session token; write routes reject requests without one
+pub fn with_auth(request: RequestBuilder) -> RequestBuilder {
+    match auth_token() {
+        Some(token) => request.header("Authorization", &format!("Bearer {token}")),
+        None => request,
+    }
+}
 
 #[derive(Default)]
 pub struct ApiClient {
@@ -13,6 +41,126 @@ impl ApiClient {
         }
     }
 
+    fn url(&self, path: &str) -> String {
+        if self.base_url.is_empty() {
+            format!("http://localhost:3000{path}")
+        } else {
+            format!("{}{}", self.base_url, path)
+        }
+    }
+
+    /// Sign in by countersigning a server challenge with Freighter
+    /// The session token is kept in LocalStorage for later requests
+    pub async fn authenticate(&self, wallet_address: &str) -> Result<AuthTokenResponse, String> {
+        let response = Request::get(&self.url("/api/auth/challenge"))
+            .query([("account", wallet_address)])
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {e}"))?;
+        let challenge: AuthChallengeResponse = Self::parse(response).await?;
+
+        let signed = sign_transaction(&challenge.transaction, &challenge.network_passphrase)
+            .await
+            .map_err(|e| format!("Signing failed: {e}"))?;
+
+        let response = Request::post(&self.url("/api/auth/token"))
+            .json(&AuthTokenRequest { transaction: signed })
+            .map_err(|e| format!("Request error: {e}"))?
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {e}"))?;
+        let session: AuthTokenResponse = Self::parse(response).await?;
+
+        let _ = LocalStorage::set(AUTH_TOKEN_KEY, &session.token);
+        Ok(session)
+    }
+
+    /// Start a server-side session for a match the game is about to play
+    pub async fn start_session(&self, request: &CreateGameSessionRequest) -> Result<GameSessionResponse, String> {
+        let response = with_auth(Request::post(&self.url("/api/sessions")))
+            .json(request)
+            .map_err(|e| format!("Request error: {e}"))?
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {e}"))?;
+        Self::parse(response).await
+    }
+
+    /// Finish a session that won't get a result
+    pub async fn finish_session(&self, session_id: &str) -> Result<GameSessionResponse, String> {
+        let response = with_auth(Request::patch(&self.url(&format!("/api/sessions/{session_id}"))))
+            .json(&UpdateGameSessionRequest { status: "finished".to_string() })
+            .map_err(|e| format!("Request error: {e}"))?
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {e}"))?;
+        Self::parse(response).await
+    }
+
+    /// A page of one wallet's games; pass the previous page's `next_cursor` to continue
+    pub async fn player_games(
+        &self,
+        wallet_address: &str,
+        filter: &GameHistoryFilter,
+        page: &PaginationParams,
+    ) -> Result<PaginatedResponse<GameInstance>, String> {
+        let mut query = history_query(filter, page);
+        query.push(("wallet_address", wallet_address.to_string()));
+
+        let response = Request::get(&self.url("/api/games/player-games"))
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {e}"))?;
+        Self::parse(response).await
+    }
+
+    /// A page of everyone's games
+    pub async fn recent_games(
+        &self,
+        filter: &GameHistoryFilter,
+        page: &PaginationParams,
+    ) -> Result<PaginatedResponse<GameInstance>, String> {
+        let response = Request::get(&self.url("/api/games/recent"))
+            .query(history_query(filter, page))
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {e}"))?;
+        Self::parse(response).await
+    }
+
+    /// A page of the leaderboard; ranks are global, so it pages by offset only
+    #[allow(dead_code)]
+    pub async fn leaderboard(&self, page: &PaginationParams) -> Result<PaginatedResponse<LeaderboardEntry>, String> {
+        let response = Request::get(&self.url("/api/games/leaderboard"))
+            .query(page_query(page))
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {e}"))?;
+        Self::parse(response).await
+    }
+
+    async fn parse<T: DeserializeOwned>(response: gloo_net::http::Response) -> Result<T, String> {
+        if response.ok() {
+            let api_response: ApiResponse<T> = response
+                .json()
+                .await
+                .map_err(|e| format!("Response parse error: {e}"))?;
+
+            if api_response.success {
+                api_response.data
+                    .ok_or_else(|| "No data in successful response".to_string())
+            } else {
+                Err(api_response.message)
+            }
+        } else {
+            match response.json::<ApiResponse<()>>().await {
+                Ok(error_response) => Err(error_response.message),
+                Err(_) => Err(format!("HTTP error: {}", response.status())),
+            }
+        }
+    }
+
     pub async fn register_guest(&self, guest: Guest) -> Result<SignUpResponse, String> {
         let url = if self.base_url.is_empty() {
             "http://localhost:3000/api/auth/register-guest".to_string()
@@ -20,7 +168,7 @@ impl ApiClient {
             format!("{}/api/auth/register-guest", self.base_url)
         };
 
-        let response = Request::post(&url)
+        let response = with_auth(Request::post(&url))
             .header("content-type", "application/json")
             .json(&guest)
             .map_err(|e| format!("Request error: {e}"))?
@@ -48,4 +196,38 @@ impl ApiClient {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Query string pairs for the set paging fields
+fn page_query(page: &PaginationParams) -> Vec<(&'static str, String)> {
+    let mut query = Vec::new();
+    if let Some(limit) = page.limit {
+        query.push(("limit", limit.to_string()));
+    }
+    if let Some(offset) = page.offset {
+        query.push(("offset", offset.to_string()));
+    }
+    if let Some(cursor) = &page.cursor {
+        query.push(("cursor", cursor.clone()));
+    }
+    query
+}
+
+/// Query string pairs for the set filter and paging fields
+fn history_query(filter: &GameHistoryFilter, page: &PaginationParams) -> Vec<(&'static str, String)> {
+    let mut query = page_query(page);
+    if let Some(result) = &filter.result {
+        query.push(("result", result.clone()));
+    }
+    if let Some(game_mode) = &filter.game_mode {
+        query.push(("game_mode", game_mode.clone()));
+    }
+    if let Some(from) = filter.from {
+        query.push(("from", from.to_rfc3339()));
+    }
+    if let Some(to) = filter.to {
+        query.push(("to", to.to_rfc3339()));
+    }
+    query.push(("sort", filter.sort.as_str().to_string()));
+    query
+}
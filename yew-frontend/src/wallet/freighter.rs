@@ -1,7 +1,7 @@
-/// Idiomatic Freighter wallet integration using direct WASM bindings
-///
-/// This module provides a clean, type-safe interface to the Freighter wallet
-/// using proper WASM bindings and robust error handling.
+//! Idiomatic Freighter wallet integration using direct WASM bindings
+//!
+//! This module provides a clean, type-safe interface to the Freighter wallet
+//! using proper WASM bindings and robust error handling.
 
 use js_sys::{Function, Promise, Reflect};
 use std::fmt;
@@ -99,8 +99,8 @@ pub async fn is_freighter_available() -> bool {
         .unwrap_or(JsValue::UNDEFINED);
 
     if is_connected.is_function() {
-        if let Some(func) = is_connected.dyn_into::<Function>().ok() {
-            if let Some(promise) = func.call0(&api).ok() {
+        if let Ok(func) = is_connected.dyn_into::<Function>() {
+            if let Ok(promise) = func.call0(&api) {
                 if let Ok(promise) = promise.dyn_into::<Promise>() {
                     if let Ok(result) = JsFuture::from(promise).await {
                         if let Ok(obj) = result.dyn_into::<js_sys::Object>() {